@@ -0,0 +1,122 @@
+// Off-chain timings for the account encoding and instruction parsing.
+//
+// These run natively with `cargo bench` and say nothing about compute units;
+// `tests/compute_budget.rs` under `cargo test-sbf` covers on-chain cost.
+
+use std::hint::black_box;
+
+use borsh::BorshSerialize;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use movie_review_program::{
+    instruction::{bench_review_instruction_unpack, AddMovieReviewPayload},
+    MovieAccountState,
+    MovieInstruction,
+};
+
+use solana_program::{borsh1::try_from_slice_unchecked, pubkey::{Pubkey, MAX_SEED_LEN}};
+
+fn review(title_len: usize, description_len: usize, tight_size: bool) -> MovieAccountState {
+    MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        version: MovieAccountState::VERSION,
+        reviewer: Pubkey::new_unique(),
+        rating: 4,
+        language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+        watched_at: 0,
+        created_at: 0,
+        reactions: [0; MovieAccountState::REACTION_KINDS],
+        comment_moderation: MovieAccountState::COMMENTS_OPEN,
+        reward_minted: true,
+        tight_size,
+        is_sponsored: false,
+        title: "a".repeat(title_len),
+        description: "a".repeat(description_len),
+        poster_uri: None,
+        tags: Vec::new(),
+    }
+}
+
+// Small and medium reviews, and one whose description fills `LEN`
+fn review_sizes() -> [(&'static str, MovieAccountState); 3] {
+    let max_description_len = MovieAccountState::LEN
+        - MovieAccountState::get_account_size("a".repeat(MAX_SEED_LEN), String::new(), None, Vec::new()).unwrap();
+
+    [
+        ("small", review(8, 16, false)),
+        ("medium", review(16, 400, false)),
+        ("max", review(MAX_SEED_LEN, max_description_len, false)),
+    ]
+}
+
+fn bench_review_account(c: &mut Criterion) {
+    let mut group = c.benchmark_group("review_account");
+
+    for (size, review) in review_sizes() {
+        group.bench_with_input(BenchmarkId::new("serialize", size), &review, |b, review| {
+            let mut data = vec![0; MovieAccountState::LEN];
+
+            b.iter(|| review.serialize(&mut &mut data[..]).unwrap());
+        });
+
+        let mut data = vec![0; MovieAccountState::LEN];
+        review.serialize(&mut &mut data[..]).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("deserialize", size), &data, |b, data| {
+            b.iter(|| try_from_slice_unchecked::<MovieAccountState>(black_box(data)).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_unpack(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unpack");
+
+    for (size, review) in review_sizes() {
+        let data = borsh::to_vec(&(0u8, AddMovieReviewPayload::new(
+            review.title,
+            review.rating,
+            review.description,
+        ))).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("add_movie_review", size), &data, |b, data| {
+            b.iter(|| MovieInstruction::unpack(black_box(data)).unwrap());
+        });
+
+        // What the processor runs for the same data since reviews are parsed in place
+        group.bench_with_input(BenchmarkId::new("add_movie_review_borrowed", size), &data, |b, data| {
+            b.iter(|| bench_review_instruction_unpack(black_box(data)).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_size_helpers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("size");
+
+    for (size, review) in review_sizes() {
+        group.bench_with_input(BenchmarkId::new("get_account_size", size), &review, |b, review| {
+            b.iter(|| MovieAccountState::get_account_size(
+                black_box(review.title.clone()),
+                black_box(review.description.clone()),
+                black_box(review.poster_uri.clone()),
+                black_box(review.tags.clone()),
+            ).unwrap());
+        });
+
+        let tight = MovieAccountState { tight_size: true, ..review };
+
+        group.bench_with_input(BenchmarkId::new("account_len_tight", size), &tight, |b, review| {
+            b.iter(|| black_box(review).account_len().unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_review_account, bench_unpack, bench_size_helpers);
+criterion_main!(benches);
@@ -0,0 +1,105 @@
+// Checks that a deployed movie review program responds.
+//
+// Sends a `Ping` instruction and prints the time from submitting the
+// transaction to its confirmation, plus the signature. Exits with code 1 if
+// the transaction fails.
+//
+//     cargo run --example healthcheck -- --program-id <PROGRAM_ID> [--url <RPC_URL>]
+//
+// The fee payer is the keypair from the Solana CLI config; `--url` defaults
+// to the config's RPC URL.
+
+use std::{process::exit, str::FromStr, time::Instant};
+
+use solana_cli_config::{Config, CONFIG_FILE};
+
+use solana_client::rpc_client::RpcClient;
+
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+    transaction::Transaction,
+};
+
+const PING_DISCRIMINATOR: u8 = 63;
+
+struct Args {
+    url: Option<String>,
+    program_id: Pubkey,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut url = None;
+    let mut program_id = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--url" => {
+                url = Some(args.next().ok_or("--url needs a value")?);
+            },
+            "--program-id" => {
+                let value = args.next().ok_or("--program-id needs a value")?;
+                program_id = Some(
+                    Pubkey::from_str(&value).map_err(|err| format!("Invalid program id {}: {}", value, err))?
+                );
+            },
+            _ => return Err(format!("Unknown argument {}", arg)),
+        }
+    }
+
+    Ok(Args {
+        url,
+        program_id: program_id.ok_or("--program-id is required")?,
+    })
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        eprintln!("Usage: healthcheck --program-id <PROGRAM_ID> [--url <RPC_URL>]");
+        exit(2);
+    });
+
+    let config = CONFIG_FILE
+        .as_ref()
+        .and_then(|config_file| Config::load(config_file).ok())
+        .unwrap_or_default();
+
+    let payer = read_keypair_file(&config.keypair_path).unwrap_or_else(|err| {
+        eprintln!("Failed to read keypair {}: {}", config.keypair_path, err);
+        exit(1);
+    });
+
+    let client = RpcClient::new_with_commitment(
+        args.url.unwrap_or(config.json_rpc_url),
+        CommitmentConfig::confirmed(),
+    );
+
+    let recent_blockhash = client.get_latest_blockhash().unwrap_or_else(|err| {
+        eprintln!("Failed to fetch a blockhash: {}", err);
+        exit(1);
+    });
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(args.program_id, &[PING_DISCRIMINATOR], vec![])],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+
+    let started = Instant::now();
+
+    match client.send_and_confirm_transaction(&transaction) {
+        Ok(signature) => {
+            println!("Round trip: {} ms", started.elapsed().as_millis());
+            println!("Signature: {}", signature);
+        },
+        Err(err) => {
+            eprintln!("Ping failed after {} ms: {}", started.elapsed().as_millis(), err);
+            exit(1);
+        },
+    }
+}
@@ -0,0 +1,155 @@
+// Writes one seed per instruction to `corpus/process_instruction`, built
+// from the `test_utils` instruction builders. The review the seeds refer to
+// already exists, owned by the program; every other account is empty.
+//
+//     cargo run --example generate_corpus
+//
+// Rerun after changing an instruction's layout or accounts.
+
+use std::{fs, path::Path};
+
+use movie_review_program::{
+    processor::{mint_auth_pda, mint_pda, TOKEN_PROGRAM_ID},
+    test_utils::*,
+    MovieAccountState,
+};
+
+use movie_review_program_fuzz::{seed_review_pda, FuzzInput, OTHER_REVIEWER, PROGRAM_ID, REVIEWER, SEED_TITLE};
+
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+use solana_sdk_ids::{bpf_loader_upgradeable, system_program};
+
+use spl_associated_token_account::get_associated_token_address;
+
+const SIGNER_LAMPORTS: u64 = 10_000_000_000;
+const ACCOUNT_LAMPORTS: u64 = 1_000_000_000;
+
+fn seed_review() -> Vec<u8> {
+    review_account_data(&review_fixture(REVIEWER, SEED_TITLE, 4, "Sand"), MovieAccountState::LEN)
+}
+
+fn fixture(key: &Pubkey) -> (Pubkey, u64, Vec<u8>) {
+    if *key == seed_review_pda() {
+        (PROGRAM_ID, ACCOUNT_LAMPORTS, seed_review())
+    } else if [PROGRAM_ID, system_program::ID, TOKEN_PROGRAM_ID, spl_associated_token_account::ID].contains(key) {
+        (bpf_loader_upgradeable::ID, ACCOUNT_LAMPORTS, Vec::new())
+    } else if [REVIEWER, OTHER_REVIEWER].contains(key) {
+        (system_program::ID, SIGNER_LAMPORTS, Vec::new())
+    } else {
+        (system_program::ID, 0, Vec::new())
+    }
+}
+
+fn seeds() -> Vec<(&'static str, Instruction)> {
+    let mint = mint_pda(&PROGRAM_ID).0;
+    let mint_auth = mint_auth_pda(&PROGRAM_ID).0;
+    let user_ata = get_associated_token_address(&REVIEWER, &mint);
+    let review = seed_review_pda();
+    let title = || SEED_TITLE.to_string();
+
+    vec![
+        ("add_movie_review", create_add_movie_review_ix(
+            REVIEWER, PROGRAM_ID, title(), 5, String::from("Spice"), mint, mint_auth, user_ata,
+            system_program::ID, TOKEN_PROGRAM_ID,
+        )),
+        ("update_movie_review", create_update_movie_instruction(
+            REVIEWER, PROGRAM_ID, title(), 3, String::from("Less spice"),
+        )),
+        ("add_comment", create_add_comment_instruction(
+            REVIEWER, PROGRAM_ID, title(), String::from("Agreed"), 0, mint, mint_auth, user_ata,
+            system_program::ID, TOKEN_PROGRAM_ID,
+        )),
+        ("initialize_mint", create_init_mint_ix(REVIEWER, &PROGRAM_ID).2),
+        ("migrate_legacy_review", create_migrate_legacy_review_ix(REVIEWER, PROGRAM_ID, title())),
+        ("initialize_config", create_initialize_config_ix(REVIEWER, PROGRAM_ID)),
+        ("set_reward_mint", create_set_reward_mint_ix(REVIEWER, PROGRAM_ID, mint)),
+        ("claim_rewards", create_claim_rewards_ix(REVIEWER, PROGRAM_ID, mint, user_ata)),
+        ("set_claim_rewards", create_set_claim_rewards_ix(REVIEWER, PROGRAM_ID, true)),
+        ("delete_movie_review", create_delete_movie_review_ix(REVIEWER, PROGRAM_ID, title(), mint, user_ata)),
+        ("set_clawback_period", create_set_clawback_period_ix(REVIEWER, PROGRAM_ID, 86_400)),
+        ("approve_comment", create_approve_comment_ix(REVIEWER, PROGRAM_ID, title(), 0)),
+        ("backfill_reward", create_backfill_reward_ix(REVIEWER, PROGRAM_ID, review, mint, user_ata)),
+        ("set_skip_revoked_rewards", create_set_skip_revoked_rewards_ix(REVIEWER, PROGRAM_ID, true)),
+        ("sync_comment_counter", create_sync_comment_counter_ix(PROGRAM_ID, REVIEWER, title(), &[])),
+        ("update_comment_text", create_update_comment_text_ix(
+            OTHER_REVIEWER, PROGRAM_ID, REVIEWER, title(), 0, String::from("Edited"),
+        )),
+        ("rename_review", create_rename_review_ix(REVIEWER, PROGRAM_ID, title(), String::from("Dune Part Two"))),
+        ("set_min_description_len", create_set_min_description_len_ix(REVIEWER, PROGRAM_ID, 10)),
+        ("ping", create_ping_ix(PROGRAM_ID)),
+        ("change_review_language", create_change_review_language_ix(REVIEWER, PROGRAM_ID, title(), *b"fr")),
+        ("gift_tokens", create_gift_tokens_ix(REVIEWER, PROGRAM_ID, OTHER_REVIEWER, 1, mint)),
+        ("query_reviews_by_range", create_query_reviews_by_range_ix(PROGRAM_ID, 1, 5, 1, &[review])),
+        ("batch_create_atas", create_batch_create_atas_ix(REVIEWER, PROGRAM_ID, vec![OTHER_REVIEWER], mint)),
+        ("view_audit_log", create_view_audit_log_ix(REVIEWER, PROGRAM_ID, title())),
+        ("create_series", create_series_ix(REVIEWER, PROGRAM_ID, String::from("Arrakis"))),
+        ("add_to_series", create_add_to_series_ix(REVIEWER, PROGRAM_ID, String::from("Arrakis"), title())),
+        ("finalize_series", create_finalize_series_ix(REVIEWER, PROGRAM_ID, String::from("Arrakis"))),
+        ("react_to_review", create_react_to_review_ix(OTHER_REVIEWER, PROGRAM_ID, REVIEWER, title(), 0)),
+        ("set_comment_moderation", create_set_comment_moderation_ix(
+            REVIEWER, PROGRAM_ID, title(), MovieAccountState::COMMENTS_OPEN,
+        )),
+        ("nominate_review", create_nominate_review_ix(
+            OTHER_REVIEWER, PROGRAM_ID, review, REVIEWER, title(), String::from("Best Picture"), 2024,
+        )),
+        ("vote_for_nomination", create_vote_for_nomination_ix(
+            OTHER_REVIEWER, PROGRAM_ID, review, REVIEWER, title(), 2024,
+        )),
+        ("declare_award_winner", create_declare_award_winner_ix(
+            REVIEWER, PROGRAM_ID, String::from("Best Picture"), 2024, &[],
+        )),
+        ("sync_counter", create_sync_counter_ix(REVIEWER, PROGRAM_ID, REVIEWER, title(), &[])),
+        ("update_merkle_root", create_update_merkle_root_ix(REVIEWER, PROGRAM_ID, REVIEWER, title(), [1; 32], 1)),
+        ("verify_comment_inclusion", create_verify_comment_inclusion_ix(
+            PROGRAM_ID, REVIEWER, title(), OTHER_REVIEWER, vec![[2; 32]],
+        )),
+        ("set_callback_program", create_set_callback_program_ix(REVIEWER, PROGRAM_ID, Some(OTHER_REVIEWER))),
+        ("mark_sponsored", create_mark_sponsored_ix(REVIEWER, PROGRAM_ID, title())),
+        ("update_tag_cloud", create_update_tag_cloud_ix(REVIEWER, PROGRAM_ID, title())),
+        ("mint_first_review_badge", create_mint_first_review_badge_ix(REVIEWER, PROGRAM_ID, REVIEWER)),
+        ("fetch_comments", create_fetch_comments_ix(REVIEWER, PROGRAM_ID, title(), 0, 1)),
+        ("verify_program_version", create_verify_program_version_ix(PROGRAM_ID, [0; 32])),
+        ("propose_authority_transfer", create_propose_authority_transfer_ix(REVIEWER, PROGRAM_ID, OTHER_REVIEWER)),
+        ("accept_authority_transfer", create_accept_authority_transfer_ix(OTHER_REVIEWER, PROGRAM_ID)),
+        ("set_auto_verify_threshold", create_set_auto_verify_threshold_ix(REVIEWER, PROGRAM_ID, 2)),
+        ("create_collection", create_collection_ix(
+            REVIEWER, PROGRAM_ID, 0, String::from("Arrakis"), String::from("Every Dune"),
+        )),
+        ("add_review_to_collection", create_add_review_to_collection_ix(REVIEWER, PROGRAM_ID, 0, title())),
+        ("remove_review_from_collection", create_remove_review_from_collection_ix(REVIEWER, PROGRAM_ID, 0, title())),
+        ("compact_comment", create_compact_comment_ix(REVIEWER, PROGRAM_ID, REVIEWER, title(), 1, 0)),
+        ("send_direct_message", create_send_direct_message_ix(
+            REVIEWER, PROGRAM_ID, OTHER_REVIEWER, String::from("Great take"), 0,
+        )),
+        ("mark_message_read", create_mark_message_read_ix(OTHER_REVIEWER, PROGRAM_ID, REVIEWER, 0)),
+        ("set_max_reward_amount", create_set_max_reward_amount_ix(REVIEWER, PROGRAM_ID, 1_000_000_000)),
+        ("export_all_reviews", create_export_all_reviews_ix(PROGRAM_ID, REVIEWER, &[review])),
+        ("recompute_program_stats", create_recompute_program_stats_ix(REVIEWER, PROGRAM_ID, &[review])),
+        ("set_comment_mint", create_set_comment_mint_ix(REVIEWER, PROGRAM_ID, Some(mint))),
+        ("freeze_token_mint", create_freeze_token_mint_ix(REVIEWER, PROGRAM_ID, user_ata)),
+        ("thaw_token_mint", create_thaw_token_mint_ix(REVIEWER, PROGRAM_ID, user_ata)),
+        ("approve_pending_comment", create_approve_pending_comment_ix(
+            REVIEWER, PROGRAM_ID, OTHER_REVIEWER, title(), 0,
+        )),
+        ("reject_pending_comment", create_reject_pending_comment_ix(
+            REVIEWER, PROGRAM_ID, OTHER_REVIEWER, title(), String::from("Spoilers"),
+        )),
+    ]
+}
+
+fn main() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/process_instruction");
+    fs::create_dir_all(&corpus).unwrap();
+
+    for (name, instruction) in seeds() {
+        let input = FuzzInput::from_instruction(&instruction, fixture);
+        let encoded = input.encode();
+        // Seeds must survive the harness's own decoding unchanged
+        assert_eq!(FuzzInput::decode(&encoded), input, "{} does not round trip", name);
+
+        let path = corpus.join(format!("{:03}-{}", instruction.data[0], name));
+        fs::write(&path, encoded).unwrap();
+        println!("{}", path.display());
+    }
+}
@@ -0,0 +1,15 @@
+// Runs `process_instruction` on fuzzer-built accounts and instruction data;
+// see the `movie_review_program_fuzz` crate docs for the input layout.
+//
+//     cargo fuzz run process_instruction
+//
+// The seeds in `corpus/process_instruction` hold one valid call per
+// instruction and are rebuilt with `cargo run --example generate_corpus`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    movie_review_program_fuzz::run(data);
+});
@@ -0,0 +1,441 @@
+//! Harness for fuzzing `process_instruction` over fuzzer-built accounts.
+//!
+//! An input is decoded into a list of accounts and the instruction data,
+//! serialized into the same buffer layout the runtime hands to a program's
+//! entrypoint, and read back with `entrypoint::deserialize`. The processor
+//! then runs against those account infos; it may fail, but must not panic,
+//! and afterwards the buffer must still be one the runtime would accept.
+//!
+//! Input layout, with every read past the end taken as zero:
+//!
+//! ```text
+//! [count]        number of accounts, modulo MAX_ACCOUNTS + 1
+//! per account:
+//!   [flags]      FLAG_SIGNER | FLAG_WRITABLE | FLAG_EXECUTABLE | FLAG_DUPLICATE
+//!   duplicate:   [index] of an earlier account, modulo the accounts so far
+//!   otherwise:   [key]        index into `key_pool()`, or EXPLICIT_KEY and 32 bytes
+//!                [owner]      index into `owner_pool()`
+//!                [lamports]   u64 LE
+//!                [len]        u16 LE, modulo MAX_DATA_LEN + 1
+//!                [data]       len bytes
+//! rest:          instruction data
+//! ```
+//!
+//! A unique account whose key was already used becomes a duplicate of the
+//! first one, as the runtime would pass it.
+
+use std::{mem::size_of, sync::{Once, OnceLock}};
+
+use movie_review_program::{
+    processor::{mint_auth_pda, mint_pda, process_instruction, TOKEN_PROGRAM_ID},
+    test_utils::{
+        find_authority_record_pda, find_config_pda, find_profile_pda, find_stats_pda,
+        find_tag_cloud_pda,
+    },
+    MovieAccountState,
+};
+
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{deserialize, ProgramResult, BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER, SUCCESS},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+};
+
+use solana_sdk_ids::{bpf_loader_upgradeable, system_program};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+// Signers the seeds are built for
+pub const REVIEWER: Pubkey = Pubkey::new_from_array([1; 32]);
+pub const OTHER_REVIEWER: Pubkey = Pubkey::new_from_array([2; 32]);
+
+// Title of the review in `key_pool()`
+pub const SEED_TITLE: &str = "Dune";
+
+pub const MAX_ACCOUNTS: usize = 16;
+pub const MAX_DATA_LEN: usize = 4096;
+
+pub const FLAG_SIGNER: u8 = 1;
+pub const FLAG_WRITABLE: u8 = 1 << 1;
+pub const FLAG_EXECUTABLE: u8 = 1 << 2;
+pub const FLAG_DUPLICATE: u8 = 1 << 3;
+
+pub const EXPLICIT_KEY: u8 = u8::MAX;
+
+// Returned by every CPI. Invoked programs are not simulated, so a handler
+// only gets past a CPI when the fuzzer supplies the accounts it would have
+// created.
+pub const CPI_UNAVAILABLE: ProgramError = ProgramError::Custom(0xf022);
+
+// Addresses the processor checks for, so mutated inputs keep hitting them
+pub fn key_pool() -> &'static [Pubkey] {
+    static POOL: OnceLock<Vec<Pubkey>> = OnceLock::new();
+
+    POOL.get_or_init(|| vec![
+        PROGRAM_ID,
+        REVIEWER,
+        OTHER_REVIEWER,
+        system_program::ID,
+        TOKEN_PROGRAM_ID,
+        spl_associated_token_account::ID,
+        bpf_loader_upgradeable::ID,
+        mint_pda(&PROGRAM_ID).0,
+        mint_auth_pda(&PROGRAM_ID).0,
+        find_config_pda(&PROGRAM_ID),
+        find_stats_pda(&PROGRAM_ID),
+        find_tag_cloud_pda(&PROGRAM_ID),
+        find_authority_record_pda(&PROGRAM_ID),
+        find_profile_pda(&REVIEWER, &PROGRAM_ID),
+        seed_review_pda(),
+    ])
+}
+
+pub fn owner_pool() -> &'static [Pubkey] {
+    &[PROGRAM_ID, system_program::ID, TOKEN_PROGRAM_ID, bpf_loader_upgradeable::ID]
+}
+
+fn is_program(key: &Pubkey) -> bool {
+    [
+        PROGRAM_ID,
+        system_program::ID,
+        TOKEN_PROGRAM_ID,
+        spl_associated_token_account::ID,
+        bpf_loader_upgradeable::ID,
+    ].contains(key)
+}
+
+pub fn seed_review_pda() -> Pubkey {
+    Pubkey::find_program_address(
+        &[REVIEWER.as_ref(), MovieAccountState::title_seed(SEED_TITLE).as_bytes()],
+        &PROGRAM_ID,
+    ).0
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FuzzAccount {
+    // Index of an earlier account
+    Duplicate(usize),
+    Unique {
+        is_signer: bool,
+        is_writable: bool,
+        executable: bool,
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FuzzInput {
+    pub accounts: Vec<FuzzAccount>,
+    pub instruction_data: Vec<u8>,
+}
+
+// Reads from the fuzzer input, yielding zeros once it runs out
+struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Vec<u8> {
+        let taken = len.min(self.input.len());
+        let mut bytes = self.input[..taken].to_vec();
+        bytes.resize(len, 0);
+        self.input = &self.input[taken..];
+
+        bytes
+    }
+
+    fn byte(&mut self) -> u8 {
+        self.take(1)[0]
+    }
+
+    fn array<const N: usize>(&mut self) -> [u8; N] {
+        self.take(N).try_into().unwrap()
+    }
+}
+
+impl FuzzInput {
+    pub fn decode(input: &[u8]) -> Self {
+        let mut reader = Reader { input };
+        let count = reader.byte() as usize % (MAX_ACCOUNTS + 1);
+
+        let mut accounts: Vec<FuzzAccount> = Vec::with_capacity(count);
+        for index in 0..count {
+            let flags = reader.byte();
+
+            if flags & FLAG_DUPLICATE != 0 && index > 0 {
+                let original = reader.byte() as usize % index;
+                accounts.push(FuzzAccount::Duplicate(Self::resolve(&accounts, original)));
+                continue;
+            }
+
+            let key = match reader.byte() {
+                EXPLICIT_KEY => Pubkey::new_from_array(reader.array()),
+                pool_index => key_pool()[pool_index as usize % key_pool().len()],
+            };
+            let owner = owner_pool()[reader.byte() as usize % owner_pool().len()];
+            let lamports = u64::from_le_bytes(reader.array());
+            let data_len = u16::from_le_bytes(reader.array()) as usize % (MAX_DATA_LEN + 1);
+            let data = reader.take(data_len);
+
+            let earlier = accounts.iter().position(|account| {
+                matches!(account, FuzzAccount::Unique { key: earlier_key, .. } if *earlier_key == key)
+            });
+
+            accounts.push(match earlier {
+                Some(original) => FuzzAccount::Duplicate(original),
+                None => FuzzAccount::Unique {
+                    is_signer: flags & FLAG_SIGNER != 0,
+                    is_writable: flags & FLAG_WRITABLE != 0,
+                    executable: flags & FLAG_EXECUTABLE != 0,
+                    key,
+                    owner,
+                    lamports,
+                    data,
+                },
+            });
+        }
+
+        Self {
+            accounts,
+            instruction_data: reader.input.to_vec(),
+        }
+    }
+
+    // Duplicates always point at the unique account they copy
+    fn resolve(accounts: &[FuzzAccount], index: usize) -> usize {
+        match accounts[index] {
+            FuzzAccount::Duplicate(original) => original,
+            FuzzAccount::Unique { .. } => index,
+        }
+    }
+
+    // Inverse of `decode` for inputs it could have produced
+    pub fn encode(&self) -> Vec<u8> {
+        assert!(self.accounts.len() <= MAX_ACCOUNTS, "too many accounts");
+
+        let mut input = vec![self.accounts.len() as u8];
+        for account in &self.accounts {
+            match account {
+                FuzzAccount::Duplicate(original) => {
+                    input.extend([FLAG_DUPLICATE, *original as u8]);
+                },
+                FuzzAccount::Unique { is_signer, is_writable, executable, key, owner, lamports, data } => {
+                    assert!(data.len() <= MAX_DATA_LEN, "account data too long");
+
+                    input.push(
+                        if *is_signer { FLAG_SIGNER } else { 0 }
+                            | if *is_writable { FLAG_WRITABLE } else { 0 }
+                            | if *executable { FLAG_EXECUTABLE } else { 0 }
+                    );
+                    match key_pool().iter().position(|pool_key| pool_key == key) {
+                        Some(pool_index) => input.push(pool_index as u8),
+                        None => {
+                            input.push(EXPLICIT_KEY);
+                            input.extend(key.to_bytes());
+                        },
+                    }
+                    input.push(
+                        owner_pool().iter().position(|pool_owner| pool_owner == owner)
+                            .expect("owner is not in the owner pool") as u8
+                    );
+                    input.extend(lamports.to_le_bytes());
+                    input.extend((data.len() as u16).to_le_bytes());
+                    input.extend(data);
+                },
+            }
+        }
+        input.extend(&self.instruction_data);
+
+        input
+    }
+
+    // Accounts as the runtime would pass `instruction`'s metas: repeated keys
+    // become duplicates carrying the first one's signer and writable flags,
+    // and `fixtures` supplies each unique account's owner, lamports and data
+    pub fn from_instruction(
+        instruction: &Instruction,
+        fixtures: impl Fn(&Pubkey) -> (Pubkey, u64, Vec<u8>),
+    ) -> Self {
+        let mut accounts: Vec<FuzzAccount> = Vec::new();
+
+        for meta in &instruction.accounts {
+            let earlier = accounts.iter().position(|account| {
+                matches!(account, FuzzAccount::Unique { key, .. } if *key == meta.pubkey)
+            });
+
+            accounts.push(match earlier {
+                Some(original) => FuzzAccount::Duplicate(original),
+                None => {
+                    let (owner, lamports, data) = fixtures(&meta.pubkey);
+
+                    FuzzAccount::Unique {
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                        executable: is_program(&meta.pubkey),
+                        key: meta.pubkey,
+                        owner,
+                        lamports,
+                        data,
+                    }
+                },
+            });
+        }
+
+        Self {
+            accounts,
+            instruction_data: instruction.data.clone(),
+        }
+    }
+
+    // The entrypoint input buffer, as `u64`s so it is 8-byte aligned. Also
+    // returns the byte offset of the instruction data length.
+    fn serialize(&self) -> (Vec<u64>, usize) {
+        let mut bytes = (self.accounts.len() as u64).to_le_bytes().to_vec();
+
+        for account in &self.accounts {
+            match account {
+                FuzzAccount::Duplicate(original) => {
+                    bytes.push(*original as u8);
+                    bytes.extend([0; 7]);
+                },
+                FuzzAccount::Unique { is_signer, is_writable, executable, key, owner, lamports, data } => {
+                    bytes.extend([NON_DUP_MARKER, *is_signer as u8, *is_writable as u8, *executable as u8]);
+                    // Original data length, filled in by `deserialize`
+                    bytes.extend([0; 4]);
+                    bytes.extend(key.to_bytes());
+                    bytes.extend(owner.to_bytes());
+                    bytes.extend(lamports.to_le_bytes());
+                    bytes.extend((data.len() as u64).to_le_bytes());
+                    bytes.extend(data);
+                    bytes.resize(bytes.len() + MAX_PERMITTED_DATA_INCREASE, 0);
+                    bytes.resize(bytes.len().next_multiple_of(BPF_ALIGN_OF_U128), 0);
+                    // Rent epoch
+                    bytes.extend(u64::MAX.to_le_bytes());
+                },
+            }
+        }
+
+        let instruction_offset = bytes.len();
+        bytes.extend((self.instruction_data.len() as u64).to_le_bytes());
+        bytes.extend(&self.instruction_data);
+        bytes.extend(PROGRAM_ID.to_bytes());
+
+        let mut buffer = vec![0u64; bytes.len().div_ceil(size_of::<u64>())];
+        for (word, chunk) in buffer.iter_mut().zip(bytes.chunks(size_of::<u64>())) {
+            let mut word_bytes = [0; size_of::<u64>()];
+            word_bytes[..chunk.len()].copy_from_slice(chunk);
+            *word = u64::from_ne_bytes(word_bytes);
+        }
+
+        (buffer, instruction_offset)
+    }
+
+    // Panics if the processor left the accounts in a state the runtime
+    // rejects for any program, or if a successful call broke a rule a
+    // correct program keeps itself
+    fn check_accounts(&self, accounts: &[AccountInfo], result: &ProgramResult) {
+        let mut lamports_before: u128 = 0;
+        let mut lamports_after: u128 = 0;
+
+        for (index, (account, info)) in self.accounts.iter().zip(accounts).enumerate() {
+            let FuzzAccount::Unique { key, owner, lamports, data, .. } = account else {
+                continue;
+            };
+
+            assert_eq!(info.key, key, "account {} key changed", index);
+
+            let data_after = info.try_borrow_data()
+                .unwrap_or_else(|_| panic!("account {} data still borrowed", index));
+            let lamports_now = info.try_borrow_lamports()
+                .map(|lamports| **lamports)
+                .unwrap_or_else(|_| panic!("account {} lamports still borrowed", index));
+
+            assert!(
+                data_after.len() <= data.len() + MAX_PERMITTED_DATA_INCREASE,
+                "account {} grew past the permitted increase", index
+            );
+
+            lamports_before += *lamports as u128;
+            lamports_after += lamports_now as u128;
+
+            if result.is_ok() && *owner != PROGRAM_ID {
+                assert_eq!(**data_after, data[..], "account {} is not program owned but its data changed", index);
+                assert!(lamports_now >= *lamports, "account {} is not program owned but was debited", index);
+            }
+        }
+
+        if result.is_ok() {
+            assert_eq!(lamports_before, lamports_after, "lamports were created or destroyed");
+        }
+    }
+}
+
+struct FuzzSyscalls;
+
+impl SyscallStubs for FuzzSyscalls {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_log_compute_units(&self) {}
+
+    fn sol_log_data(&self, _fields: &[&[u8]]) {}
+
+    fn sol_invoke_signed(
+        &self,
+        _instruction: &Instruction,
+        _account_infos: &[AccountInfo],
+        _signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        Err(CPI_UNAVAILABLE)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { (var_addr as *mut Clock).write_unaligned(Clock::default()) };
+
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { (var_addr as *mut Rent).write_unaligned(Rent::default()) };
+
+        SUCCESS
+    }
+}
+
+pub fn run(input: &[u8]) {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        set_syscall_stubs(Box::new(FuzzSyscalls));
+    });
+
+    let input = FuzzInput::decode(input);
+    let (mut buffer, instruction_offset) = input.serialize();
+
+    {
+        // SAFETY: `buffer` is laid out as the runtime lays out entrypoint
+        // input, with room for each account to grow, and outlives `accounts`
+        let (program_id, accounts, instruction_data) = unsafe {
+            deserialize(buffer.as_mut_ptr() as *mut u8)
+        };
+
+        let result = process_instruction(program_id, &accounts, instruction_data);
+        input.check_accounts(&accounts, &result);
+    }
+
+    // The instruction data and program id follow the accounts in the buffer
+    let bytes: Vec<u8> = buffer.iter().flat_map(|word| word.to_ne_bytes()).collect();
+    let tail = &bytes[instruction_offset..];
+    let data_start = size_of::<u64>();
+    let data_end = data_start + input.instruction_data.len();
+
+    assert_eq!(&tail[..data_start], (input.instruction_data.len() as u64).to_le_bytes(), "instruction data length changed");
+    assert_eq!(&tail[data_start..data_end], &input.instruction_data[..], "instruction data changed");
+    assert_eq!(&tail[data_end..data_end + 32], PROGRAM_ID.as_ref(), "program id changed");
+}
@@ -0,0 +1,150 @@
+use crate::state::{MovieAccountState, MovieComment, MovieCommentCounter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountKind {
+    Review,
+    Comment,
+    CommentCounter,
+}
+
+impl AccountKind {
+    pub fn discriminator(&self) -> &'static str {
+        match self {
+            AccountKind::Review => MovieAccountState::DISCRIMINATOR,
+            AccountKind::Comment => MovieComment::DISCRIMINATOR,
+            AccountKind::CommentCounter => MovieCommentCounter::DISCRIMINATOR,
+        }
+    }
+}
+
+// Returns the offset and bytes to `memcmp` against in getProgramAccounts.
+// Discriminators are Borsh strings, so the match covers the u32 length
+// prefix as well as the string itself.
+pub fn discriminator_filter(kind: AccountKind) -> (usize, Vec<u8>) {
+    let discriminator = kind.discriminator().as_bytes();
+
+    let mut bytes = Vec::with_capacity(4 + discriminator.len());
+    bytes.extend_from_slice(&(discriminator.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(discriminator);
+
+    (0, bytes)
+}
+
+// Cheap "add or update" check on raw review account data. Only the
+// discriminator and the `is_initialized` flag right after it are read, so a
+// zeroed buffer or an account of another type reads as no review.
+pub fn review_exists(data: &[u8]) -> bool {
+    let (offset, prefix) = discriminator_filter(AccountKind::Review);
+
+    data.get(offset..offset + prefix.len()) == Some(prefix.as_slice())
+        && data.get(offset + prefix.len()) == Some(&1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use solana_program::pubkey::Pubkey;
+
+    fn assert_prefix(kind: AccountKind, data: Vec<u8>) {
+        let (offset, bytes) = discriminator_filter(kind);
+
+        assert_eq!(&data[offset..offset + bytes.len()], bytes.as_slice());
+    }
+
+    #[test]
+    fn test_review_filter_matches_serialized_account() {
+        let review = MovieAccountState {
+            discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            version: MovieAccountState::VERSION,
+            reviewer: Pubkey::new_unique(),
+            rating: 5,
+            language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+            watched_at: 0,
+            created_at: 0,
+            reactions: [0; MovieAccountState::REACTION_KINDS],
+            comment_moderation: MovieAccountState::COMMENTS_OPEN,
+            reward_minted: true,
+            tight_size: false,
+            is_sponsored: false,
+            title: String::from("Captain America"),
+            description: String::from("Liked the movie"),
+            poster_uri: None,
+            tags: Vec::new(),
+        };
+
+        assert_prefix(AccountKind::Review, borsh::to_vec(&review).unwrap());
+    }
+
+    #[test]
+    fn test_comment_filter_matches_serialized_account() {
+        let comment = MovieComment {
+            discriminator: MovieComment::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            review: Pubkey::new_unique(),
+            commenter: Pubkey::new_unique(),
+            comment: String::from("Totally agree!"),
+            count: 0,
+            created_at: 0,
+        };
+
+        assert_prefix(AccountKind::Comment, borsh::to_vec(&comment).unwrap());
+    }
+
+    #[test]
+    fn test_counter_filter_matches_serialized_account() {
+        let counter = MovieCommentCounter {
+            discriminator: MovieCommentCounter::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            counter: 3,
+        };
+
+        assert_prefix(AccountKind::CommentCounter, borsh::to_vec(&counter).unwrap());
+    }
+
+    #[test]
+    fn test_review_exists() {
+        assert!(!review_exists(&[]));
+        assert!(!review_exists(&[0; MovieAccountState::LEN]));
+
+        let review = MovieAccountState {
+            discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            version: MovieAccountState::VERSION,
+            reviewer: Pubkey::new_unique(),
+            rating: 5,
+            language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+            watched_at: 0,
+            created_at: 0,
+            reactions: [0; MovieAccountState::REACTION_KINDS],
+            comment_moderation: MovieAccountState::COMMENTS_OPEN,
+            reward_minted: true,
+            tight_size: false,
+            is_sponsored: false,
+            title: String::from("Captain America"),
+            description: String::from("Liked the movie"),
+            poster_uri: None,
+            tags: Vec::new(),
+        };
+        assert!(review_exists(&borsh::to_vec(&review).unwrap()));
+
+        let counter = MovieCommentCounter {
+            discriminator: MovieCommentCounter::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            counter: 3,
+        };
+        assert!(!review_exists(&borsh::to_vec(&counter).unwrap()));
+    }
+
+    #[test]
+    fn test_filters_do_not_overlap() {
+        let (_, review) = discriminator_filter(AccountKind::Review);
+        let (_, comment) = discriminator_filter(AccountKind::Comment);
+        let (_, counter) = discriminator_filter(AccountKind::CommentCounter);
+
+        assert_ne!(review, comment);
+        assert_ne!(review, counter);
+        assert_ne!(comment, counter);
+    }
+}
@@ -0,0 +1,174 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+use crate::instruction::{AddMovieReviewPayload, MovieReviewPayload};
+
+// Accounts for a call into the review program, plus the seeds of any PDA
+// that signs through `invoke_signed`. Handlers only look at `is_signer`, so
+// a PDA reviewer works the same as a wallet.
+pub struct CpiContext<'a, 'b, T> {
+    pub program: AccountInfo<'a>,
+    pub accounts: T,
+    pub signer_seeds: &'b [&'b [&'b [u8]]],
+}
+
+impl<'a, 'b, T> CpiContext<'a, 'b, T> {
+    pub fn new(program: AccountInfo<'a>, accounts: T) -> Self {
+        Self {
+            program,
+            accounts,
+            signer_seeds: &[],
+        }
+    }
+
+    pub fn new_with_signer(
+        program: AccountInfo<'a>,
+        accounts: T,
+        signer_seeds: &'b [&'b [&'b [u8]]],
+    ) -> Self {
+        Self {
+            program,
+            accounts,
+            signer_seeds,
+        }
+    }
+}
+
+pub struct AddMovieReview<'a> {
+    pub reviewer: AccountInfo<'a>,
+    pub review: AccountInfo<'a>,
+    pub comment_counter: AccountInfo<'a>,
+    pub token_mint: AccountInfo<'a>,
+    pub mint_auth: AccountInfo<'a>,
+    pub user_ata: AccountInfo<'a>,
+    pub system_program: AccountInfo<'a>,
+    pub token_program: AccountInfo<'a>,
+    pub config: AccountInfo<'a>,
+    pub stats: AccountInfo<'a>,
+    pub profile: AccountInfo<'a>,
+    // Funds the new accounts; needed when the reviewer is a PDA that is not a
+    // funded system account
+    pub payer: Option<AccountInfo<'a>>,
+    // Required once the config registers a callback program
+    pub callback_program: Option<AccountInfo<'a>>,
+}
+
+pub struct UpdateMovieReview<'a> {
+    pub reviewer: AccountInfo<'a>,
+    pub review: AccountInfo<'a>,
+    pub audit_log: AccountInfo<'a>,
+    pub system_program: AccountInfo<'a>,
+}
+
+pub fn add_movie_review<'a>(
+    ctx: CpiContext<'a, '_, AddMovieReview<'a>>,
+    title: String,
+    rating: u8,
+    description: String,
+) -> ProgramResult {
+    let AddMovieReview {
+        reviewer,
+        review,
+        comment_counter,
+        token_mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+        config,
+        stats,
+        profile,
+        payer,
+        callback_program,
+    } = ctx.accounts;
+
+    let mut account_metas = vec![
+        AccountMeta::new(*reviewer.key, true),
+        AccountMeta::new(*review.key, false),
+        AccountMeta::new(*comment_counter.key, false),
+        AccountMeta::new(*token_mint.key, false),
+        AccountMeta::new_readonly(*mint_auth.key, false),
+        AccountMeta::new(*user_ata.key, false),
+        AccountMeta::new_readonly(*system_program.key, false),
+        AccountMeta::new_readonly(*token_program.key, false),
+        AccountMeta::new_readonly(*config.key, false),
+        AccountMeta::new(*stats.key, false),
+        AccountMeta::new(*profile.key, false),
+    ];
+
+    let mut account_infos = vec![
+        ctx.program,
+        reviewer.clone(),
+        review,
+        comment_counter,
+        token_mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+        config,
+        stats,
+        profile,
+    ];
+
+    // The callback program sits after the sponsor slot, which the reviewer
+    // fills itself when there is no separate payer
+    let payer = match (payer, &callback_program) {
+        (Some(payer), _) => Some(payer),
+        (None, Some(_)) => Some(reviewer),
+        (None, None) => None,
+    };
+
+    if let Some(payer) = payer {
+        account_metas.push(AccountMeta::new(*payer.key, true));
+        account_infos.push(payer);
+    }
+
+    if let Some(callback_program) = callback_program {
+        account_metas.push(AccountMeta::new_readonly(*callback_program.key, false));
+        account_infos.push(callback_program);
+    }
+
+    let instruction = Instruction::new_with_borsh(
+        *account_infos[0].key,
+        &(0u8, AddMovieReviewPayload::new(title, rating, description)),
+        account_metas,
+    );
+
+    invoke_signed(&instruction, &account_infos, ctx.signer_seeds)
+}
+
+pub fn update_movie_review<'a>(
+    ctx: CpiContext<'a, '_, UpdateMovieReview<'a>>,
+    title: String,
+    rating: u8,
+    description: String,
+) -> ProgramResult {
+    let UpdateMovieReview {
+        reviewer,
+        review,
+        audit_log,
+        system_program,
+    } = ctx.accounts;
+
+    let instruction = Instruction::new_with_borsh(
+        *ctx.program.key,
+        &(1u8, MovieReviewPayload::new(title, rating, description)),
+        vec![
+            AccountMeta::new(*reviewer.key, true),
+            AccountMeta::new(*review.key, false),
+            AccountMeta::new(*audit_log.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+        ],
+    );
+
+    invoke_signed(
+        &instruction,
+        &[ctx.program, reviewer, review, audit_log, system_program],
+        ctx.signer_seeds,
+    )
+}
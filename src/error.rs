@@ -1,8 +1,13 @@
-use solana_program::program_error::ProgramError;
+use borsh::{BorshSerialize, BorshDeserialize};
+
+use solana_program::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
 
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum ReviewError {
     // Error 0
     #[error("Account not initialized yet")]
@@ -19,10 +24,104 @@ pub enum ReviewError {
     // Error 4
     #[error("Account do not match")]
     IncorrectAccount,
+    // Error 5
+    #[error("Language code is not a two-letter ISO 639-1 code")]
+    InvalidLanguageCode,
+    // Error 6
+    #[error("Token balance is lower than the requested amount")]
+    InsufficientTokenBalance,
+    // Error 7
+    #[error("Watched date is in the future")]
+    InvalidWatchedDate,
+    // Error 8
+    #[error("Account is not a legacy review")]
+    NotLegacyAccount,
+    // Error 9
+    #[error("Series is finalized and can no longer change")]
+    SeriesFinalized,
+    // Error 10
+    #[error("Series already holds the maximum number of parts")]
+    SeriesFull,
+    // Error 11
+    #[error("Program does not hold the mint authority of the token mint")]
+    InvalidMintAuthority,
+    // Error 12
+    #[error("Review reward could not be burned from the reviewer's token account")]
+    RewardClawbackFailed,
+    // Error 13
+    #[error("Review is locked for new comments")]
+    CommentsLocked,
+    // Error 14
+    #[error("Review reward has already been minted")]
+    RewardAlreadyMinted,
+    // Error 15
+    #[error("Signer has not written a review")]
+    NotVerifiedReviewer,
+    // Error 16
+    #[error("Voting has closed for this award year")]
+    VotingClosed,
+    // Error 17
+    #[error("Voting is still open for this award year")]
+    VotingStillOpen,
+    // Error 18
+    #[error("Comment counter did not match the comments passed")]
+    CounterSyncMismatch,
+    // Error 19
+    #[error("Program mint authority has been revoked")]
+    MintAuthorityRevoked,
+    // Error 20
+    #[error("Comment is not included in the review's Merkle root")]
+    CommentNotIncluded,
+    // Error 21
+    #[error("Review is already disclosed as sponsored")]
+    AlreadySponsored,
+    // Error 22
+    #[error("Poster URI is too long or uses an unsupported scheme")]
+    InvalidUri,
+    // Error 23
+    #[error("Badge is only available right after the first review")]
+    BadgeNotEarned,
+    // Error 24
+    #[error("Badge has already been minted")]
+    BadgeAlreadyMinted,
+    // Error 25
+    #[error("Deployed program does not match the expected hash")]
+    VersionMismatch,
+    // Error 26
+    #[error("Collection already holds the maximum number of reviews")]
+    CollectionFull,
+    // Error 27
+    #[error("Reward exceeds the configured maximum")]
+    RewardTooLarge,
+    // Error 28
+    #[error("Account does not belong to the given reviewer")]
+    Unauthorized,
+    // Error 29
+    #[error("Too many tags, or a tag is empty or too long")]
+    InvalidTags,
+    // Error 30
+    #[error("Arithmetic overflow")]
+    ArithmeticOverflow,
+    // Error 31
+    #[error("Arithmetic underflow")]
+    ArithmeticUnderflow,
+    // Error 32
+    #[error("Claimed rewards cannot be paid in a separate comment mint")]
+    ConflictingRewardSettings,
 }
 
 impl From<ReviewError> for ProgramError {
     fn from(value: ReviewError) -> Self {
         ProgramError::Custom(value as u32)
     }
+}
+
+// Written to return data before some errors when the `error-context` feature
+// is enabled, so simulating clients can see which account was rejected
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ErrorContext {
+    pub error_code: u32,
+    pub account_index: u8,
+    pub expected: Pubkey,
+    pub actual: Pubkey,
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use solana_program::{hash::hash, pubkey::Pubkey};
+
+// Events in the layout Anchor's `emit!` logs, so Anchor's event decoders can
+// read them. With the `anchor-events` feature each one is written through
+// `sol_log_data`, which logs `Program data: <base64>` of an 8-byte
+// discriminator, `sha256("event:<Name>")[..8]`, followed by the borsh-encoded
+// event.
+pub trait Event: BorshSerialize {
+    const NAME: &'static str;
+
+    fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0; 8];
+        discriminator.copy_from_slice(&hash(format!("event:{}", Self::NAME).as_bytes()).to_bytes()[..8]);
+
+        discriminator
+    }
+
+    // Discriminator followed by the event, as carried in the log line
+    fn data(&self) -> Vec<u8> {
+        let mut data = Self::discriminator().to_vec();
+        // Writing into a `Vec` cannot fail
+        self.serialize(&mut data).unwrap();
+
+        data
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReviewAdded {
+    pub reviewer: Pubkey,
+    pub review: Pubkey,
+    pub title: String,
+    pub rating: u8,
+}
+
+impl Event for ReviewAdded {
+    const NAME: &'static str = "ReviewAdded";
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReviewUpdated {
+    pub reviewer: Pubkey,
+    pub review: Pubkey,
+    pub rating: u8,
+}
+
+impl Event for ReviewUpdated {
+    const NAME: &'static str = "ReviewUpdated";
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CommentAdded {
+    pub commenter: Pubkey,
+    pub review: Pubkey,
+    pub comment: Pubkey,
+    pub index: u64,
+}
+
+impl Event for CommentAdded {
+    const NAME: &'static str = "CommentAdded";
+}
+
+#[cfg(feature = "anchor-events")]
+pub fn emit<E: Event>(event: &E) {
+    solana_program::log::sol_log_data(&[&event.data()]);
+}
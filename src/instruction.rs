@@ -1,22 +1,222 @@
-use borsh::BorshDeserialize;
+use borsh::{io, BorshSerialize, BorshDeserialize};
 
-use solana_program::program_error::ProgramError;
+use solana_program::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::state::MovieAccountState;
 
 pub enum MovieInstruction {
     AddMovieReview {
         title: String,
         rating: u8,
         description: String,
+        language_code: [u8; 2],
+        watched_at: i64,
+        tight_size: bool,
+        is_sponsored: bool,
+        poster_uri: Option<String>,
+        tags: Vec<String>,
     },
     UpdateMovieReview {
         title: String,
         rating: u8,
         description: String,
+        // `None` keeps the current poster or tags
+        poster_uri: Option<String>,
+        tags: Option<Vec<String>>,
     },
     AddComment {
         comment: String,
     },
     InitializeMint,
+    ChangeReviewLanguage {
+        title: String,
+        language_code: [u8; 2],
+    },
+    GiftTokens {
+        recipient: Pubkey,
+        amount: u64,
+    },
+    QueryReviewsByRange {
+        min_rating: u8,
+        max_rating: u8,
+        max_results: u8,
+    },
+    BatchCreateATAs {
+        users: Vec<Pubkey>,
+    },
+    MigrateLegacyReview,
+    InitializeConfig,
+    SetRewardMint {
+        reward_mint: Pubkey,
+    },
+    ClaimRewards,
+    SetClaimRewards {
+        enabled: bool,
+    },
+    DeleteMovieReview {
+        title: String,
+    },
+    SetClawbackPeriod {
+        seconds: i64,
+    },
+    ViewAuditLog {
+        title: String,
+    },
+    CreateSeries {
+        name: String,
+    },
+    AddToSeries {
+        series_name: String,
+        review_title: String,
+    },
+    FinalizeSeries {
+        series_name: String,
+    },
+    ReactToReview {
+        title: String,
+        reviewer: Pubkey,
+        reaction: u8,
+    },
+    SetCommentModeration {
+        title: String,
+        mode: u8,
+    },
+    ApproveComment {
+        title: String,
+        comment_index: u64,
+    },
+    BackfillReward,
+    NominateReview {
+        reviewer: Pubkey,
+        title: String,
+        category: String,
+    },
+    VoteForNomination {
+        reviewer: Pubkey,
+        title: String,
+        year: u16,
+    },
+    DeclareAwardWinner {
+        category: String,
+        year: u16,
+    },
+    SyncCounter {
+        title: String,
+    },
+    SetSkipRevokedRewards {
+        enabled: bool,
+    },
+    UpdateMerkleRoot {
+        title: String,
+        root: [u8; 32],
+        num_comments: u64,
+    },
+    VerifyCommentInclusion {
+        title: String,
+        comment_pda: Pubkey,
+        proof: Vec<[u8; 32]>,
+    },
+    SyncCommentCounter {
+        title: String,
+    },
+    UpdateCommentText {
+        title: String,
+        comment_index: u64,
+        comment: String,
+    },
+    RenameReview {
+        title: String,
+        new_title: String,
+    },
+    SetCallbackProgram {
+        program_id: Option<Pubkey>,
+    },
+    MarkSponsored {
+        title: String,
+    },
+    SetMinDescriptionLenForReward {
+        min_len: u32,
+    },
+    UpdateTagCloud {
+        title: String,
+    },
+    MintFirstReviewBadge {
+        reviewer: Pubkey,
+    },
+    FetchComments {
+        review_title: String,
+        start_index: u64,
+        count: u8,
+    },
+    VerifyProgramVersion {
+        expected_hash: [u8; 32],
+    },
+    ProposeAuthorityTransfer {
+        new_authority: Pubkey,
+    },
+    AcceptAuthorityTransfer,
+    // Takes no accounts; lets operators check the program responds
+    Ping,
+    SetAutoVerifyThreshold {
+        threshold: u8,
+    },
+    CreateCollection {
+        name: String,
+        description: String,
+    },
+    AddReviewToCollection {
+        collection_id: u64,
+        review_title: String,
+    },
+    RemoveReviewFromCollection {
+        collection_id: u64,
+        review_title: String,
+    },
+    // Repair tooling: moves a comment to the free index `new_index`
+    CompactComment {
+        old_index: u64,
+        new_index: u64,
+    },
+    SendDirectMessage {
+        recipient: Pubkey,
+        message: String,
+    },
+    MarkMessageRead {
+        sender: Pubkey,
+        message_index: u64,
+    },
+    SetMaxRewardAmount {
+        max_amount: u64,
+    },
+    // Logs every review passed as an account; all must be `reviewer`'s
+    ExportAllReviews {
+        reviewer: Pubkey,
+    },
+    RecomputeProgramStats,
+    SetCommentMint {
+        comment_mint: Option<Pubkey>,
+    },
+    // Freezes or thaws a token account of the PDA mint
+    FreezeTokenMint,
+    ThawTokenMint,
+    // Turns `submitter`'s `PendingComment` on the signer's review into a
+    // regular comment
+    ApprovePendingComment {
+        submitter: Pubkey,
+        title: String,
+    },
+    // Closes the pending comment without storing it; `reason` is only logged
+    RejectPendingComment {
+        submitter: Pubkey,
+        title: String,
+        reason: String,
+    },
+    // Panics on purpose, to check how the runtime reports a program panic
+    #[cfg(feature = "test-panic")]
+    TestPanic,
 }
 
 impl MovieInstruction {
@@ -26,13 +226,19 @@ impl MovieInstruction {
 
         Ok(match discriminator {
             0 => {
-                let payload = MovieReviewPayload::try_from_slice(rest)
+                let payload = AddMovieReviewPayload::try_from_slice(rest)
                     .map_err(|_| ProgramError::InvalidInstructionData)?;
 
                 Self::AddMovieReview { 
                     title: payload.title, 
                     rating: payload.rating, 
-                    description: payload.description 
+                    description: payload.description,
+                    language_code: payload.language_code,
+                    watched_at: payload.watched_at,
+                    tight_size: payload.tight_size,
+                    is_sponsored: payload.is_sponsored,
+                    poster_uri: payload.poster_uri,
+                    tags: payload.tags,
                 }
             },
             1 => {
@@ -42,7 +248,9 @@ impl MovieInstruction {
                 Self::UpdateMovieReview { 
                     title: payload.title, 
                     rating: payload.rating, 
-                    description: payload.description 
+                    description: payload.description,
+                    poster_uri: payload.poster_uri,
+                    tags: payload.tags,
                 }
             },
             2 => {
@@ -56,20 +264,2060 @@ impl MovieInstruction {
             3 => {
                 Self::InitializeMint
             },
+            4 => {
+                Self::MigrateLegacyReview
+            },
+            5 => {
+                Self::InitializeConfig
+            },
+            6 => {
+                let payload = RewardMintPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-            _ => return Err(ProgramError::InvalidInstructionData)
-        })
-    }
-}
+                Self::SetRewardMint {
+                    reward_mint: payload.reward_mint,
+                }
+            },
+            7 => {
+                Self::ClaimRewards
+            },
+            8 => {
+                let payload = ClaimRewardsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-#[derive(BorshDeserialize)]
-struct MovieReviewPayload {
-    title: String,
-    rating: u8,
-    description: String,
-}
+                Self::SetClaimRewards {
+                    enabled: payload.enabled,
+                }
+            },
+            9 => {
+                let payload = DeleteReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::DeleteMovieReview {
+                    title: payload.title,
+                }
+            },
+            10 => {
+                let payload = ClawbackPeriodPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetClawbackPeriod {
+                    seconds: payload.seconds,
+                }
+            },
+            11 => {
+                let payload = ApproveCommentPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::ApproveComment {
+                    title: payload.title,
+                    comment_index: payload.comment_index,
+                }
+            },
+            12 => {
+                Self::BackfillReward
+            },
+            13 => {
+                let payload = SkipRevokedRewardsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetSkipRevokedRewards {
+                    enabled: payload.enabled,
+                }
+            },
+            14 => {
+                let payload = SyncCounterPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SyncCommentCounter {
+                    title: payload.title,
+                }
+            },
+            15 => {
+                let payload = UpdateCommentTextPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::UpdateCommentText {
+                    title: payload.title,
+                    comment_index: payload.comment_index,
+                    comment: payload.comment,
+                }
+            },
+            16 => {
+                let payload = RenameReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::RenameReview {
+                    title: payload.title,
+                    new_title: payload.new_title,
+                }
+            },
+            17 => {
+                let payload = MinDescriptionLenPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetMinDescriptionLenForReward {
+                    min_len: payload.min_len,
+                }
+            },
+            18 => {
+                let payload = CompactCommentPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::CompactComment {
+                    old_index: payload.old_index,
+                    new_index: payload.new_index,
+                }
+            },
+            19 => {
+                let payload = MaxRewardAmountPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetMaxRewardAmount {
+                    max_amount: payload.max_amount,
+                }
+            },
+            20 => {
+                let payload = CommentMintPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetCommentMint {
+                    comment_mint: payload.comment_mint,
+                }
+            },
+            63 => {
+                Self::Ping
+            },
+            95 => {
+                let payload = ReviewLanguagePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::ChangeReviewLanguage {
+                    title: payload.title,
+                    language_code: payload.language_code,
+                }
+            },
+            96 => {
+                let payload = GiftTokensPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::GiftTokens {
+                    recipient: payload.recipient,
+                    amount: payload.amount,
+                }
+            },
+            97 => {
+                let payload = QueryReviewsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::QueryReviewsByRange {
+                    min_rating: payload.min_rating,
+                    max_rating: payload.max_rating,
+                    max_results: payload.max_results,
+                }
+            },
+            98 => {
+                let payload = BatchCreateATAsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::BatchCreateATAs {
+                    users: payload.users,
+                }
+            },
+            100 => {
+                let payload = ViewAuditLogPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::ViewAuditLog {
+                    title: payload.title,
+                }
+            },
+            101 => {
+                let payload = SeriesPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::CreateSeries {
+                    name: payload.series_name,
+                }
+            },
+            102 => {
+                let payload = AddToSeriesPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::AddToSeries {
+                    series_name: payload.series_name,
+                    review_title: payload.review_title,
+                }
+            },
+            103 => {
+                let payload = SeriesPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::FinalizeSeries {
+                    series_name: payload.series_name,
+                }
+            },
+            104 => {
+                let payload = ReactionPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::ReactToReview {
+                    title: payload.title,
+                    reviewer: payload.reviewer,
+                    reaction: payload.reaction,
+                }
+            },
+            105 => {
+                let payload = CommentModerationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetCommentModeration {
+                    title: payload.title,
+                    mode: payload.mode,
+                }
+            },
+            106 => {
+                let payload = NominateReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::NominateReview {
+                    reviewer: payload.reviewer,
+                    title: payload.title,
+                    category: payload.category,
+                }
+            },
+            107 => {
+                let payload = VoteForNominationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::VoteForNomination {
+                    reviewer: payload.reviewer,
+                    title: payload.title,
+                    year: payload.year,
+                }
+            },
+            108 => {
+                let payload = DeclareAwardWinnerPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::DeclareAwardWinner {
+                    category: payload.category,
+                    year: payload.year,
+                }
+            },
+            109 => {
+                let payload = SyncCounterPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SyncCounter {
+                    title: payload.title,
+                }
+            },
+            111 => {
+                let payload = UpdateMerkleRootPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::UpdateMerkleRoot {
+                    title: payload.title,
+                    root: payload.root,
+                    num_comments: payload.num_comments,
+                }
+            },
+            112 => {
+                let payload = VerifyCommentInclusionPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::VerifyCommentInclusion {
+                    title: payload.title,
+                    comment_pda: payload.comment_pda,
+                    proof: payload.proof,
+                }
+            },
+            113 => {
+                let payload = CallbackProgramPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetCallbackProgram {
+                    program_id: payload.program_id,
+                }
+            },
+            114 => {
+                let payload = MarkSponsoredPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::MarkSponsored {
+                    title: payload.title,
+                }
+            },
+            115 => {
+                let payload = UpdateTagCloudPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::UpdateTagCloud {
+                    title: payload.title,
+                }
+            },
+            116 => {
+                let payload = MintFirstReviewBadgePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::MintFirstReviewBadge {
+                    reviewer: payload.reviewer,
+                }
+            },
+            117 => {
+                let payload = FetchCommentsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::FetchComments {
+                    review_title: payload.review_title,
+                    start_index: payload.start_index,
+                    count: payload.count,
+                }
+            },
+            118 => {
+                let payload = VerifyProgramVersionPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::VerifyProgramVersion {
+                    expected_hash: payload.expected_hash,
+                }
+            },
+            119 => {
+                let payload = ProposeAuthorityTransferPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::ProposeAuthorityTransfer {
+                    new_authority: payload.new_authority,
+                }
+            },
+            120 => {
+                Self::AcceptAuthorityTransfer
+            },
+            121 => {
+                let payload = AutoVerifyThresholdPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SetAutoVerifyThreshold {
+                    threshold: payload.threshold,
+                }
+            },
+            122 => {
+                let payload = CreateCollectionPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::CreateCollection {
+                    name: payload.name,
+                    description: payload.description,
+                }
+            },
+            123 => {
+                let payload = CollectionReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::AddReviewToCollection {
+                    collection_id: payload.collection_id,
+                    review_title: payload.review_title,
+                }
+            },
+            124 => {
+                let payload = CollectionReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::RemoveReviewFromCollection {
+                    collection_id: payload.collection_id,
+                    review_title: payload.review_title,
+                }
+            },
+            125 => {
+                let payload = SendDirectMessagePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::SendDirectMessage {
+                    recipient: payload.recipient,
+                    message: payload.message,
+                }
+            },
+            126 => {
+                let payload = MarkMessageReadPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::MarkMessageRead {
+                    sender: payload.sender,
+                    message_index: payload.message_index,
+                }
+            },
+            127 => {
+                let payload = ExportAllReviewsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::ExportAllReviews {
+                    reviewer: payload.reviewer,
+                }
+            },
+            128 => {
+                Self::RecomputeProgramStats
+            },
+            129 => {
+                Self::FreezeTokenMint
+            },
+            130 => {
+                Self::ThawTokenMint
+            },
+            131 => {
+                let payload = ApprovePendingCommentPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+                Self::ApprovePendingComment {
+                    submitter: payload.submitter,
+                    title: payload.title,
+                }
+            },
+            132 => {
+                let payload = RejectPendingCommentPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
 
-#[derive(BorshDeserialize)]
-struct CommentPayload {
-    comment: String,
+                Self::RejectPendingComment {
+                    submitter: payload.submitter,
+                    title: payload.title,
+                    reason: payload.reason,
+                }
+            },
+            #[cfg(feature = "test-panic")]
+            255 => {
+                Self::TestPanic
+            },
+
+            _ => return Err(ProgramError::InvalidInstructionData)
+        })
+    }
+}
+
+// Optional field at the end of a review payload; clients that stop before
+// it get `default`
+fn trailing_field_or<T: BorshDeserialize>(data: &mut &[u8], default: T) -> io::Result<T> {
+    if data.is_empty() {
+        return Ok(default);
+    }
+
+    T::deserialize(data)
+}
+
+fn trailing_field<T: BorshDeserialize + Default>(data: &mut &[u8]) -> io::Result<T> {
+    trailing_field_or(data, T::default())
+}
+
+// Reads the optional fields that follow the fixed part of a review payload.
+// Borsh's own leftover check can't see past `read_to_end`, so it is repeated.
+fn trailing_fields<R: io::Read, T>(
+    reader: &mut R,
+    read: impl FnOnce(&mut &[u8]) -> io::Result<T>,
+) -> io::Result<T> {
+    let mut trailing = Vec::new();
+    reader.read_to_end(&mut trailing)?;
+
+    let mut data = trailing.as_slice();
+    let fields = read(&mut data)?;
+
+    if !data.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Unexpected length of input"));
+    }
+
+    Ok(fields)
+}
+
+// Borsh rejects bytes left over after a `try_from_slice`
+fn finish(data: &[u8]) -> Result<(), ProgramError> {
+    if !data.is_empty() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+// Borrowed view of the review instructions, read in place from the
+// instruction buffer. The processor uses it so review strings are only
+// copied when they are written to an account; clients keep `MovieInstruction`.
+pub(crate) enum ReviewInstruction<'a> {
+    AddMovieReview {
+        title: &'a str,
+        rating: u8,
+        description: &'a str,
+        language_code: [u8; 2],
+        watched_at: i64,
+        tight_size: bool,
+        is_sponsored: bool,
+        poster_uri: Option<&'a str>,
+        tags: Vec<&'a str>,
+    },
+    UpdateMovieReview {
+        title: &'a str,
+        rating: u8,
+        description: &'a str,
+        poster_uri: Option<&'a str>,
+        tags: Option<Vec<&'a str>>,
+    },
+    AddComment {
+        comment: &'a str,
+    },
+}
+
+impl<'a> ReviewInstruction<'a> {
+    // Accepts exactly the buffers `MovieInstruction::unpack` accepts for the
+    // same discriminators. Returns `None` for every other instruction.
+    pub(crate) fn unpack(input: &'a [u8]) -> Result<Option<Self>, ProgramError> {
+        let (&discriminator, rest) = input.split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        let mut reader = PayloadReader { data: rest };
+
+        Ok(Some(match discriminator {
+            0 => {
+                let title = reader.read_str()?;
+                let rating = reader.read_u8()?;
+                let description = reader.read_str()?;
+
+                // Everything after the description is omitted by older clients
+                let language_code = if reader.is_empty() {
+                    MovieAccountState::DEFAULT_LANGUAGE_CODE
+                } else {
+                    reader.read_array()?
+                };
+                let watched_at = if reader.is_empty() {
+                    0
+                } else {
+                    i64::from_le_bytes(reader.read_array()?)
+                };
+                let tight_size = if reader.is_empty() {
+                    false
+                } else {
+                    reader.read_bool()?
+                };
+                let is_sponsored = if reader.is_empty() {
+                    false
+                } else {
+                    reader.read_bool()?
+                };
+                let poster_uri = reader.read_trailing_poster_uri()?;
+                let tags = if reader.is_empty() {
+                    Vec::new()
+                } else {
+                    reader.read_str_vec()?
+                };
+                reader.finish()?;
+
+                Self::AddMovieReview {
+                    title,
+                    rating,
+                    description,
+                    language_code,
+                    watched_at,
+                    tight_size,
+                    is_sponsored,
+                    poster_uri,
+                    tags,
+                }
+            },
+            1 => {
+                let title = reader.read_str()?;
+                let rating = reader.read_u8()?;
+                let description = reader.read_str()?;
+                let poster_uri = reader.read_trailing_poster_uri()?;
+                let tags = if reader.is_empty() || !reader.read_bool()? {
+                    None
+                } else {
+                    Some(reader.read_str_vec()?)
+                };
+                reader.finish()?;
+
+                Self::UpdateMovieReview {
+                    title,
+                    rating,
+                    description,
+                    poster_uri,
+                    tags,
+                }
+            },
+            2 => {
+                let comment = reader.read_str()?;
+                reader.finish()?;
+
+                Self::AddComment { comment }
+            },
+            _ => return Ok(None)
+        }))
+    }
+}
+
+// Lets `benches/serialization.rs` time the borrowed parser, which is
+// otherwise crate-private. Returns whether it reads the instruction at all.
+#[doc(hidden)]
+pub fn bench_review_instruction_unpack(input: &[u8]) -> Result<bool, ProgramError> {
+    ReviewInstruction::unpack(input).map(|instruction| instruction.is_some())
+}
+
+// Walks borsh-encoded fields without copying them. Every length prefix is
+// checked against the bytes left before it is used.
+struct PayloadReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PayloadReader<'a> {
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        if len > self.data.len() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ProgramError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+
+        Ok(array)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, ProgramError> {
+        Ok(self.take(1)?[0])
+    }
+
+    // Borsh only accepts 0 and 1
+    fn read_bool(&mut self) -> Result<bool, ProgramError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    fn read_str(&mut self) -> Result<&'a str, ProgramError> {
+        let len = u32::from_le_bytes(self.read_array()?) as usize;
+
+        std::str::from_utf8(self.take(len)?)
+            .map_err(|_| ProgramError::InvalidInstructionData)
+    }
+
+    // Each string takes at least its 4-byte prefix, so a count the bytes left
+    // cannot hold is rejected before anything is allocated for it
+    fn read_str_vec(&mut self) -> Result<Vec<&'a str>, ProgramError> {
+        let len = u32::from_le_bytes(self.read_array()?) as usize;
+        if len > self.data.len() / 4 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        (0..len).map(|_| self.read_str()).collect()
+    }
+
+    // Optional poster in the trailing fields of a review payload, like `trailing_field`
+    fn read_trailing_poster_uri(&mut self) -> Result<Option<&'a str>, ProgramError> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        let poster_uri = if self.read_bool()? {
+            Some(self.read_str()?)
+        } else {
+            None
+        };
+
+        Ok(poster_uri)
+    }
+
+    fn finish(&self) -> Result<(), ProgramError> {
+        finish(self.data)
+    }
+}
+
+// Payloads that follow the discriminator byte. Public so the builders in
+// `test_utils` and off-chain clients serialize the same structs `unpack` reads.
+#[derive(BorshSerialize)]
+pub struct AddMovieReviewPayload {
+    pub title: String,
+    pub rating: u8,
+    pub description: String,
+    pub language_code: [u8; 2],
+    pub watched_at: i64,
+    pub tight_size: bool,
+    pub is_sponsored: bool,
+    pub poster_uri: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl AddMovieReviewPayload {
+    pub fn new(title: String, rating: u8, description: String) -> Self {
+        Self {
+            title,
+            rating,
+            description,
+            language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+            watched_at: 0,
+            tight_size: false,
+            is_sponsored: false,
+            poster_uri: None,
+            tags: Vec::new(),
+        }
+    }
+}
+
+// Everything after the description is omitted by older clients, down to
+// the baseline `{ title, rating, description }` payload
+impl BorshDeserialize for AddMovieReviewPayload {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let title = String::deserialize_reader(reader)?;
+        let rating = u8::deserialize_reader(reader)?;
+        let description = String::deserialize_reader(reader)?;
+
+        let (language_code, watched_at, tight_size, is_sponsored, poster_uri, tags) = trailing_fields(reader, |data| {
+            Ok((
+                trailing_field_or(data, MovieAccountState::DEFAULT_LANGUAGE_CODE)?,
+                trailing_field(data)?,
+                trailing_field(data)?,
+                trailing_field(data)?,
+                trailing_field(data)?,
+                trailing_field(data)?,
+            ))
+        })?;
+
+        Ok(Self {
+            title,
+            rating,
+            description,
+            language_code,
+            watched_at,
+            tight_size,
+            is_sponsored,
+            poster_uri,
+            tags,
+        })
+    }
+}
+
+#[derive(BorshSerialize)]
+pub struct MovieReviewPayload {
+    pub title: String,
+    pub rating: u8,
+    pub description: String,
+    // `None` keeps the current poster or tags
+    pub poster_uri: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+impl MovieReviewPayload {
+    pub fn new(title: String, rating: u8, description: String) -> Self {
+        Self {
+            title,
+            rating,
+            description,
+            poster_uri: None,
+            tags: None,
+        }
+    }
+}
+
+// The poster and tags are omitted by older clients
+impl BorshDeserialize for MovieReviewPayload {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let title = String::deserialize_reader(reader)?;
+        let rating = u8::deserialize_reader(reader)?;
+        let description = String::deserialize_reader(reader)?;
+
+        let (poster_uri, tags) = trailing_fields(reader, |data| {
+            Ok((trailing_field(data)?, trailing_field(data)?))
+        })?;
+
+        Ok(Self {
+            title,
+            rating,
+            description,
+            poster_uri,
+            tags,
+        })
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CommentPayload {
+    pub comment: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReviewLanguagePayload {
+    pub title: String,
+    pub language_code: [u8; 2],
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct GiftTokensPayload {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct QueryReviewsPayload {
+    pub min_rating: u8,
+    pub max_rating: u8,
+    pub max_results: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct BatchCreateATAsPayload {
+    pub users: Vec<Pubkey>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RewardMintPayload {
+    pub reward_mint: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ClaimRewardsPayload {
+    pub enabled: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SkipRevokedRewardsPayload {
+    pub enabled: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct DeleteReviewPayload {
+    pub title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ClawbackPeriodPayload {
+    pub seconds: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ViewAuditLogPayload {
+    pub title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SeriesPayload {
+    pub series_name: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AddToSeriesPayload {
+    pub series_name: String,
+    pub review_title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReactionPayload {
+    pub title: String,
+    pub reviewer: Pubkey,
+    pub reaction: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CommentModerationPayload {
+    pub title: String,
+    pub mode: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct NominateReviewPayload {
+    pub reviewer: Pubkey,
+    pub title: String,
+    pub category: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct VoteForNominationPayload {
+    pub reviewer: Pubkey,
+    pub title: String,
+    pub year: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct DeclareAwardWinnerPayload {
+    pub category: String,
+    pub year: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SyncCounterPayload {
+    pub title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UpdateMerkleRootPayload {
+    pub title: String,
+    pub root: [u8; 32],
+    pub num_comments: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct VerifyCommentInclusionPayload {
+    pub title: String,
+    pub comment_pda: Pubkey,
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ApproveCommentPayload {
+    pub title: String,
+    pub comment_index: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ApprovePendingCommentPayload {
+    pub submitter: Pubkey,
+    pub title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RejectPendingCommentPayload {
+    pub submitter: Pubkey,
+    pub title: String,
+    pub reason: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UpdateCommentTextPayload {
+    pub title: String,
+    pub comment_index: u64,
+    pub comment: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MarkSponsoredPayload {
+    pub title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CallbackProgramPayload {
+    pub program_id: Option<Pubkey>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RenameReviewPayload {
+    pub title: String,
+    pub new_title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MinDescriptionLenPayload {
+    pub min_len: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UpdateTagCloudPayload {
+    pub title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MintFirstReviewBadgePayload {
+    pub reviewer: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct FetchCommentsPayload {
+    pub review_title: String,
+    pub start_index: u64,
+    pub count: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct VerifyProgramVersionPayload {
+    pub expected_hash: [u8; 32],
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProposeAuthorityTransferPayload {
+    pub new_authority: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AutoVerifyThresholdPayload {
+    pub threshold: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CreateCollectionPayload {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CollectionReviewPayload {
+    pub collection_id: u64,
+    pub review_title: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CompactCommentPayload {
+    pub old_index: u64,
+    pub new_index: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SendDirectMessagePayload {
+    pub recipient: Pubkey,
+    pub message: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MarkMessageReadPayload {
+    pub sender: Pubkey,
+    pub message_index: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MaxRewardAmountPayload {
+    pub max_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ExportAllReviewsPayload {
+    pub reviewer: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CommentMintPayload {
+    pub comment_mint: Option<Pubkey>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use solana_program::pubkey::Pubkey;
+
+    use crate::test_utils::*;
+
+    // The builders in `test_utils` (and any off-chain client) serialize the
+    // payload structs behind a discriminator byte; these checks make sure the
+    // bytes they produce still decode to the intended variant and field values.
+
+    fn unpack_payload(payload: &impl borsh::BorshSerialize) -> MovieInstruction {
+        let bytes = borsh::to_vec(payload).unwrap();
+
+        MovieInstruction::unpack(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_add_movie_review_round_trip() {
+        let instruction = unpack_payload(&(0u8, AddMovieReviewPayload {
+            title: String::from("Captain America"),
+            rating: 3,
+            description: String::from("Liked the movie"),
+            language_code: *b"de",
+            watched_at: 1_700_000_000,
+            tight_size: true,
+            is_sponsored: true,
+            poster_uri: Some(String::from("ipfs://poster")),
+            tags: vec![String::from("classic"), String::from("rewatch")],
+        }));
+
+        let MovieInstruction::AddMovieReview { title, rating, description, language_code, watched_at, tight_size, is_sponsored, poster_uri, tags } = instruction else {
+            panic!("expected AddMovieReview");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(rating, 3);
+        assert_eq!(description, "Liked the movie");
+        assert_eq!(language_code, *b"de");
+        assert_eq!(watched_at, 1_700_000_000);
+        assert!(tight_size);
+        assert!(is_sponsored);
+        assert_eq!(poster_uri.as_deref(), Some("ipfs://poster"));
+        assert_eq!(tags, ["classic", "rewatch"]);
+    }
+
+    #[test]
+    fn test_add_movie_review_without_sponsored_flag() {
+        let mut bytes = borsh::to_vec(&(0u8, AddMovieReviewPayload::new(
+            String::from("Captain America"),
+            3,
+            String::from("Liked the movie"),
+        ))).unwrap();
+
+        // Older clients stop after `tight_size`
+        bytes.truncate(bytes.len() - 6);
+
+        let MovieInstruction::AddMovieReview { is_sponsored, poster_uri, tags, .. } = MovieInstruction::unpack(&bytes).unwrap() else {
+            panic!("expected AddMovieReview");
+        };
+        assert!(!is_sponsored);
+        assert_eq!(poster_uri, None);
+        assert!(tags.is_empty());
+
+        bytes.extend([2]);
+        assert!(MovieInstruction::unpack(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_add_movie_review_baseline_payload() {
+        // The original client only sent the title, rating and description
+        let bytes = borsh::to_vec(&(
+            0u8,
+            String::from("Captain America"),
+            3u8,
+            String::from("Liked the movie"),
+        )).unwrap();
+
+        let MovieInstruction::AddMovieReview { title, rating, description, language_code, watched_at, tight_size, is_sponsored, poster_uri, tags } = MovieInstruction::unpack(&bytes).unwrap() else {
+            panic!("expected AddMovieReview");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(rating, 3);
+        assert_eq!(description, "Liked the movie");
+        assert_eq!(language_code, MovieAccountState::DEFAULT_LANGUAGE_CODE);
+        assert_eq!(watched_at, 0);
+        assert!(!tight_size);
+        assert!(!is_sponsored);
+        assert_eq!(poster_uri, None);
+        assert!(tags.is_empty());
+
+        assert!(matches!(
+            ReviewInstruction::unpack(&bytes).unwrap(),
+            Some(ReviewInstruction::AddMovieReview { language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE, watched_at: 0, tight_size: false, .. })
+        ));
+
+        // A partial language code is still malformed
+        let mut partial = bytes.clone();
+        partial.push(b'd');
+        assert!(MovieInstruction::unpack(&partial).is_err());
+        assert!(ReviewInstruction::unpack(&partial).is_err());
+    }
+
+    #[test]
+    fn test_mark_sponsored_round_trip() {
+        let instruction = unpack_payload(&(114u8, MarkSponsoredPayload {
+            title: String::from("Captain America"),
+        }));
+
+        let MovieInstruction::MarkSponsored { title } = instruction else {
+            panic!("expected MarkSponsored");
+        };
+
+        assert_eq!(title, "Captain America");
+    }
+
+    #[test]
+    fn test_update_tag_cloud_round_trip() {
+        let instruction = unpack_payload(&(115u8, UpdateTagCloudPayload {
+            title: String::from("The Dark Knight"),
+        }));
+
+        let MovieInstruction::UpdateTagCloud { title } = instruction else {
+            panic!("expected UpdateTagCloud");
+        };
+
+        assert_eq!(title, "The Dark Knight");
+    }
+
+    #[test]
+    fn test_mint_first_review_badge_round_trip() {
+        let reviewer = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(116u8, MintFirstReviewBadgePayload {
+            reviewer,
+        }));
+
+        let MovieInstruction::MintFirstReviewBadge { reviewer: decoded } = instruction else {
+            panic!("expected MintFirstReviewBadge");
+        };
+
+        assert_eq!(decoded, reviewer);
+    }
+
+    #[test]
+    fn test_fetch_comments_round_trip() {
+        let instruction = unpack_payload(&(117u8, FetchCommentsPayload {
+            review_title: String::from("Captain America"),
+            start_index: 4,
+            count: 3,
+        }));
+
+        let MovieInstruction::FetchComments { review_title, start_index, count } = instruction else {
+            panic!("expected FetchComments");
+        };
+
+        assert_eq!(review_title, "Captain America");
+        assert_eq!(start_index, 4);
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_verify_program_version_round_trip() {
+        let instruction = unpack_payload(&(118u8, VerifyProgramVersionPayload {
+            expected_hash: [7; 32],
+        }));
+
+        let MovieInstruction::VerifyProgramVersion { expected_hash } = instruction else {
+            panic!("expected VerifyProgramVersion");
+        };
+
+        assert_eq!(expected_hash, [7; 32]);
+    }
+
+    #[test]
+    fn test_authority_transfer_round_trip() {
+        let new_authority = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(119u8, ProposeAuthorityTransferPayload {
+            new_authority,
+        }));
+
+        let MovieInstruction::ProposeAuthorityTransfer { new_authority: decoded } = instruction else {
+            panic!("expected ProposeAuthorityTransfer");
+        };
+
+        assert_eq!(decoded, new_authority);
+
+        assert!(matches!(
+            MovieInstruction::unpack(&[120]).unwrap(),
+            MovieInstruction::AcceptAuthorityTransfer
+        ));
+    }
+
+    #[test]
+    fn test_ping_round_trip() {
+        let ping_ix = create_ping_ix(Pubkey::new_unique());
+
+        assert!(ping_ix.accounts.is_empty());
+        assert!(matches!(
+            MovieInstruction::unpack(&ping_ix.data).unwrap(),
+            MovieInstruction::Ping
+        ));
+    }
+
+    #[test]
+    fn test_update_movie_review_round_trip() {
+        let instruction = unpack_payload(&(1u8, MovieReviewPayload {
+            title: String::from("Captain America"),
+            rating: 2,
+            description: String::from("Didn't like the movie"),
+            poster_uri: Some(String::from("https://example.com/poster.png")),
+            tags: Some(vec![String::from("rewatch")]),
+        }));
+
+        let MovieInstruction::UpdateMovieReview { title, rating, description, poster_uri, tags } = instruction else {
+            panic!("expected UpdateMovieReview");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(rating, 2);
+        assert_eq!(description, "Didn't like the movie");
+        assert_eq!(poster_uri.as_deref(), Some("https://example.com/poster.png"));
+        assert_eq!(tags, Some(vec![String::from("rewatch")]));
+    }
+
+    #[test]
+    fn test_add_comment_round_trip() {
+        let instruction = unpack_payload(&(2u8, CommentPayload {
+            comment: String::from("Totally agree!"),
+        }));
+
+        let MovieInstruction::AddComment { comment } = instruction else {
+            panic!("expected AddComment");
+        };
+
+        assert_eq!(comment, "Totally agree!");
+    }
+
+    #[test]
+    fn test_change_review_language_round_trip() {
+        let instruction = unpack_payload(&(95u8, ReviewLanguagePayload {
+            title: String::from("Captain America"),
+            language_code: *b"fr",
+        }));
+
+        let MovieInstruction::ChangeReviewLanguage { title, language_code } = instruction else {
+            panic!("expected ChangeReviewLanguage");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(language_code, *b"fr");
+    }
+
+    #[test]
+    fn test_gift_tokens_round_trip() {
+        let recipient = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(96u8, GiftTokensPayload {
+            recipient,
+            amount: 42,
+        }));
+
+        let MovieInstruction::GiftTokens { recipient: decoded_recipient, amount } = instruction else {
+            panic!("expected GiftTokens");
+        };
+
+        assert_eq!(decoded_recipient, recipient);
+        assert_eq!(amount, 42);
+    }
+
+    #[test]
+    fn test_query_reviews_by_range_round_trip() {
+        let instruction = unpack_payload(&(97u8, QueryReviewsPayload {
+            min_rating: 2,
+            max_rating: 4,
+            max_results: 7,
+        }));
+
+        let MovieInstruction::QueryReviewsByRange { min_rating, max_rating, max_results } = instruction else {
+            panic!("expected QueryReviewsByRange");
+        };
+
+        assert_eq!((min_rating, max_rating, max_results), (2, 4, 7));
+    }
+
+    #[test]
+    fn test_batch_create_atas_round_trip() {
+        let users = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let instruction = unpack_payload(&(98u8, BatchCreateATAsPayload {
+            users: users.clone(),
+        }));
+
+        let MovieInstruction::BatchCreateATAs { users: decoded_users } = instruction else {
+            panic!("expected BatchCreateATAs");
+        };
+
+        assert_eq!(decoded_users, users);
+    }
+
+    #[test]
+    fn test_view_audit_log_round_trip() {
+        let instruction = unpack_payload(&(100u8, ViewAuditLogPayload {
+            title: String::from("Captain America"),
+        }));
+
+        let MovieInstruction::ViewAuditLog { title } = instruction else {
+            panic!("expected ViewAuditLog");
+        };
+
+        assert_eq!(title, "Captain America");
+    }
+
+    #[test]
+    fn test_set_reward_mint_round_trip() {
+        let reward_mint = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(6u8, RewardMintPayload {
+            reward_mint,
+        }));
+
+        let MovieInstruction::SetRewardMint { reward_mint: decoded_reward_mint } = instruction else {
+            panic!("expected SetRewardMint");
+        };
+
+        assert_eq!(decoded_reward_mint, reward_mint);
+    }
+
+    #[test]
+    fn test_set_claim_rewards_round_trip() {
+        let instruction = unpack_payload(&(8u8, ClaimRewardsPayload {
+            enabled: true,
+        }));
+
+        let MovieInstruction::SetClaimRewards { enabled } = instruction else {
+            panic!("expected SetClaimRewards");
+        };
+
+        assert!(enabled);
+    }
+
+    #[test]
+    fn test_set_skip_revoked_rewards_round_trip() {
+        let instruction = unpack_payload(&(13u8, SkipRevokedRewardsPayload {
+            enabled: true,
+        }));
+
+        let MovieInstruction::SetSkipRevokedRewards { enabled } = instruction else {
+            panic!("expected SetSkipRevokedRewards");
+        };
+
+        assert!(enabled);
+    }
+
+    #[test]
+    fn test_set_callback_program_round_trip() {
+        let callback = Pubkey::new_unique();
+
+        for expected in [Some(callback), None] {
+            let instruction = unpack_payload(&(113u8, CallbackProgramPayload {
+                program_id: expected,
+            }));
+
+            let MovieInstruction::SetCallbackProgram { program_id } = instruction else {
+                panic!("expected SetCallbackProgram");
+            };
+
+            assert_eq!(program_id, expected);
+        }
+    }
+
+    #[test]
+    fn test_delete_movie_review_round_trip() {
+        let instruction = unpack_payload(&(9u8, DeleteReviewPayload {
+            title: String::from("Captain America"),
+        }));
+
+        let MovieInstruction::DeleteMovieReview { title } = instruction else {
+            panic!("expected DeleteMovieReview");
+        };
+
+        assert_eq!(title, "Captain America");
+    }
+
+    #[test]
+    fn test_set_clawback_period_round_trip() {
+        let instruction = unpack_payload(&(10u8, ClawbackPeriodPayload {
+            seconds: 3_600,
+        }));
+
+        let MovieInstruction::SetClawbackPeriod { seconds } = instruction else {
+            panic!("expected SetClawbackPeriod");
+        };
+
+        assert_eq!(seconds, 3_600);
+    }
+
+    #[test]
+    fn test_set_min_description_len_round_trip() {
+        let instruction = unpack_payload(&(17u8, MinDescriptionLenPayload {
+            min_len: 40,
+        }));
+
+        let MovieInstruction::SetMinDescriptionLenForReward { min_len } = instruction else {
+            panic!("expected SetMinDescriptionLenForReward");
+        };
+
+        assert_eq!(min_len, 40);
+    }
+
+    #[test]
+    fn test_set_auto_verify_threshold_round_trip() {
+        let instruction = unpack_payload(&(121u8, AutoVerifyThresholdPayload {
+            threshold: 2,
+        }));
+
+        let MovieInstruction::SetAutoVerifyThreshold { threshold } = instruction else {
+            panic!("expected SetAutoVerifyThreshold");
+        };
+
+        assert_eq!(threshold, 2);
+    }
+
+    #[test]
+    fn test_collection_round_trip() {
+        let instruction = unpack_payload(&(122u8, CreateCollectionPayload {
+            name: String::from("Marvel"),
+            description: String::from("Phase one"),
+        }));
+
+        let MovieInstruction::CreateCollection { name, description } = instruction else {
+            panic!("expected CreateCollection");
+        };
+
+        assert_eq!(name, "Marvel");
+        assert_eq!(description, "Phase one");
+
+        let instruction = unpack_payload(&(123u8, CollectionReviewPayload {
+            collection_id: 4,
+            review_title: String::from("Iron Man"),
+        }));
+
+        let MovieInstruction::AddReviewToCollection { collection_id, review_title } = instruction else {
+            panic!("expected AddReviewToCollection");
+        };
+
+        assert_eq!(collection_id, 4);
+        assert_eq!(review_title, "Iron Man");
+
+        let instruction = unpack_payload(&(124u8, CollectionReviewPayload {
+            collection_id: 4,
+            review_title: String::from("Iron Man"),
+        }));
+
+        let MovieInstruction::RemoveReviewFromCollection { collection_id, review_title } = instruction else {
+            panic!("expected RemoveReviewFromCollection");
+        };
+
+        assert_eq!(collection_id, 4);
+        assert_eq!(review_title, "Iron Man");
+    }
+
+    #[test]
+    fn test_compact_comment_round_trip() {
+        let instruction = unpack_payload(&(18u8, CompactCommentPayload {
+            old_index: 7,
+            new_index: 2,
+        }));
+
+        let MovieInstruction::CompactComment { old_index, new_index } = instruction else {
+            panic!("expected CompactComment");
+        };
+
+        assert_eq!(old_index, 7);
+        assert_eq!(new_index, 2);
+    }
+
+    #[test]
+    fn test_direct_message_round_trip() {
+        let recipient = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(125u8, SendDirectMessagePayload {
+            recipient,
+            message: String::from("Loved your take"),
+        }));
+
+        let MovieInstruction::SendDirectMessage { recipient: unpacked, message } = instruction else {
+            panic!("expected SendDirectMessage");
+        };
+
+        assert_eq!(unpacked, recipient);
+        assert_eq!(message, "Loved your take");
+
+        let sender = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(126u8, MarkMessageReadPayload {
+            sender,
+            message_index: 3,
+        }));
+
+        let MovieInstruction::MarkMessageRead { sender: unpacked, message_index } = instruction else {
+            panic!("expected MarkMessageRead");
+        };
+
+        assert_eq!(unpacked, sender);
+        assert_eq!(message_index, 3);
+    }
+
+    #[test]
+    fn test_set_max_reward_amount_round_trip() {
+        let instruction = unpack_payload(&(19u8, MaxRewardAmountPayload {
+            max_amount: 5_000_000_000,
+        }));
+
+        let MovieInstruction::SetMaxRewardAmount { max_amount } = instruction else {
+            panic!("expected SetMaxRewardAmount");
+        };
+
+        assert_eq!(max_amount, 5_000_000_000);
+    }
+
+    #[test]
+    fn test_export_all_reviews_round_trip() {
+        let reviewer = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(127u8, ExportAllReviewsPayload {
+            reviewer,
+        }));
+
+        let MovieInstruction::ExportAllReviews { reviewer: unpacked } = instruction else {
+            panic!("expected ExportAllReviews");
+        };
+
+        assert_eq!(unpacked, reviewer);
+    }
+
+    #[test]
+    fn test_recompute_program_stats_round_trip() {
+        assert!(matches!(
+            MovieInstruction::unpack(&[128]).unwrap(),
+            MovieInstruction::RecomputeProgramStats
+        ));
+    }
+
+    #[test]
+    fn test_set_comment_mint_round_trip() {
+        let comment_mint = Pubkey::new_unique();
+
+        for expected in [Some(comment_mint), None] {
+            let instruction = unpack_payload(&(20u8, CommentMintPayload {
+                comment_mint: expected,
+            }));
+
+            let MovieInstruction::SetCommentMint { comment_mint } = instruction else {
+                panic!("expected SetCommentMint");
+            };
+
+            assert_eq!(comment_mint, expected);
+        }
+    }
+
+    #[cfg(feature = "test-panic")]
+    #[test]
+    fn test_test_panic_round_trip() {
+        assert!(matches!(
+            MovieInstruction::unpack(&[255]).unwrap(),
+            MovieInstruction::TestPanic
+        ));
+    }
+
+    #[test]
+    fn test_freeze_token_mint_round_trip() {
+        assert!(matches!(
+            MovieInstruction::unpack(&[129]).unwrap(),
+            MovieInstruction::FreezeTokenMint
+        ));
+
+        assert!(matches!(
+            MovieInstruction::unpack(&[130]).unwrap(),
+            MovieInstruction::ThawTokenMint
+        ));
+    }
+
+    #[test]
+    fn test_approve_pending_comment_round_trip() {
+        let submitter = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(131u8, ApprovePendingCommentPayload {
+            submitter,
+            title: String::from("Captain America"),
+        }));
+
+        let MovieInstruction::ApprovePendingComment { submitter: decoded_submitter, title } = instruction else {
+            panic!("expected ApprovePendingComment");
+        };
+
+        assert_eq!(decoded_submitter, submitter);
+        assert_eq!(title, "Captain America");
+
+        let instruction = unpack_payload(&(132u8, RejectPendingCommentPayload {
+            submitter,
+            title: String::from("Captain America"),
+            reason: String::from("Spoilers"),
+        }));
+
+        let MovieInstruction::RejectPendingComment { submitter: decoded_submitter, title, reason } = instruction else {
+            panic!("expected RejectPendingComment");
+        };
+
+        assert_eq!(decoded_submitter, submitter);
+        assert_eq!(title, "Captain America");
+        assert_eq!(reason, "Spoilers");
+    }
+
+    #[test]
+    fn test_series_round_trip() {
+        let instruction = unpack_payload(&(101u8, SeriesPayload {
+            series_name: String::from("Star Wars"),
+        }));
+
+        let MovieInstruction::CreateSeries { name } = instruction else {
+            panic!("expected CreateSeries");
+        };
+
+        assert_eq!(name, "Star Wars");
+
+        let instruction = unpack_payload(&(102u8, AddToSeriesPayload {
+            series_name: String::from("Star Wars"),
+            review_title: String::from("A New Hope"),
+        }));
+
+        let MovieInstruction::AddToSeries { series_name, review_title } = instruction else {
+            panic!("expected AddToSeries");
+        };
+
+        assert_eq!(series_name, "Star Wars");
+        assert_eq!(review_title, "A New Hope");
+
+        let instruction = unpack_payload(&(103u8, SeriesPayload {
+            series_name: String::from("Star Wars"),
+        }));
+
+        let MovieInstruction::FinalizeSeries { series_name } = instruction else {
+            panic!("expected FinalizeSeries");
+        };
+
+        assert_eq!(series_name, "Star Wars");
+    }
+
+    #[test]
+    fn test_react_to_review_round_trip() {
+        let reviewer = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(104u8, ReactionPayload {
+            title: String::from("Captain America"),
+            reviewer,
+            reaction: 3,
+        }));
+
+        let MovieInstruction::ReactToReview { title, reviewer: decoded_reviewer, reaction } = instruction else {
+            panic!("expected ReactToReview");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(decoded_reviewer, reviewer);
+        assert_eq!(reaction, 3);
+    }
+
+    #[test]
+    fn test_comment_moderation_round_trip() {
+        let instruction = unpack_payload(&(105u8, CommentModerationPayload {
+            title: String::from("Captain America"),
+            mode: 2,
+        }));
+
+        let MovieInstruction::SetCommentModeration { title, mode } = instruction else {
+            panic!("expected SetCommentModeration");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(mode, 2);
+
+        let instruction = unpack_payload(&(11u8, ApproveCommentPayload {
+            title: String::from("Captain America"),
+            comment_index: 7,
+        }));
+
+        let MovieInstruction::ApproveComment { title, comment_index } = instruction else {
+            panic!("expected ApproveComment");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(comment_index, 7);
+    }
+
+    #[test]
+    fn test_update_comment_text_round_trip() {
+        let instruction = unpack_payload(&(15u8, UpdateCommentTextPayload {
+            title: String::from("Captain America"),
+            comment_index: 7,
+            comment: String::from("Changed my mind"),
+        }));
+
+        let MovieInstruction::UpdateCommentText { title, comment_index, comment } = instruction else {
+            panic!("expected UpdateCommentText");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(comment_index, 7);
+        assert_eq!(comment, "Changed my mind");
+    }
+
+    #[test]
+    fn test_rename_review_round_trip() {
+        let instruction = unpack_payload(&(16u8, RenameReviewPayload {
+            title: String::from("Captian America"),
+            new_title: String::from("Captain America"),
+        }));
+
+        let MovieInstruction::RenameReview { title, new_title } = instruction else {
+            panic!("expected RenameReview");
+        };
+
+        assert_eq!(title, "Captian America");
+        assert_eq!(new_title, "Captain America");
+    }
+
+    #[test]
+    fn test_award_round_trip() {
+        let reviewer = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(106u8, NominateReviewPayload {
+            reviewer,
+            title: String::from("Captain America"),
+            category: String::from("Best Review"),
+        }));
+
+        let MovieInstruction::NominateReview { reviewer: decoded_reviewer, title, category } = instruction else {
+            panic!("expected NominateReview");
+        };
+
+        assert_eq!(decoded_reviewer, reviewer);
+        assert_eq!(title, "Captain America");
+        assert_eq!(category, "Best Review");
+
+        let instruction = unpack_payload(&(107u8, VoteForNominationPayload {
+            reviewer,
+            title: String::from("Captain America"),
+            year: 2024,
+        }));
+
+        let MovieInstruction::VoteForNomination { reviewer: decoded_reviewer, title, year } = instruction else {
+            panic!("expected VoteForNomination");
+        };
+
+        assert_eq!(decoded_reviewer, reviewer);
+        assert_eq!(title, "Captain America");
+        assert_eq!(year, 2024);
+
+        let instruction = unpack_payload(&(108u8, DeclareAwardWinnerPayload {
+            category: String::from("Best Review"),
+            year: 2024,
+        }));
+
+        let MovieInstruction::DeclareAwardWinner { category, year } = instruction else {
+            panic!("expected DeclareAwardWinner");
+        };
+
+        assert_eq!(category, "Best Review");
+        assert_eq!(year, 2024);
+    }
+
+    #[test]
+    fn test_sync_counter_round_trip() {
+        let instruction = unpack_payload(&(109u8, SyncCounterPayload {
+            title: String::from("Captain America"),
+        }));
+
+        let MovieInstruction::SyncCounter { title } = instruction else {
+            panic!("expected SyncCounter");
+        };
+
+        assert_eq!(title, "Captain America");
+
+        let instruction = unpack_payload(&(14u8, SyncCounterPayload {
+            title: String::from("Captain America"),
+        }));
+
+        let MovieInstruction::SyncCommentCounter { title } = instruction else {
+            panic!("expected SyncCommentCounter");
+        };
+
+        assert_eq!(title, "Captain America");
+    }
+
+    #[test]
+    fn test_merkle_root_round_trip() {
+        let instruction = unpack_payload(&(111u8, UpdateMerkleRootPayload {
+            title: String::from("Captain America"),
+            root: [7; 32],
+            num_comments: 2,
+        }));
+
+        let MovieInstruction::UpdateMerkleRoot { title, root, num_comments } = instruction else {
+            panic!("expected UpdateMerkleRoot");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(root, [7; 32]);
+        assert_eq!(num_comments, 2);
+
+        let comment_pda = Pubkey::new_unique();
+
+        let instruction = unpack_payload(&(112u8, VerifyCommentInclusionPayload {
+            title: String::from("Captain America"),
+            comment_pda,
+            proof: vec![[1; 32], [2; 32]],
+        }));
+
+        let MovieInstruction::VerifyCommentInclusion { title, comment_pda: decoded_comment_pda, proof } = instruction else {
+            panic!("expected VerifyCommentInclusion");
+        };
+
+        assert_eq!(title, "Captain America");
+        assert_eq!(decoded_comment_pda, comment_pda);
+        assert_eq!(proof, vec![[1; 32], [2; 32]]);
+    }
+
+    #[test]
+    fn test_builder_instruction_data_round_trip() {
+        let program_id = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        let (_mint, _mint_auth, init_mint_ix) = create_init_mint_ix(payer, &program_id);
+        assert!(matches!(
+            MovieInstruction::unpack(&init_mint_ix.data).unwrap(),
+            MovieInstruction::InitializeMint
+        ));
+
+        let migrate_ix = create_migrate_legacy_review_ix(
+            payer, 
+            program_id, 
+            String::from("Captain America"),
+        );
+        assert!(matches!(
+            MovieInstruction::unpack(&migrate_ix.data).unwrap(),
+            MovieInstruction::MigrateLegacyReview
+        ));
+
+        let initialize_config_ix = create_initialize_config_ix(payer, program_id);
+        assert!(matches!(
+            MovieInstruction::unpack(&initialize_config_ix.data).unwrap(),
+            MovieInstruction::InitializeConfig
+        ));
+
+        let backfill_reward_ix = create_backfill_reward_ix(
+            payer, 
+            program_id, 
+            Pubkey::new_unique(), 
+            Pubkey::new_unique(), 
+            Pubkey::new_unique(),
+        );
+        assert!(matches!(
+            MovieInstruction::unpack(&backfill_reward_ix.data).unwrap(),
+            MovieInstruction::BackfillReward
+        ));
+
+        let claim_rewards_ix = create_claim_rewards_ix(
+            payer, 
+            program_id, 
+            Pubkey::new_unique(), 
+            Pubkey::new_unique(),
+        );
+        assert!(matches!(
+            MovieInstruction::unpack(&claim_rewards_ix.data).unwrap(),
+            MovieInstruction::ClaimRewards
+        ));
+    }
+
+    // Runs both parsers over `input` and checks they agree on the outcome
+    // and on every field
+    fn assert_same_parse(input: &[u8]) {
+        match (MovieInstruction::unpack(input), ReviewInstruction::unpack(input)) {
+            (Err(owned), Err(borrowed)) => assert_eq!(owned, borrowed),
+            (
+                Ok(MovieInstruction::AddMovieReview { title, rating, description, language_code, watched_at, tight_size, is_sponsored, poster_uri, tags }),
+                Ok(Some(ReviewInstruction::AddMovieReview {
+                    title: borrowed_title,
+                    rating: borrowed_rating,
+                    description: borrowed_description,
+                    language_code: borrowed_language_code,
+                    watched_at: borrowed_watched_at,
+                    tight_size: borrowed_tight_size,
+                    is_sponsored: borrowed_is_sponsored,
+                    poster_uri: borrowed_poster_uri,
+                    tags: borrowed_tags,
+                })),
+            ) => {
+                assert_eq!(title, borrowed_title);
+                assert_eq!(rating, borrowed_rating);
+                assert_eq!(description, borrowed_description);
+                assert_eq!(language_code, borrowed_language_code);
+                assert_eq!(watched_at, borrowed_watched_at);
+                assert_eq!(tight_size, borrowed_tight_size);
+                assert_eq!(is_sponsored, borrowed_is_sponsored);
+                assert_eq!(poster_uri.as_deref(), borrowed_poster_uri);
+                assert_eq!(tags, borrowed_tags);
+            },
+            (
+                Ok(MovieInstruction::UpdateMovieReview { title, rating, description, poster_uri, tags }),
+                Ok(Some(ReviewInstruction::UpdateMovieReview {
+                    title: borrowed_title,
+                    rating: borrowed_rating,
+                    description: borrowed_description,
+                    poster_uri: borrowed_poster_uri,
+                    tags: borrowed_tags,
+                })),
+            ) => {
+                assert_eq!(title, borrowed_title);
+                assert_eq!(rating, borrowed_rating);
+                assert_eq!(description, borrowed_description);
+                assert_eq!(poster_uri.as_deref(), borrowed_poster_uri);
+                assert_eq!(tags, borrowed_tags.map(|tags| tags.into_iter().map(String::from).collect::<Vec<_>>()));
+            },
+            (
+                Ok(MovieInstruction::AddComment { comment }),
+                Ok(Some(ReviewInstruction::AddComment { comment: borrowed_comment })),
+            ) => {
+                assert_eq!(comment, borrowed_comment);
+            },
+            _ => panic!("parsers disagree on {:?}", input),
+        }
+    }
+
+    #[test]
+    fn test_borrowed_review_parser_matches_borsh() {
+        let mut add_review = AddMovieReviewPayload::new(
+            String::from("Captain America"),
+            3,
+            String::from("Liked the movie"),
+        );
+        add_review.is_sponsored = true;
+        add_review.poster_uri = Some(String::from("ipfs://poster"));
+        add_review.tags = vec![String::from("classic"), String::new()];
+
+        let mut corpus = vec![
+            borsh::to_vec(&(0u8, &add_review)).unwrap(),
+            borsh::to_vec(&(0u8, AddMovieReviewPayload::new(
+                String::from("Блейдраннер"),
+                5,
+                String::new(),
+            ))).unwrap(),
+            borsh::to_vec(&(1u8, MovieReviewPayload {
+                title: String::from("Captain America"),
+                rating: 2,
+                description: String::from("Didn't hold up"),
+                poster_uri: Some(String::from("https://poster")),
+                tags: Some(vec![String::from("rewatch")]),
+            })).unwrap(),
+            borsh::to_vec(&(1u8, MovieReviewPayload {
+                title: String::from("Captain America"),
+                rating: 2,
+                description: String::from("Didn't hold up"),
+                poster_uri: None,
+                tags: None,
+            })).unwrap(),
+            borsh::to_vec(&(2u8, CommentPayload {
+                comment: String::from("Great review"),
+            })).unwrap(),
+        ];
+
+        // Malformed variants of every valid payload: each truncation, which
+        // also covers older clients dropping the trailing fields, and a
+        // stray trailing byte
+        for bytes in corpus.clone() {
+            for len in 0..bytes.len() {
+                corpus.push(bytes[..len].to_vec());
+            }
+
+            let mut trailing = bytes.clone();
+            trailing.push(0);
+            corpus.push(trailing);
+        }
+
+        let add_bytes = borsh::to_vec(&(0u8, &add_review)).unwrap();
+        let title_len = add_review.title.len();
+        let tight_size_offset = 1 + (4 + title_len) + 1 + (4 + add_review.description.len()) + 2 + 8;
+
+        // Title that is not UTF-8
+        let mut invalid_utf8 = add_bytes.clone();
+        invalid_utf8[5] = 0xff;
+        corpus.push(invalid_utf8);
+
+        // Length prefix far past the end of the buffer
+        let mut oversized_len = add_bytes.clone();
+        oversized_len[1..5].copy_from_slice(&u32::MAX.to_le_bytes());
+        corpus.push(oversized_len);
+
+        // Booleans and the option tag only take 0 or 1
+        let mut invalid_bool = add_bytes.clone();
+        invalid_bool[tight_size_offset] = 2;
+        corpus.push(invalid_bool);
+
+        let mut invalid_sponsored = add_bytes.clone();
+        invalid_sponsored[tight_size_offset + 1] = 2;
+        corpus.push(invalid_sponsored);
+
+        let mut invalid_option = add_bytes.clone();
+        invalid_option[tight_size_offset + 2] = 2;
+        corpus.push(invalid_option);
+
+        // Tag count far past the end of the buffer
+        let tags_offset = add_bytes.len() - borsh::to_vec(&add_review.tags).unwrap().len();
+        let mut oversized_tags = add_bytes;
+        oversized_tags[tags_offset..tags_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        corpus.push(oversized_tags);
+
+        for input in &corpus {
+            assert_same_parse(input);
+        }
+    }
+
+    #[test]
+    fn test_borrowed_review_parser_skips_other_instructions() {
+        assert!(matches!(ReviewInstruction::unpack(&[3]), Ok(None)));
+        assert!(matches!(ReviewInstruction::unpack(&[118, 0]), Ok(None)));
+        assert!(ReviewInstruction::unpack(&[]).is_err());
+    }
 }
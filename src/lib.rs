@@ -1,5 +1,98 @@
-mod entrypoint;
-mod processor;
-mod instruction;
-mod state;
-mod error;
\ No newline at end of file
+//! Movie review program.
+//!
+//! The crate builds as a `cdylib` for deployment and as an `rlib` for
+//! clients and other programs. Downstream programs should enable the
+//! `no-entrypoint` feature so the entrypoint symbol is not linked twice.
+//!
+//! Reading a review account from raw bytes:
+//!
+//! ```
+//! use borsh::BorshSerialize;
+//! use movie_review_program::MovieAccountState;
+//! use solana_program::{borsh1::try_from_slice_unchecked, pubkey::Pubkey};
+//!
+//! let review = MovieAccountState {
+//!     discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+//!     is_initialized: true,
+//!     version: MovieAccountState::VERSION,
+//!     reviewer: Pubkey::new_unique(),
+//!     rating: 4,
+//!     language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+//!     watched_at: 0,
+//!     created_at: 0,
+//!     reactions: [0; MovieAccountState::REACTION_KINDS],
+//!     comment_moderation: MovieAccountState::COMMENTS_OPEN,
+//!     reward_minted: true,
+//!     tight_size: false,
+//!     is_sponsored: false,
+//!     title: String::from("Captain America"),
+//!     description: String::from("Liked the movie"),
+//!     poster_uri: None,
+//!     tags: Vec::new(),
+//! };
+//!
+//! // Review accounts are allocated at `LEN` bytes and zero padded
+//! let mut data = vec![0; MovieAccountState::LEN];
+//! review.serialize(&mut &mut data[..]).unwrap();
+//!
+//! let decoded: MovieAccountState = try_from_slice_unchecked(&data).unwrap();
+//! assert_eq!(decoded.title, "Captain America");
+//! assert_eq!(decoded.rating, 4);
+//! ```
+//!
+//! Adding reviews through CPI:
+//!
+//! `AddMovieReview` only needs the reviewer to sign, so another program can
+//! post reviews on behalf of one of its PDAs with `invoke_signed`. The PDA is
+//! then the reviewer: the review is seeded by `[pda, title]`, later updates
+//! and deletes must be signed by the same PDA, and the reward goes to the
+//! PDA's associated token account. The PDA only pays for the new accounts
+//! itself if it is a funded system account; otherwise fill the sponsor slot
+//! (the account after the reviewer profile PDA) with a signer that pays instead. Signer
+//! privileges are checked by the runtime, so invoking without the PDA's
+//! seeds fails before the program runs.
+//!
+//! With `no-entrypoint` enabled, the `cpi` module builds and invokes these
+//! calls: fill a `cpi::CpiContext` with the accounts and the PDA's seeds and
+//! pass it to `cpi::add_movie_review` or `cpi::update_movie_review`.
+//!
+//! Title normalization:
+//!
+//! Review PDAs are seeded by the raw title bytes, so "Inception" and
+//! "inception" are separate reviews. Building with the `normalize-titles`
+//! feature trims and lowercases the title before seeding (see
+//! `MovieAccountState::title_seed`); the stored `title` keeps the spelling
+//! the reviewer used. Normalization is one way: the original casing cannot
+//! be recovered from the address, and reviews created without the feature
+//! under a non-normalized title are no longer reachable once it is enabled.
+//!
+//! Anchor events:
+//!
+//! With the `anchor-events` feature, adding or updating a review and adding a
+//! comment log an event from the `events` module in Anchor's `emit!` format:
+//! a `Program data:` line with base64 of the event discriminator and the
+//! borsh-encoded event. Anchor's event parsers decode these lines as they
+//! would an Anchor program's events.
+
+#[cfg(not(feature = "no-entrypoint"))]
+pub mod entrypoint;
+pub mod processor;
+pub mod instruction;
+pub mod state;
+pub mod error;
+pub mod math;
+pub mod events;
+pub mod sysvar;
+
+#[cfg(feature = "no-entrypoint")]
+pub mod cpi;
+
+#[cfg(any(test, feature = "client"))]
+pub mod client;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+pub use instruction::MovieInstruction;
+pub use state::{MovieAccountState, MovieComment, MovieCommentCounter};
+pub use error::ReviewError;
@@ -0,0 +1,102 @@
+use crate::error::ReviewError;
+
+// Checked arithmetic for counters, totals and lamport/token amounts. Each
+// helper reports a wrapped result as a `ReviewError`, so handlers can `?`
+// it straight into a `ProgramResult`.
+
+pub trait CheckedArithmetic: Copy {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_arithmetic {
+    ($($ty:ty),*) => {
+        $(
+            impl CheckedArithmetic for $ty {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_arithmetic!(u8, u16, u32, u64, usize, i64);
+
+pub fn checked_add<T: CheckedArithmetic>(lhs: T, rhs: T) -> Result<T, ReviewError> {
+    CheckedArithmetic::checked_add(lhs, rhs).ok_or(ReviewError::ArithmeticOverflow)
+}
+
+pub fn checked_sub<T: CheckedArithmetic>(lhs: T, rhs: T) -> Result<T, ReviewError> {
+    CheckedArithmetic::checked_sub(lhs, rhs).ok_or(ReviewError::ArithmeticUnderflow)
+}
+
+pub fn checked_mul<T: CheckedArithmetic>(lhs: T, rhs: T) -> Result<T, ReviewError> {
+    CheckedArithmetic::checked_mul(lhs, rhs).ok_or(ReviewError::ArithmeticOverflow)
+}
+
+pub fn checked_sum<T: CheckedArithmetic + Default>(values: impl IntoIterator<Item = T>) -> Result<T, ReviewError> {
+    values.into_iter().try_fold(T::default(), checked_add)
+}
+
+// Narrowing conversion, e.g. a `Vec` length into a `u8` count field
+pub fn checked_cast<T: TryFrom<U>, U>(value: U) -> Result<T, ReviewError> {
+    T::try_from(value).map_err(|_| ReviewError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_add() {
+        assert_eq!(checked_add(1u64, 2), Ok(3));
+        assert_eq!(checked_add(u64::MAX - 1, 1), Ok(u64::MAX));
+        assert_eq!(checked_add(u64::MAX, 1), Err(ReviewError::ArithmeticOverflow));
+        assert_eq!(checked_add(u8::MAX, 1), Err(ReviewError::ArithmeticOverflow));
+        assert_eq!(checked_add(i64::MAX, 1), Err(ReviewError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn test_checked_sub() {
+        assert_eq!(checked_sub(3u64, 2), Ok(1));
+        assert_eq!(checked_sub(1u64, 1), Ok(0));
+        assert_eq!(checked_sub(0u64, 1), Err(ReviewError::ArithmeticUnderflow));
+        assert_eq!(checked_sub(0usize, 1), Err(ReviewError::ArithmeticUnderflow));
+        assert_eq!(checked_sub(-1i64, 1), Ok(-2));
+        assert_eq!(checked_sub(i64::MIN, 1), Err(ReviewError::ArithmeticUnderflow));
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        assert_eq!(checked_mul(4u64, 5), Ok(20));
+        assert_eq!(checked_mul(u64::MAX, 0), Ok(0));
+        assert_eq!(checked_mul(u64::MAX, 2), Err(ReviewError::ArithmeticOverflow));
+        assert_eq!(checked_mul(u32::MAX, 2), Err(ReviewError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        assert_eq!(checked_sum([1usize, 2, 3]), Ok(6));
+        assert_eq!(checked_sum(Vec::<u64>::new()), Ok(0));
+        assert_eq!(checked_sum([usize::MAX, 0]), Ok(usize::MAX));
+        assert_eq!(checked_sum([usize::MAX, 1]), Err(ReviewError::ArithmeticOverflow));
+    }
+
+    #[test]
+    fn test_checked_cast() {
+        assert_eq!(checked_cast::<u8, usize>(255), Ok(255));
+        assert_eq!(checked_cast::<u8, usize>(256), Err(ReviewError::ArithmeticOverflow));
+        assert_eq!(checked_cast::<u32, usize>(7), Ok(7));
+        assert_eq!(checked_cast::<u64, i64>(-1), Err(ReviewError::ArithmeticOverflow));
+    }
+}
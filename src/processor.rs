@@ -1,66 +1,665 @@
 use solana_program::{
-    pubkey::Pubkey,
+    pubkey::{Pubkey, MAX_SEED_LEN},
     account_info::{AccountInfo, next_account_info},
     entrypoint::ProgramResult,
     msg,
+    instruction::{AccountMeta, Instruction},
     program_error::ProgramError,
     rent::Rent,
-    sysvar::Sysvar,
-    program::invoke_signed,
-    system_instruction,
+    program::{invoke, invoke_signed},
     borsh1::try_from_slice_unchecked,
     program_pack::IsInitialized,
-    native_token::sol_to_lamports,
-    program_pack::Pack,
+    hash::{hash, hashv, Hash},
+    program_option::COption,
 };
 
-use spl_token::{
-    ID as TOKEN_PROGRAM_ID,
-    instruction::initialize_mint2,
+use solana_loader_v3_interface::{
+    get_program_data_address, instruction::set_upgrade_authority, state::UpgradeableLoaderState,
 };
+use solana_sdk_ids::{bpf_loader_upgradeable, system_program};
+use solana_system_interface::instruction as system_instruction;
 
-use spl_associated_token_account::get_associated_token_address;
+#[cfg(not(feature = "token-2022"))]
+use solana_program::program_pack::Pack;
+
+// The `token-2022` feature swaps the reward token over to Token-2022 and
+// makes it non-transferable (soulbound).
+#[cfg(not(feature = "token-2022"))]
+use spl_token as token;
+#[cfg(feature = "token-2022")]
+use spl_token_2022 as token;
+
+pub use token::ID as TOKEN_PROGRAM_ID;
+
+use token::instruction::{initialize_mint2, set_authority, AuthorityType};
+
+use spl_associated_token_account::{
+    get_associated_token_address_with_program_id,
+    instruction::create_associated_token_account_idempotent,
+    ID as ASSOCIATED_TOKEN_PROGRAM_ID,
+};
 
 use borsh::BorshSerialize;
 
-use crate::instruction::MovieInstruction;
-use crate::state::{MovieAccountState, MovieCommentCounter, MovieComment};
+use crate::instruction::{MovieInstruction, ReviewInstruction};
+use crate::state::{
+    MovieAccountState, MovieCommentCounter, MovieComment, LegacyMovieAccountState, BaselineMovieAccountState, AuditEntry, AuditLog,
+    ReviewSeries, ProgramConfig, RewardTracker, ReactionRecord, CommentApproval, AwardNomination,
+    NominationVote, Award, GlobalStats, CommentMerkleRoot, ReviewTombstone, TagCloud, TagCloudRecord,
+    ReviewerProfile, ProgramAuthorityRecord, ReviewCollection, DirectMessage, DirectMessageCounter, PendingComment,
+};
 use crate::error::ReviewError;
+use crate::math::{checked_add, checked_cast, checked_sub};
+use crate::sysvar::{get_clock, get_rent};
+#[cfg(feature = "error-context")]
+use crate::error::ErrorContext;
+#[cfg(feature = "anchor-events")]
+use crate::events::{emit, CommentAdded, ReviewAdded, ReviewUpdated};
+
+const MAX_QUERY_RESULTS: u8 = 10;
+
+const MAX_FETCH_COMMENTS: u8 = 10;
+
+const REWARD_MINT_DECIMALS: u8 = 9;
+
+const MAX_BATCH_ATAS: usize = 5;
+
+const MAX_SYNC_COMMENTS: usize = 20;
+
+const MAX_EXPORT_REVIEWS: usize = 20;
+
+const MAX_RECOMPUTE_ACCOUNTS: usize = 50;
+
+// Enough for a tree over 2^32 comments
+const MAX_MERKLE_PROOF_LEN: usize = 32;
+
+// Comment rewards decay with the review's comment count:
+// reward = max(COMMENT_MIN_REWARD, COMMENT_BASE_REWARD - count * COMMENT_REWARD_STEP)
+// Amounts are in base units of the 9-decimal reward mint. The minimum must
+// not exceed the base reward.
+const REVIEW_REWARD: u64 = 10_000_000_000;
+
+const COMMENT_BASE_REWARD: u64 = 5_000_000_000;
+const COMMENT_REWARD_STEP: u64 = 500_000_000;
+const COMMENT_MIN_REWARD: u64 = 1_000_000_000;
+
+const TOKEN_MINT_SEED: &[u8] = b"token_mint";
+const TOKEN_AUTH_SEED: &[u8] = b"token_auth";
+const FREEZE_AUTH_SEED: &[u8] = b"freeze_auth";
+
+// The program-owned reward mint
+pub fn mint_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOKEN_MINT_SEED], program_id)
+}
+
+// Mint authority of both the PDA mint and any configured reward mint
+pub fn mint_auth_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TOKEN_AUTH_SEED], program_id)
+}
+
+// Freeze authority of the PDA mint
+pub fn freeze_auth_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FREEZE_AUTH_SEED], program_id)
+}
+
+// The PDA mint and its authority, shared by every handler that pays rewards
+struct RewardAddresses {
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    mint_auth_bump: u8,
+}
+
+fn reward_addresses(program_id: &Pubkey) -> RewardAddresses {
+    let (mint, _mint_bump) = mint_pda(program_id);
+    let (mint_auth, mint_auth_bump) = mint_auth_pda(program_id);
+
+    RewardAddresses {
+        mint,
+        mint_auth,
+        mint_auth_bump,
+    }
+}
+
+// The PDAs `add_movie_review` checks and signs for. Each is searched once at
+// the top of the handler and reused for both the check and the seeds.
+struct DerivedAddresses {
+    review: (Pubkey, u8),
+    counter: (Pubkey, u8),
+    reward: RewardAddresses,
+}
+
+impl DerivedAddresses {
+    fn new(program_id: &Pubkey, reviewer: &Pubkey, title: &str) -> Self {
+        let review = Pubkey::find_program_address(
+            &[reviewer.as_ref(), MovieAccountState::title_seed(title).as_bytes()], 
+            program_id,
+        );
+        let counter = Pubkey::find_program_address(
+            &[review.0.as_ref(), b"comment"], 
+            program_id
+        );
+
+        Self {
+            review,
+            counter,
+            reward: reward_addresses(program_id),
+        }
+    }
+}
 
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8]
 ) -> ProgramResult {
+    // Review payloads are read in place, so their strings are only copied
+    // into the accounts that store them
+    if let Some(instruction) = ReviewInstruction::unpack(instruction_data)? {
+        return process_review_instruction(program_id, accounts, instruction);
+    }
+
     let instruction = MovieInstruction::unpack(instruction_data)?;
 
+    validate_payload(&instruction)?;
+
+    match instruction {
+        // Taken by `ReviewInstruction::unpack` above
+        MovieInstruction::AddMovieReview { .. }
+        | MovieInstruction::UpdateMovieReview { .. }
+        | MovieInstruction::AddComment { .. } => Err(ProgramError::InvalidInstructionData),
+        MovieInstruction::InitializeMint => {
+            initialize_token_mint(program_id, accounts)
+        },
+        MovieInstruction::ChangeReviewLanguage { title, language_code } => {
+            change_review_language(program_id, accounts, title, language_code)
+        },
+        MovieInstruction::GiftTokens { recipient, amount } => {
+            gift_tokens(program_id, accounts, recipient, amount)
+        },
+        MovieInstruction::QueryReviewsByRange { min_rating, max_rating, max_results } => {
+            query_reviews_by_range(program_id, accounts, min_rating, max_rating, max_results)
+        },
+        MovieInstruction::BatchCreateATAs { users } => {
+            batch_create_atas(program_id, accounts, users)
+        },
+        MovieInstruction::MigrateLegacyReview => {
+            migrate_legacy_review(program_id, accounts)
+        },
+        MovieInstruction::InitializeConfig => {
+            initialize_config(program_id, accounts)
+        },
+        MovieInstruction::SetRewardMint { reward_mint } => {
+            set_reward_mint(program_id, accounts, reward_mint)
+        },
+        MovieInstruction::ClaimRewards => {
+            claim_rewards(program_id, accounts)
+        },
+        MovieInstruction::SetClaimRewards { enabled } => {
+            set_claim_rewards(program_id, accounts, enabled)
+        },
+        MovieInstruction::DeleteMovieReview { title } => {
+            delete_movie_review(program_id, accounts, title)
+        },
+        MovieInstruction::SetClawbackPeriod { seconds } => {
+            set_clawback_period(program_id, accounts, seconds)
+        },
+        MovieInstruction::ViewAuditLog { title } => {
+            view_audit_log(program_id, accounts, title)
+        },
+        MovieInstruction::CreateSeries { name } => {
+            create_series(program_id, accounts, name)
+        },
+        MovieInstruction::AddToSeries { series_name, review_title } => {
+            add_to_series(program_id, accounts, series_name, review_title)
+        },
+        MovieInstruction::FinalizeSeries { series_name } => {
+            finalize_series(program_id, accounts, series_name)
+        },
+        MovieInstruction::ReactToReview { title, reviewer, reaction } => {
+            react_to_review(program_id, accounts, title, reviewer, reaction)
+        },
+        MovieInstruction::SetCommentModeration { title, mode } => {
+            set_comment_moderation(program_id, accounts, title, mode)
+        },
+        MovieInstruction::ApproveComment { title, comment_index } => {
+            approve_comment(program_id, accounts, title, comment_index)
+        },
+        MovieInstruction::BackfillReward => {
+            backfill_reward(program_id, accounts)
+        },
+        MovieInstruction::NominateReview { reviewer, title, category } => {
+            nominate_review(program_id, accounts, reviewer, title, category)
+        },
+        MovieInstruction::VoteForNomination { reviewer, title, year } => {
+            vote_for_nomination(program_id, accounts, reviewer, title, year)
+        },
+        MovieInstruction::DeclareAwardWinner { category, year } => {
+            declare_award_winner(program_id, accounts, category, year)
+        },
+        MovieInstruction::SyncCounter { title } => {
+            sync_counter(program_id, accounts, title)
+        },
+        MovieInstruction::SetSkipRevokedRewards { enabled } => {
+            set_skip_revoked_rewards(program_id, accounts, enabled)
+        },
+        MovieInstruction::UpdateMerkleRoot { title, root, num_comments } => {
+            update_merkle_root(program_id, accounts, title, root, num_comments)
+        },
+        MovieInstruction::VerifyCommentInclusion { title, comment_pda, proof } => {
+            verify_comment_inclusion(program_id, accounts, title, comment_pda, proof)
+        },
+        MovieInstruction::SyncCommentCounter { title } => {
+            sync_comment_counter(program_id, accounts, title)
+        },
+        MovieInstruction::UpdateCommentText { title, comment_index, comment } => {
+            update_comment_text(program_id, accounts, title, comment_index, comment)
+        },
+        MovieInstruction::RenameReview { title, new_title } => {
+            rename_review(program_id, accounts, title, new_title)
+        },
+        MovieInstruction::SetCallbackProgram { program_id: callback_program_id } => {
+            set_callback_program(program_id, accounts, callback_program_id)
+        },
+        MovieInstruction::MarkSponsored { title } => {
+            mark_sponsored(program_id, accounts, title)
+        },
+        MovieInstruction::SetMinDescriptionLenForReward { min_len } => {
+            set_min_description_len_for_reward(program_id, accounts, min_len)
+        },
+        MovieInstruction::UpdateTagCloud { title } => {
+            update_tag_cloud(program_id, accounts, title)
+        },
+        MovieInstruction::MintFirstReviewBadge { reviewer } => {
+            mint_first_review_badge(program_id, accounts, reviewer)
+        },
+        MovieInstruction::FetchComments { review_title, start_index, count } => {
+            fetch_comments(program_id, accounts, review_title, start_index, count)
+        },
+        MovieInstruction::VerifyProgramVersion { expected_hash } => {
+            verify_program_version(program_id, accounts, expected_hash)
+        },
+        MovieInstruction::ProposeAuthorityTransfer { new_authority } => {
+            propose_authority_transfer(program_id, accounts, new_authority)
+        },
+        MovieInstruction::AcceptAuthorityTransfer => {
+            accept_authority_transfer(program_id, accounts)
+        },
+        MovieInstruction::Ping => {
+            msg!("Pong");
+
+            Ok(())
+        },
+        MovieInstruction::SetAutoVerifyThreshold { threshold } => {
+            set_auto_verify_threshold(program_id, accounts, threshold)
+        },
+        MovieInstruction::CreateCollection { name, description } => {
+            create_collection(program_id, accounts, name, description)
+        },
+        MovieInstruction::AddReviewToCollection { collection_id, review_title } => {
+            add_review_to_collection(program_id, accounts, collection_id, review_title)
+        },
+        MovieInstruction::RemoveReviewFromCollection { collection_id, review_title } => {
+            remove_review_from_collection(program_id, accounts, collection_id, review_title)
+        },
+        MovieInstruction::CompactComment { old_index, new_index } => {
+            compact_comment(program_id, accounts, old_index, new_index)
+        },
+        MovieInstruction::SendDirectMessage { recipient, message } => {
+            send_direct_message(program_id, accounts, recipient, message)
+        },
+        MovieInstruction::MarkMessageRead { sender, message_index } => {
+            mark_message_read(program_id, accounts, sender, message_index)
+        },
+        MovieInstruction::SetMaxRewardAmount { max_amount } => {
+            set_max_reward_amount(program_id, accounts, max_amount)
+        },
+        MovieInstruction::ExportAllReviews { reviewer } => {
+            export_all_reviews(program_id, accounts, reviewer)
+        },
+        MovieInstruction::RecomputeProgramStats => {
+            recompute_program_stats(program_id, accounts)
+        },
+        MovieInstruction::SetCommentMint { comment_mint } => {
+            set_comment_mint(program_id, accounts, comment_mint)
+        },
+        MovieInstruction::FreezeTokenMint => {
+            set_token_account_frozen(program_id, accounts, true)
+        },
+        MovieInstruction::ThawTokenMint => {
+            set_token_account_frozen(program_id, accounts, false)
+        },
+        MovieInstruction::ApprovePendingComment { submitter, title } => {
+            approve_pending_comment(program_id, accounts, submitter, title)
+        },
+        MovieInstruction::RejectPendingComment { submitter, title, reason } => {
+            reject_pending_comment(program_id, accounts, submitter, title, reason)
+        },
+        #[cfg(feature = "test-panic")]
+        MovieInstruction::TestPanic => {
+            panic!("intentional test panic")
+        }
+    }
+}
+
+fn process_review_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction: ReviewInstruction,
+) -> ProgramResult {
+    validate_review_payload(&instruction)?;
+
     match instruction {
-        MovieInstruction::AddMovieReview { title, rating, description } => {
-            add_movie_review(program_id, accounts, title, rating, description)
+        ReviewInstruction::AddMovieReview { title, rating, description, language_code, watched_at, tight_size, is_sponsored, poster_uri, tags } => {
+            add_movie_review(program_id, accounts, title, rating, description, language_code, watched_at, tight_size, is_sponsored, poster_uri, &tags)
         },
-        MovieInstruction::UpdateMovieReview { title, rating, description } => {
-            update_movie_review(program_id, accounts, title, rating, description)
+        ReviewInstruction::UpdateMovieReview { rating, description, poster_uri, tags, .. } => {
+            update_movie_review(program_id, accounts, rating, description, poster_uri, tags.as_deref())
         },
-        MovieInstruction::AddComment { comment } => {
+        ReviewInstruction::AddComment { comment } => {
             add_comment(program_id, accounts, comment)
+        }
+    }
+}
+
+fn validate_review_payload(instruction: &ReviewInstruction) -> ProgramResult {
+    match instruction {
+        ReviewInstruction::AddMovieReview { title, poster_uri, tags, .. } => {
+            validate_poster_uri(*poster_uri)?;
+            validate_tags(tags)?;
+            validate_title(title)
         },
-        MovieInstruction::InitializeMint => {
-            initialize_token_mint(program_id, accounts)
+        ReviewInstruction::UpdateMovieReview { title, poster_uri, tags, .. } => {
+            validate_poster_uri(*poster_uri)?;
+            if let Some(tags) = tags {
+                validate_tags(tags)?;
+            }
+            validate_title(title)
+        },
+        ReviewInstruction::AddComment { comment } => {
+            if comment.is_empty() {
+                msg!("Comment cannot be empty");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
         }
     }
 }
 
+fn validate_payload(instruction: &MovieInstruction) -> ProgramResult {
+    match instruction {
+        MovieInstruction::ChangeReviewLanguage { title, .. }
+        | MovieInstruction::ViewAuditLog { title }
+        | MovieInstruction::DeleteMovieReview { title }
+        | MovieInstruction::SyncCounter { title }
+        | MovieInstruction::SyncCommentCounter { title }
+        | MovieInstruction::MarkSponsored { title }
+        | MovieInstruction::UpdateTagCloud { title }
+        | MovieInstruction::UpdateMerkleRoot { title, .. } => {
+            validate_title(title)
+        },
+        MovieInstruction::GiftTokens { amount, .. } => {
+            if *amount == 0 {
+                msg!("Gift amount cannot be zero");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
+        },
+        MovieInstruction::QueryReviewsByRange { min_rating, max_rating, .. } => {
+            if min_rating > max_rating {
+                msg!("Minimum rating cannot exceed maximum rating");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
+        },
+        MovieInstruction::FetchComments { review_title, count, .. } => {
+            if *count == 0 || *count > MAX_FETCH_COMMENTS {
+                msg!("Between 1 and {} comments can be fetched", MAX_FETCH_COMMENTS);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            validate_title(review_title)
+        },
+        MovieInstruction::BatchCreateATAs { users } => {
+            if users.is_empty() || users.len() > MAX_BATCH_ATAS {
+                msg!("Between 1 and {} users can be passed", MAX_BATCH_ATAS);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
+        },
+        MovieInstruction::CreateSeries { name: series_name }
+        | MovieInstruction::FinalizeSeries { series_name } => {
+            validate_series_name(series_name)
+        },
+        MovieInstruction::AddToSeries { series_name, review_title } => {
+            validate_series_name(series_name)?;
+            validate_title(review_title)
+        },
+        MovieInstruction::CreateCollection { name, description } => {
+            if name.is_empty() || name.len() > ReviewCollection::MAX_NAME_LEN {
+                msg!("Collection name must be between 1 and {} bytes", ReviewCollection::MAX_NAME_LEN);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            if description.len() > ReviewCollection::MAX_DESCRIPTION_LEN {
+                msg!("Collection description cannot be longer than {} bytes", ReviewCollection::MAX_DESCRIPTION_LEN);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
+        },
+        MovieInstruction::AddReviewToCollection { review_title, .. }
+        | MovieInstruction::RemoveReviewFromCollection { review_title, .. } => {
+            validate_title(review_title)
+        },
+        MovieInstruction::SetClawbackPeriod { seconds } => {
+            if *seconds < 0 {
+                msg!("Clawback period cannot be negative");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
+        },
+        MovieInstruction::SetCommentModeration { title, mode } => {
+            if *mode > MovieAccountState::COMMENTS_LOCKED {
+                msg!("Unknown comment moderation mode {}", mode);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            validate_title(title)
+        },
+        MovieInstruction::ApproveComment { title, .. }
+        | MovieInstruction::ApprovePendingComment { title, .. }
+        | MovieInstruction::RejectPendingComment { title, .. } => {
+            validate_title(title)
+        },
+        MovieInstruction::RenameReview { title, new_title } => {
+            if title == new_title {
+                msg!("New title must differ from the current one");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            validate_title(title)?;
+            validate_title(new_title)
+        },
+        MovieInstruction::UpdateCommentText { title, comment, .. } => {
+            if comment.is_empty() {
+                msg!("Comment cannot be empty");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            validate_title(title)
+        },
+        MovieInstruction::ReactToReview { title, reaction, .. } => {
+            if *reaction as usize >= MovieAccountState::REACTION_KINDS {
+                msg!("Reaction must be below {}", MovieAccountState::REACTION_KINDS);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            validate_title(title)
+        },
+        MovieInstruction::NominateReview { title, category, .. } => {
+            validate_award_category(category)?;
+            validate_title(title)
+        },
+        MovieInstruction::VoteForNomination { title, .. } => {
+            validate_title(title)
+        },
+        MovieInstruction::DeclareAwardWinner { category, .. } => {
+            validate_award_category(category)
+        },
+        MovieInstruction::VerifyCommentInclusion { title, proof, .. } => {
+            if proof.len() > MAX_MERKLE_PROOF_LEN {
+                msg!("Proof cannot be longer than {}", MAX_MERKLE_PROOF_LEN);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            validate_title(title)
+        },
+        MovieInstruction::CompactComment { old_index, new_index } => {
+            if old_index == new_index {
+                msg!("New index must differ from the current one");
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
+        },
+        MovieInstruction::SendDirectMessage { message, .. } => {
+            if message.is_empty() || message.len() > DirectMessage::MAX_CONTENT_LEN {
+                msg!("Message must be between 1 and {} bytes", DirectMessage::MAX_CONTENT_LEN);
+                return Err(ProgramError::InvalidInstructionData);
+            }
+
+            Ok(())
+        },
+        // Checked by `validate_review_payload`
+        MovieInstruction::AddMovieReview { .. }
+        | MovieInstruction::UpdateMovieReview { .. }
+        | MovieInstruction::AddComment { .. } => Ok(()),
+        MovieInstruction::InitializeMint
+        | MovieInstruction::MigrateLegacyReview
+        | MovieInstruction::InitializeConfig
+        | MovieInstruction::SetRewardMint { .. }
+        | MovieInstruction::SetCommentMint { .. }
+        | MovieInstruction::FreezeTokenMint
+        | MovieInstruction::ThawTokenMint
+        | MovieInstruction::ClaimRewards
+        | MovieInstruction::SetClaimRewards { .. }
+        | MovieInstruction::SetSkipRevokedRewards { .. }
+        | MovieInstruction::SetCallbackProgram { .. }
+        | MovieInstruction::SetMinDescriptionLenForReward { .. }
+        | MovieInstruction::SetAutoVerifyThreshold { .. }
+        | MovieInstruction::SetMaxRewardAmount { .. }
+        | MovieInstruction::ExportAllReviews { .. }
+        | MovieInstruction::RecomputeProgramStats
+        | MovieInstruction::MintFirstReviewBadge { .. }
+        | MovieInstruction::VerifyProgramVersion { .. }
+        | MovieInstruction::ProposeAuthorityTransfer { .. }
+        | MovieInstruction::AcceptAuthorityTransfer
+        | MovieInstruction::MarkMessageRead { .. }
+        | MovieInstruction::Ping
+        | MovieInstruction::BackfillReward => Ok(()),
+        #[cfg(feature = "test-panic")]
+        MovieInstruction::TestPanic => Ok(()),
+    }
+}
+
+fn validate_title(title: &str) -> ProgramResult {
+    let seed = MovieAccountState::title_seed(title);
+
+    if seed.is_empty() {
+        msg!("Title cannot be empty");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Lowercasing can lengthen a normalized seed, so check the seed itself
+    if seed.len() > MAX_SEED_LEN {
+        msg!("Title cannot be longer than {} bytes", MAX_SEED_LEN);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+fn validate_poster_uri(poster_uri: Option<&str>) -> ProgramResult {
+    let Some(uri) = poster_uri else {
+        return Ok(());
+    };
+
+    if uri.len() > MovieAccountState::MAX_POSTER_URI_LEN {
+        msg!("Poster URI cannot be longer than {} bytes", MovieAccountState::MAX_POSTER_URI_LEN);
+        return Err(ReviewError::InvalidUri.into());
+    }
+
+    if !MovieAccountState::POSTER_URI_SCHEMES.iter().any(|scheme| uri.starts_with(scheme)) {
+        msg!("Poster URI must start with one of {:?}", MovieAccountState::POSTER_URI_SCHEMES);
+        return Err(ReviewError::InvalidUri.into());
+    }
+
+    Ok(())
+}
+
+fn validate_tags(tags: &[&str]) -> ProgramResult {
+    if tags.len() > MovieAccountState::MAX_TAGS {
+        msg!("A review can have at most {} tags", MovieAccountState::MAX_TAGS);
+        return Err(ReviewError::InvalidTags.into());
+    }
+
+    if tags.iter().any(|tag| tag.is_empty() || tag.len() > MovieAccountState::MAX_TAG_LEN) {
+        msg!("Tags must be between 1 and {} bytes", MovieAccountState::MAX_TAG_LEN);
+        return Err(ReviewError::InvalidTags.into());
+    }
+
+    Ok(())
+}
+
+fn validate_award_category(category: &str) -> ProgramResult {
+    if category.is_empty() || category.len() > AwardNomination::MAX_CATEGORY_LEN {
+        msg!("Category must be between 1 and {} bytes", AwardNomination::MAX_CATEGORY_LEN);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+fn validate_series_name(name: &str) -> ProgramResult {
+    if name.is_empty() || name.len() > ReviewSeries::MAX_NAME_LEN {
+        msg!("Series name must be between 1 and {} bytes", ReviewSeries::MAX_NAME_LEN);
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn add_movie_review(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    title: String,
+    title: &str,
     rating: u8,
-    description: String,
+    description: &str,
+    language_code: [u8; 2],
+    watched_at: i64,
+    tight_size: bool,
+    is_sponsored: bool,
+    poster_uri: Option<&str>,
+    tags: &[&str],
 ) -> ProgramResult {
     msg!("Adding movie review...");
     msg!("Title: {}", title);
     msg!("Rating: {}", rating);
     msg!("Description: {}", description);
+    msg!("Language: {}", String::from_utf8_lossy(&language_code));
+    msg!("Watched at: {}", watched_at);
+    msg!("Tight size: {}", tight_size);
+    msg!("Sponsored: {}", is_sponsored);
+    msg!("Poster: {:?}", poster_uri);
+    msg!("Tags: {:?}", tags);
+
+    require_accounts(accounts, 11)?;
 
     let account_info_iter = &mut accounts.iter();
 
@@ -72,105 +671,378 @@ pub fn add_movie_review(
     let user_ata = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
-
-    if !initializer.is_signer {
+    let config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    // Optional sponsor that funds the new accounts in place of the reviewer.
+    // With claim rewards enabled the `user_ata` slot carries the reviewer's
+    // `RewardTracker` PDA and the other token accounts are not used.
+    // When a callback program is configured the sponsor slot must be filled
+    // (the reviewer may repeat itself) and the callback program follows it.
+    let payer = account_info_iter.next().unwrap_or(initializer);
+
+    if !initializer.is_signer || !payer.is_signer {
         msg!("Missing required signature");
         return Err(ProgramError::MissingRequiredSignature)
     }
 
-    let (pda, bump_seed) = Pubkey::find_program_address(
-        &[initializer.key.as_ref(), title.as_bytes().as_ref()], 
-        program_id,
-    );
+    let derived = DerivedAddresses::new(program_id, initializer.key, title);
+    let (pda, bump_seed) = derived.review;
 
     if pda != *pda_account.key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into())
+        return Err(invalid_pda(1, &pda, pda_account.key))
     }
 
-    if rating > 5 || rating < 1 {
-        msg!("Rating cannot be higher than 5");
-        return Err(ReviewError::InvalidRating.into())
-    }
+    // A zero-lamport account is closed (or never existed) and can be created
+    // again regardless of any bytes left behind
+    if pda_account.lamports() > 0 && pda_account.owner == program_id {
+        let account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
 
-    if MovieAccountState::get_account_size(title.clone(), description.clone()) > MovieAccountState::LEN {
-        msg!("Data length is larger than 1000 bytes");
-        return Err(ReviewError::InvalidDataLength.into())
+        if account_data.is_initialized() {
+            msg!("Account already initialized");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
     }
 
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(MovieAccountState::LEN);
+    let now = get_clock()?.unix_timestamp;
 
-    invoke_signed(
-        &system_instruction::create_account(
-            initializer.key, 
-            pda_account.key, 
-            rent_lamports, 
-            MovieAccountState::LEN.try_into().unwrap(), 
-            program_id
-        ), 
-        &[
-            initializer.clone(),
-            pda_account.clone(),
-            system_program.clone(),
-        ], 
-        &[&[
-            initializer.key.as_ref(),
-            title.as_bytes().as_ref(),
-            &[bump_seed]
-        ]],
+    let tags: Vec<String> = tags.iter().map(|tag| tag.to_string()).collect();
+
+    validate_review_inputs(
+        title,
+        rating,
+        description,
+        poster_uri,
+        &tags,
+        &language_code,
+        watched_at,
+        now,
     )?;
 
-    msg!("PDA created: {}", pda);
+    let rent = get_rent()?;
+
+    // Bytes, like the size limits, not chars
+    let description_len = description.len();
+
+    let review = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        version: MovieAccountState::VERSION,
+        reviewer: *initializer.key,
+        rating,
+        language_code,
+        watched_at,
+        created_at: now,
+        reactions: [0; MovieAccountState::REACTION_KINDS],
+        comment_moderation: MovieAccountState::COMMENTS_OPEN,
+        reward_minted: true,
+        tight_size,
+        is_sponsored,
+        title: title.to_string(),
+        description: description.to_string(),
+        poster_uri: poster_uri.map(str::to_string),
+        tags,
+    };
 
-    msg!("Unpacking account");
-    let mut account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
-    msg!("Borrowed account data");
+    create_review_account(
+        program_id,
+        payer,
+        pda_account,
+        system_program,
+        &rent,
+        bump_seed,
+        review,
+    )?;
 
-    msg!("Checking if movie account is already initialized");
-    if account_data.is_initialized {
-        msg!("Account already initialized");
-        return Err(ProgramError::AccountAlreadyInitialized);
-    }
+    create_comment_counter(
+        program_id,
+        payer,
+        pda_account.key,
+        derived.counter,
+        pda_counter,
+        system_program,
+        &rent,
+    )?;
 
-    account_data.discriminator = MovieAccountState::DISCRIMINATOR.to_string();
-    account_data.reviewer = *initializer.key;
-    account_data.title = title;
-    account_data.rating = rating;
-    account_data.description = description;
-    account_data.is_initialized = true;
+    let reward_config = reward_config(program_id, config, &derived.reward.mint)?;
 
-    msg!("Serializing account");
-    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
-    msg!("State account serialized");
+    let mut stats = load_global_stats(program_id, payer, pda_stats, system_program)?;
+    stats.total_reviews = checked_add(stats.total_reviews, 1)?;
 
-    msg!("Create comment counter");
-    let counter_rent_lamports = rent.minimum_balance(MovieCommentCounter::LEN);
+    let rewarded = if description_len < reward_config.min_description_len_for_reward as usize {
+        msg!(
+            "Description is {} bytes, below the {} byte reward threshold; skipping reward",
+            description_len,
+            reward_config.min_description_len_for_reward
+        );
 
-    let (counter, counter_bump) = Pubkey::find_program_address(
-        &[pda.as_ref(), b"comment"], 
-        program_id
-    );
+        false
+    } else if reward_config.claim_rewards {
+        check_reward_cap(&reward_config, REVIEW_REWARD)?;
 
-    if counter != *pda_counter.key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into());
+        accrue_reward(
+            program_id,
+            initializer.key,
+            payer,
+            user_ata,
+            system_program,
+            REVIEW_REWARD,
+        )?;
+
+        true
+    } else {
+        check_reward_cap(&reward_config, REVIEW_REWARD)?;
+
+        let minted = mint_review_reward(
+            &derived.reward,
+            initializer.key,
+            &reward_config.reward_mint,
+            token_mint,
+            mint_auth,
+            user_ata,
+            token_program,
+        );
+
+        tolerate_revoked_authority(&reward_config, minted)?
+    };
+
+    if rewarded {
+        stats.total_tokens_minted = checked_add(stats.total_tokens_minted, REVIEW_REWARD)?;
+    } else {
+        // A skipped reward can still be paid later through `BackfillReward`
+        let mut account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
+        account_data.reward_minted = false;
+        account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
     }
 
-    invoke_signed(
-        &system_instruction::create_account(
-            initializer.key, 
+    let mut profile = load_reviewer_profile(program_id, initializer.key, payer, pda_profile, system_program)?;
+    profile.review_count = checked_add(profile.review_count, 1)?;
+    if profile.review_count == 1 {
+        stats.total_reviewers = checked_add(stats.total_reviewers, 1)?;
+    }
+    stats.serialize(&mut &mut pda_stats.data.borrow_mut()[..])?;
+
+    if !profile.is_verified && profile.review_count >= reward_config.min_reviews_for_auto_verify as u64 {
+        profile.is_verified = true;
+        msg!("Reviewer verified after {} reviews", profile.review_count);
+    }
+    profile.serialize(&mut &mut pda_profile.data.borrow_mut()[..])?;
+
+    if let Some(callback_program_id) = reward_config.callback_program_id {
+        let callback_program = next_account_info(account_info_iter)?;
+
+        notify_callback_program(&callback_program_id, callback_program, pda_account)?;
+    }
+
+    #[cfg(feature = "anchor-events")]
+    emit(&ReviewAdded {
+        reviewer: *initializer.key,
+        review: *pda_account.key,
+        title: title.to_string(),
+        rating,
+    });
+
+    Ok(())
+}
+
+// Calls the registered callback program with the new review as its only
+// account and the review address as instruction data. Only the address
+// stored in the config can be invoked.
+fn notify_callback_program<'a>(
+    callback_program_id: &Pubkey,
+    callback_program: &AccountInfo<'a>,
+    pda_account: &AccountInfo<'a>,
+) -> ProgramResult {
+    if callback_program.key != callback_program_id {
+        msg!("Callback program must be {}", callback_program_id);
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    msg!("Notifying callback program {}", callback_program_id);
+
+    invoke(
+        &Instruction {
+            program_id: *callback_program_id,
+            accounts: vec![AccountMeta::new_readonly(*pda_account.key, false)],
+            data: pda_account.key.to_bytes().to_vec(),
+        },
+        &[
+            pda_account.clone(),
+            callback_program.clone(),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn validate_review_inputs(
+    title: &str,
+    rating: u8,
+    description: &str,
+    poster_uri: Option<&str>,
+    tags: &[String],
+    language_code: &[u8; 2],
+    watched_at: i64,
+    now: i64,
+) -> ProgramResult {
+    if !(1..=5).contains(&rating) {
+        msg!("Rating cannot be higher than 5");
+        return Err(ReviewError::InvalidRating.into())
+    }
+
+    validate_review_size(title, description, poster_uri, tags, MovieAccountState::LEN)?;
+
+    if !is_valid_language_code(language_code) {
+        msg!("Language code must be two lowercase ASCII letters");
+        return Err(ReviewError::InvalidLanguageCode.into())
+    }
+
+    if watched_at > now {
+        msg!("Watched date cannot be in the future");
+        return Err(ReviewError::InvalidWatchedDate.into())
+    }
+
+    Ok(())
+}
+
+// Reports which field keeps the review from fitting in `capacity` bytes.
+// Titles are capped at the seed length, so in practice it is the description
+// that has to be shortened; the title check covers callers without that cap.
+fn validate_review_size(
+    title: &str,
+    description: &str,
+    poster_uri: Option<&str>,
+    tags: &[String],
+    capacity: usize,
+) -> ProgramResult {
+    let len_without_description = MovieAccountState::get_account_size(
+        title.to_string(),
+        String::new(),
+        poster_uri.map(str::to_string),
+        tags.to_vec(),
+    )?;
+
+    if len_without_description > capacity {
+        msg!(
+            "Title is too long: {} bytes leave no room for a description within {} bytes",
+            title.len(),
+            capacity
+        );
+        return Err(ReviewError::InvalidDataLength.into())
+    }
+
+    let max_description_len = checked_sub(capacity, len_without_description)?;
+    if description.len() > max_description_len {
+        msg!(
+            "Description is too long: {} bytes, at most {} fit with this title",
+            description.len(),
+            max_description_len
+        );
+        return Err(ReviewError::InvalidDataLength.into())
+    }
+
+    Ok(())
+}
+
+// Bytes an existing review may hold: `LEN`, or its current size if it was
+// allocated under a larger cap by an earlier release. Such accounts keep
+// their size and are never shrunk to a lower `LEN`.
+fn review_capacity(pda_account: &AccountInfo) -> usize {
+    MovieAccountState::LEN.max(pda_account.data_len())
+}
+
+fn create_review_account<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rent: &Rent,
+    bump_seed: u8,
+    review: MovieAccountState,
+) -> ProgramResult {
+    let account_len = review.account_len()?;
+    let rent_lamports = rent.minimum_balance(account_len);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key, 
+            pda_account.key, 
+            rent_lamports, 
+            checked_cast(account_len)?, 
+            program_id
+        ), 
+        &[
+            payer.clone(),
+            pda_account.clone(),
+            system_program.clone(),
+        ], 
+        &[&[
+            review.reviewer.as_ref(),
+            MovieAccountState::title_seed(&review.title).as_bytes(),
+            &[bump_seed]
+        ]],
+    )?;
+
+    msg!("PDA created: {}", pda_account.key);
+
+    msg!("Unpacking account");
+    let account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
+    msg!("Borrowed account data");
+
+    msg!("Checking if movie account is already initialized");
+    if account_data.is_initialized {
+        msg!("Account already initialized");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    msg!("Serializing account");
+    review.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+    msg!("State account serialized");
+
+    Ok(())
+}
+
+fn create_comment_counter<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    review: &Pubkey,
+    (counter, counter_bump): (Pubkey, u8),
+    pda_counter: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rent: &Rent,
+) -> ProgramResult {
+    msg!("Create comment counter");
+    let counter_rent_lamports = rent.minimum_balance(MovieCommentCounter::LEN);
+
+    if counter != *pda_counter.key {
+        return Err(invalid_pda(2, &counter, pda_counter.key));
+    }
+
+    // `DeleteMovieReview` leaves the counter behind while comments still live
+    // at the review's address, so a re-created review carries on numbering
+    // after them instead of colliding with their PDAs
+    if pda_counter.owner == program_id && !pda_counter.data_is_empty() {
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+
+        if counter_data.discriminator == MovieCommentCounter::DISCRIMINATOR && counter_data.is_initialized() {
+            msg!("Reusing comment counter at {}", counter_data.counter);
+            return Ok(());
+        }
+    }
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key, 
             pda_counter.key, 
             counter_rent_lamports, 
             MovieCommentCounter::LEN.try_into().unwrap(), 
             program_id
         ), 
         &[
-            initializer.clone(),
+            payer.clone(),
             pda_counter.clone(),
             system_program.clone()
         ], 
-        &[&[pda.as_ref(), b"comment", &[counter_bump]]],
+        &[&[review.as_ref(), b"comment", &[counter_bump]]],
     )?;
     msg!("Comment counter created");
 
@@ -191,11 +1063,45 @@ pub fn add_movie_review(
 
     counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
 
-    msg!("Deriving mint authority");
-    let (mint_pda, _mint_bump) = Pubkey::find_program_address(&[b"token_mint"], program_id);
-    let (mint_auth_pda, mint_auth_bump) = Pubkey::find_program_address(&[b"token_auth"], program_id);
+    Ok(())
+}
+
+fn mint_review_reward<'a>(
+    reward_addresses: &RewardAddresses,
+    reviewer: &Pubkey,
+    reward_mint: &Pubkey,
+    token_mint: &AccountInfo<'a>,
+    mint_auth: &AccountInfo<'a>,
+    user_ata: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    msg!("Minting 10 tokens to User ATA");
+    mint_reward(
+        reward_addresses,
+        reviewer,
+        reward_mint,
+        token_mint,
+        mint_auth,
+        user_ata,
+        token_program,
+        REVIEW_REWARD,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn mint_reward<'a>(
+    reward_addresses: &RewardAddresses,
+    recipient: &Pubkey,
+    reward_mint: &Pubkey,
+    token_mint: &AccountInfo<'a>,
+    mint_auth: &AccountInfo<'a>,
+    user_ata: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let RewardAddresses { mint_auth: mint_auth_pda, mint_auth_bump, .. } = *reward_addresses;
 
-    if mint_pda != *token_mint.key {
+    if reward_mint != token_mint.key {
         msg!("Incorrect token mint");
         return Err(ReviewError::IncorrectAccount.into());
     }
@@ -205,8 +1111,8 @@ pub fn add_movie_review(
         return Err(ReviewError::InvalidPDA.into());
     }
 
-    if get_associated_token_address(initializer.key, token_mint.key) != *user_ata.key {
-        msg!("Incorrect ATA for initializer");
+    if get_associated_token_address_with_program_id(recipient, token_mint.key, &TOKEN_PROGRAM_ID) != *user_ata.key {
+        msg!("Incorrect ATA for {}", recipient);
         return Err(ReviewError::IncorrectAccount.into());
     }
 
@@ -215,18 +1121,22 @@ pub fn add_movie_review(
         return Err(ReviewError::IncorrectAccount.into());
     }
 
-    msg!("Minting 10 tokens to User ATA");
+    if mint_authority(&token_mint.data.borrow())? != COption::Some(mint_auth_pda) {
+        msg!("Mint authority PDA no longer holds the mint authority");
+        return Err(ReviewError::MintAuthorityRevoked.into());
+    }
+
     invoke_signed(
-        &spl_token::instruction::mint_to(
+        &token::instruction::mint_to(
             token_program.key, 
             token_mint.key, 
             user_ata.key, 
             mint_auth.key, 
             &[], 
-            sol_to_lamports(10.0)
+            amount
         )?, 
         &[token_mint.clone(), user_ata.clone(), mint_auth.clone()], 
-        &[&[b"token_auth", &[mint_auth_bump]]],
+        &[&[TOKEN_AUTH_SEED, &[mint_auth_bump]]],
     )?;
 
     Ok(())
@@ -235,16 +1145,21 @@ pub fn add_movie_review(
 pub fn update_movie_review(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    title: String,
     rating: u8,
-    description: String
+    description: &str,
+    poster_uri: Option<&str>,
+    tags: Option<&[&str]>,
 ) -> ProgramResult {
     msg!("Updating movie review...");
 
+    require_accounts(accounts, 4)?;
+
     let account_info_iter = &mut accounts.iter();
 
     let initializer = next_account_info(account_info_iter)?;
     let pda_account = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
 
     if pda_account.owner != program_id {
         return Err(ProgramError::InvalidAccountOwner)
@@ -260,12 +1175,11 @@ pub fn update_movie_review(
     msg!("Review title: {}", account_data.title);
 
     let (pda, _bump_seed) = Pubkey::find_program_address(
-        &[initializer.key.as_ref(), account_data.title.as_bytes().as_ref()], 
+        &[initializer.key.as_ref(), MovieAccountState::title_seed(&account_data.title).as_bytes()], 
         program_id
     );
     if pda != *pda_account.key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into());
+        return Err(invalid_pda(1, &pda, pda_account.key));
     }
 
     msg!("Checking if movie account is initialized");
@@ -279,10 +1193,15 @@ pub fn update_movie_review(
         return Err(ReviewError::InvalidRating.into());
     }
 
-    if MovieAccountState::get_account_size(title.clone(), description.clone()) > MovieAccountState::LEN {
-        msg!("Data length is larger than 1000 bytes");
-        return Err(ReviewError::InvalidDataLength.into());
-    }
+    let poster_uri = poster_uri.map(str::to_string).or(account_data.poster_uri.take());
+    let tags = match tags {
+        Some(tags) => tags.iter().map(|tag| tag.to_string()).collect(),
+        None => std::mem::take(&mut account_data.tags),
+    };
+
+    // The stored title is what gets serialized; with `normalize-titles` it
+    // can differ from the one in the payload
+    validate_review_size(&account_data.title, description, poster_uri.as_deref(), &tags, review_capacity(pda_account))?;
 
     msg!("Review before update:");
     msg!("Title: {}", account_data.title);
@@ -290,28 +1209,147 @@ pub fn update_movie_review(
     msg!("Description: {}", account_data.description);
 
     account_data.rating = rating;
-    account_data.description = description;
+    account_data.description = description.to_string();
+    account_data.poster_uri = poster_uri;
+    account_data.tags = tags;
 
     msg!("Review after update:");
     msg!("Title: {}", account_data.title);
     msg!("Rating: {}", account_data.rating);
     msg!("Description: {}", account_data.description);
 
+    grow_review_account(initializer, pda_account, system_program, account_data.account_len()?)?;
+
     msg!("Serializing account");
     account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
     msg!("State account serialized");
 
+    append_audit_entry(
+        program_id,
+        initializer,
+        pda_account.key,
+        pda_audit,
+        system_program,
+        AuditEntry {
+            changed_at: get_clock()?.unix_timestamp,
+            rating: account_data.rating,
+            description_hash: hash(account_data.description.as_bytes()).to_bytes(),
+        },
+    )?;
+
+    #[cfg(feature = "anchor-events")]
+    emit(&ReviewUpdated {
+        reviewer: *initializer.key,
+        review: *pda_account.key,
+        rating,
+    });
+
+    Ok(())
+}
+
+// Reallocates a tight-sized review that no longer fits, with `payer`
+// covering the extra rent. Padded reviews are always `LEN` bytes already.
+fn grow_review_account<'a>(
+    payer: &AccountInfo<'a>,
+    pda_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    account_len: usize,
+) -> ProgramResult {
+    if pda_account.data_len() >= account_len {
+        return Ok(());
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(account_len);
+
+    if pda_account.lamports() < rent_lamports {
+        invoke(
+            &system_instruction::transfer(
+                payer.key, 
+                pda_account.key, 
+                checked_sub(rent_lamports, pda_account.lamports())?,
+            ), 
+            &[
+                payer.clone(),
+                pda_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    msg!("Growing review account to {} bytes", account_len);
+    pda_account.resize(account_len)?;
+
+    Ok(())
+}
+
+fn append_audit_entry<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    review: &Pubkey,
+    pda_audit: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    entry: AuditEntry,
+) -> ProgramResult {
+    let (audit, audit_bump) = Pubkey::find_program_address(
+        &[review.as_ref(), b"audit"], 
+        program_id
+    );
+
+    if audit != *pda_audit.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_audit.data_is_empty() {
+        msg!("Creating audit log");
+        let rent_lamports = get_rent()?.minimum_balance(AuditLog::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key, 
+                pda_audit.key, 
+                rent_lamports, 
+                AuditLog::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                payer.clone(),
+                pda_audit.clone(),
+                system_program.clone(),
+            ], 
+            &[&[review.as_ref(), b"audit", &[audit_bump]]],
+        )?;
+    } else if pda_audit.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut audit_data: AuditLog = try_from_slice_unchecked(&pda_audit.data.borrow())?;
+
+    if !audit_data.is_initialized() {
+        audit_data.discriminator = AuditLog::DISCRIMINATOR.to_string();
+        audit_data.is_initialized = true;
+    }
+
+    audit_data.push(entry);
+    msg!("Audit entries: {}", audit_data.entries.len());
+
+    audit_data.serialize(&mut &mut pda_audit.data.borrow_mut()[..])?;
+
     Ok(())
 }
 
 pub fn add_comment(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    comment: String
+    comment: &str
 ) -> ProgramResult {
     msg!("Adding Comment...");
     msg!("Comment: {}", comment);
 
+    require_accounts(accounts, 11)?;
+
+    let reward_addresses = reward_addresses(program_id);
+
     let account_info_iter = &mut accounts.iter();
 
     let commenter = next_account_info(account_info_iter)?;
@@ -323,13 +1361,58 @@ pub fn add_comment(
     let user_ata = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
+    let config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    // Optional relayer that funds the comment account in place of the commenter.
+    // With claim rewards enabled `user_ata` is the commenter's `RewardTracker`.
+    let payer = account_info_iter.next().unwrap_or(commenter);
+
+    if !commenter.is_signer || !payer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature)
+    }
+
+    if pda_review.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if review_data.discriminator != MovieAccountState::DISCRIMINATOR || !review_data.is_initialized() {
+        msg!("Account is not a movie review");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if review_data.comment_moderation == MovieAccountState::COMMENTS_LOCKED {
+        msg!("Review is locked for comments");
+        return Err(ReviewError::CommentsLocked.into());
+    }
+
+    if review_data.comment_moderation == MovieAccountState::COMMENTS_REQUIRE_APPROVAL {
+        return submit_pending_comment(program_id, commenter, payer, pda_review, pda_comment, system_program, comment);
+    }
+
+    let (counter, _counter_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"comment"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        return Err(invalid_pda(2, &counter, pda_counter.key));
+    }
+
+    if pda_counter.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
 
     let mut counter_data: MovieCommentCounter = 
         try_from_slice_unchecked(&pda_counter.data.borrow())?;
+    if counter_data.discriminator != MovieCommentCounter::DISCRIMINATOR {
+        msg!("Account is not a comment counter");
+        return Err(ProgramError::InvalidAccountData);
+    }
 
-    let account_len: usize = MovieComment::get_account_size(comment.clone());
+    let account_len: usize = MovieComment::get_account_size(comment.to_string())?;
 
-    let rent = Rent::get()?;
+    let rent = get_rent()?;
     let rent_lamports = rent.minimum_balance(account_len);
 
     let (pda, bump_seed) = Pubkey::find_program_address(
@@ -341,20 +1424,19 @@ pub fn add_comment(
     );
 
     if pda != *pda_comment.key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into());
+        return Err(invalid_pda(3, &pda, pda_comment.key));
     }
 
     invoke_signed(
         &system_instruction::create_account(
-            commenter.key, 
+            payer.key, 
             pda_comment.key, 
             rent_lamports, 
-            account_len.try_into().unwrap(), 
+            checked_cast(account_len)?, 
             program_id
         ), 
         &[
-            commenter.clone(),
+            payer.clone(),
             pda_comment.clone(),
             system_program.clone(),
         ], 
@@ -379,52 +1461,65 @@ pub fn add_comment(
     comment_data.discriminator = MovieComment::DISCRIMINATOR.to_string();
     comment_data.review = *pda_review.key;
     comment_data.commenter = *commenter.key;
-    comment_data.comment = comment;
+    comment_data.comment = comment.to_string();
+    comment_data.count = counter_data.counter;
+    comment_data.created_at = get_clock()?.unix_timestamp;
     comment_data.is_initialized = true;
     
     comment_data.serialize(&mut &mut pda_comment.data.borrow_mut()[..])?;
 
     msg!("Comment Count: {}", counter_data.counter);
-    counter_data.counter += 1;
+    let reward = comment_reward(counter_data.counter);
+    counter_data.counter = checked_add(counter_data.counter, 1)?;
     counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
 
-    msg!("Deriving mint authority");
-    let (mint_pda, _mint_bump) = Pubkey::find_program_address(&[b"token_mint"], program_id);
-    let (mint_auth_pda, mint_auth_bump) = Pubkey::find_program_address(&[b"token_auth"], program_id);
+    let reward_config = reward_config(program_id, config, &reward_addresses.mint)?;
 
-    if mint_pda != *token_mint.key {
-        msg!("Incorrect token mint");
-        return Err(ReviewError::IncorrectAccount.into());
-    }
+    check_reward_cap(&reward_config, reward)?;
 
-    if mint_auth_pda != *mint_auth.key {
-        msg!("Mint authority passed in and mint authority derived do not match");
-        return Err(ReviewError::InvalidPDA.into());
-    }
+    let mut stats = load_global_stats(program_id, payer, pda_stats, system_program)?;
+    stats.total_comments = checked_add(stats.total_comments, 1)?;
 
-    if get_associated_token_address(commenter.key, token_mint.key) != *user_ata.key {
-        msg!("Incorrect ATA for commenter");
-        return Err(ReviewError::IncorrectAccount.into());
-    }
+    let rewarded = if reward_config.claim_rewards {
+        accrue_reward(
+            program_id,
+            commenter.key,
+            payer,
+            user_ata,
+            system_program,
+            reward,
+        )?;
+
+        true
+    } else {
+        msg!("Minting {} reward units to User ATA", reward);
+        let minted = mint_reward(
+            &reward_addresses,
+            commenter.key,
+            &reward_config.comment_mint.unwrap_or(reward_config.reward_mint),
+            token_mint,
+            mint_auth,
+            user_ata,
+            token_program,
+            reward,
+        );
 
-    if TOKEN_PROGRAM_ID != *token_program.key {
-        msg!("Incorrect token program");
-        return Err(ReviewError::IncorrectAccount.into());
+        tolerate_revoked_authority(&reward_config, minted)?
+    };
+
+    if rewarded {
+        stats.total_tokens_minted = checked_add(stats.total_tokens_minted, reward)?;
     }
 
-    msg!("Minting 5 tokens to User ATA");
-    invoke_signed(
-        &spl_token::instruction::mint_to(
-            token_program.key, 
-            token_mint.key, 
-            user_ata.key, 
-            mint_auth.key, 
-            &[], 
-            sol_to_lamports(5.0)
-        )?, 
-        &[token_mint.clone(), user_ata.clone(), mint_auth.clone()], 
-        &[&[b"token_auth", &[mint_auth_bump]]],
-    )?;
+    stats.serialize(&mut &mut pda_stats.data.borrow_mut()[..])?;
+
+    #[cfg(feature = "anchor-events")]
+    emit(&CommentAdded {
+        commenter: *commenter.key,
+        review: *pda_review.key,
+        comment: *pda_comment.key,
+        index: comment_data.count,
+    });
 
     Ok(())
 }   
@@ -433,6 +1528,8 @@ pub fn initialize_token_mint(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
 ) -> ProgramResult {
+    require_accounts(accounts, 5)?;
+
     let account_info_iter = &mut accounts.iter();
 
     let initializer = next_account_info(account_info_iter)?;
@@ -441,11 +1538,13 @@ pub fn initialize_token_mint(
     let system_program = next_account_info(account_info_iter)?;
     let token_program = next_account_info(account_info_iter)?;
 
-    let (mint_pda, mint_bump) = Pubkey::find_program_address(&[b"token_mint"], program_id);
-    let (mint_auth_pda, _mint_auth_bump) = Pubkey::find_program_address(&[b"token_auth"], program_id);
+    let (mint_pda, mint_bump) = mint_pda(program_id);
+    let (mint_auth_pda, _mint_auth_bump) = mint_auth_pda(program_id);
+    let (freeze_auth_pda, _freeze_auth_bump) = freeze_auth_pda(program_id);
 
     msg!("Token mint: {:?}", mint_pda);
     msg!("Mint authority: {:?}", mint_auth_pda);
+    msg!("Freeze authority: {:?}", freeze_auth_pda);
 
     if mint_pda != *token_mint.key {
         msg!("Incorrect token mint account");
@@ -462,15 +1561,22 @@ pub fn initialize_token_mint(
         return Err(ReviewError::IncorrectAccount.into());
     }
 
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(spl_token::state::Mint::LEN);
+    #[cfg(not(feature = "token-2022"))]
+    let mint_len = token::state::Mint::LEN;
+    #[cfg(feature = "token-2022")]
+    let mint_len = token::extension::ExtensionType::try_calculate_account_len::<token::state::Mint>(
+        &[token::extension::ExtensionType::NonTransferable]
+    )?;
+
+    let rent = get_rent()?;
+    let rent_lamports = rent.minimum_balance(mint_len);
 
     invoke_signed(
         &system_instruction::create_account(
             initializer.key, 
             token_mint.key, 
             rent_lamports, 
-            spl_token::state::Mint::LEN.try_into().unwrap(), 
+            checked_cast(mint_len)?, 
             token_program.key,
         ), 
         &[
@@ -478,24 +1584,33 @@ pub fn initialize_token_mint(
             token_mint.clone(),
             system_program.clone(),
         ], 
-        &[&[b"token_mint", &[mint_bump]]],
+        &[&[TOKEN_MINT_SEED, &[mint_bump]]],
     )?;
 
     msg!("Created token mint account");
 
+    #[cfg(feature = "token-2022")]
+    invoke(
+        &token::instruction::initialize_non_transferable_mint(
+            token_program.key, 
+            token_mint.key,
+        )?, 
+        std::slice::from_ref(token_mint),
+    )?;
+
     invoke_signed(
         &initialize_mint2(
             token_program.key, 
             token_mint.key, 
             mint_auth.key, 
-            None, 
-            9,
+            Some(&freeze_auth_pda), 
+            REWARD_MINT_DECIMALS,
         )?, 
         &[
             token_mint.clone(),
             mint_auth.clone(),
         ],
-        &[&[b"token_mint", &[mint_bump]]], 
+        &[&[TOKEN_MINT_SEED, &[mint_bump]]], 
     )?;
 
     msg!("Initialized token mint");
@@ -503,49 +1618,12979 @@ pub fn initialize_token_mint(
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub fn change_review_language(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    language_code: [u8; 2],
+) -> ProgramResult {
+    msg!("Changing review language...");
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+
+    if pda_account.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    if !reviewer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[reviewer.key.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    if pda != *pda_account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let mut account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
+
+    if !account_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if !is_valid_language_code(&language_code) {
+        msg!("Language code must be two lowercase ASCII letters");
+        return Err(ReviewError::InvalidLanguageCode.into());
+    }
+
+    msg!(
+        "Language: {} -> {}",
+        String::from_utf8_lossy(&account_data.language_code),
+        String::from_utf8_lossy(&language_code)
+    );
+
+    account_data.language_code = language_code;
+    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn gift_tokens(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    recipient: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    msg!("Gifting {} tokens to {}", amount, recipient);
+
+    require_accounts(accounts, 5)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let gifter = next_account_info(account_info_iter)?;
+    let gifter_ata = next_account_info(account_info_iter)?;
+    let recipient_ata = next_account_info(account_info_iter)?;
+    let token_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    if !gifter.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (mint_pda, _mint_bump) = mint_pda(program_id);
+
+    if mint_pda != *token_mint.key {
+        msg!("Incorrect token mint");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if TOKEN_PROGRAM_ID != *token_program.key {
+        msg!("Incorrect token program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if get_associated_token_address_with_program_id(gifter.key, token_mint.key, &TOKEN_PROGRAM_ID) != *gifter_ata.key {
+        msg!("Incorrect ATA for gifter");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if get_associated_token_address_with_program_id(&recipient, token_mint.key, &TOKEN_PROGRAM_ID) != *recipient_ata.key {
+        msg!("Incorrect ATA for recipient");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if recipient_ata.owner != token_program.key || recipient_ata.data_is_empty() {
+        msg!("Recipient ATA does not exist");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let gifter_balance = token_account_balance(&gifter_ata.data.borrow())?;
+
+    if gifter_balance < amount {
+        msg!("Gifter holds {} tokens", gifter_balance);
+        return Err(ReviewError::InsufficientTokenBalance.into());
+    }
+
+    invoke(
+        &token::instruction::transfer_checked(
+            token_program.key, 
+            gifter_ata.key, 
+            token_mint.key, 
+            recipient_ata.key, 
+            gifter.key, 
+            &[], 
+            amount,
+            REWARD_MINT_DECIMALS,
+        )?, 
+        &[gifter_ata.clone(), token_mint.clone(), recipient_ata.clone(), gifter.clone()],
+    )?;
+
+    msg!("Tokens gifted");
+
+    Ok(())
+}
+
+pub fn query_reviews_by_range(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_rating: u8,
+    max_rating: u8,
+    max_results: u8,
+) -> ProgramResult {
+    msg!("Querying reviews rated {} to {}", min_rating, max_rating);
+
+    let max_results = max_results.min(MAX_QUERY_RESULTS);
+    let mut results: u8 = 0;
+
+    for pda_review in accounts {
+        if results >= max_results {
+            break;
+        }
+
+        if pda_review.owner != program_id {
+            continue;
+        }
+
+        let review: MovieAccountState = match try_from_slice_unchecked(&pda_review.data.borrow()) {
+            Ok(review) => review,
+            Err(_) => continue,
+        };
+
+        if review.discriminator != MovieAccountState::DISCRIMINATOR || !review.is_initialized() {
+            continue;
+        }
+
+        if review.rating < min_rating || review.rating > max_rating {
+            continue;
+        }
+
+        msg!("REVIEW:{}:{}:{}:{}", pda_review.key, review.reviewer, review.rating, review.title);
+        results += 1;
+    }
+
+    msg!("Matched {} reviews", results);
+
+    Ok(())
+}
+
+// Logs each review as `EXPORT:title:rating:description:created_at` for
+// backups. Fields are not escaped, so a title containing `:` makes the line
+// ambiguous; the account order gives the PDA of each line.
+pub fn export_all_reviews(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reviewer: Pubkey,
+) -> ProgramResult {
+    msg!("Exporting reviews of {}", reviewer);
+
+    if accounts.len() > MAX_EXPORT_REVIEWS {
+        msg!("At most {} reviews can be exported at once", MAX_EXPORT_REVIEWS);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    for pda_review in accounts {
+        if pda_review.owner != program_id {
+            return Err(ProgramError::InvalidAccountOwner)
+        }
+
+        let review: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+        if review.discriminator != MovieAccountState::DISCRIMINATOR || !review.is_initialized() {
+            msg!("{} is not a review", pda_review.key);
+            return Err(ReviewError::UninitializedAccount.into());
+        }
+
+        if review.reviewer != reviewer {
+            msg!("Review {} belongs to {}", pda_review.key, review.reviewer);
+            return Err(ReviewError::Unauthorized.into());
+        }
+
+        msg!("EXPORT:{}:{}:{}:{}", review.title, review.rating, review.description, review.created_at);
+    }
+
+    msg!("Exported {} reviews", accounts.len());
+
+    Ok(())
+}
+
+pub fn batch_create_atas(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    users: Vec<Pubkey>,
+) -> ProgramResult {
+    msg!("Creating {} ATAs...", users.len());
+
+    require_accounts(accounts, 5 + 2 * users.len())?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let payer = next_account_info(account_info_iter)?;
+    let token_mint = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let ata_program = next_account_info(account_info_iter)?;
+
+    if !payer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (mint_pda, _mint_bump) = mint_pda(program_id);
+
+    if mint_pda != *token_mint.key {
+        msg!("Incorrect token mint");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if TOKEN_PROGRAM_ID != *token_program.key {
+        msg!("Incorrect token program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if ASSOCIATED_TOKEN_PROGRAM_ID != *ata_program.key {
+        msg!("Incorrect associated token program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    for user in users {
+        let user_account = next_account_info(account_info_iter)?;
+        let user_ata = next_account_info(account_info_iter)?;
+
+        if *user_account.key != user {
+            msg!("User account does not match {}", user);
+            return Err(ReviewError::IncorrectAccount.into());
+        }
+
+        if get_associated_token_address_with_program_id(&user, token_mint.key, &TOKEN_PROGRAM_ID) != *user_ata.key {
+            msg!("Incorrect ATA for {}", user);
+            return Err(ReviewError::IncorrectAccount.into());
+        }
+
+        invoke(
+            &create_associated_token_account_idempotent(
+                payer.key, 
+                &user, 
+                token_mint.key, 
+                token_program.key,
+            ), 
+            &[
+                payer.clone(),
+                user_ata.clone(),
+                user_account.clone(),
+                token_mint.clone(),
+                system_program.clone(),
+                token_program.clone(),
+                ata_program.clone(),
+            ],
+        )?;
+
+        msg!("ATA ready for {}", user);
+    }
+
+    Ok(())
+}
+
+pub fn migrate_legacy_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Migrating legacy review...");
+
+    require_accounts(accounts, 3)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if pda_account.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    if !reviewer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (rating, title, description, reward_minted) = if has_review_discriminator(&pda_account.data.borrow()) {
+        if is_current_review(program_id, pda_account) {
+            msg!("Account already uses the current layout");
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let baseline_data: BaselineMovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())
+            .map_err(|_| ReviewError::NotLegacyAccount)?;
+
+        if !baseline_data.is_initialized || baseline_data.reviewer != *reviewer.key {
+            msg!("Account does not look like a baseline review");
+            return Err(ReviewError::NotLegacyAccount.into());
+        }
+
+        // The baseline handler minted `REVIEW_REWARD` with every review, so
+        // a migrated one must not become eligible for `BackfillReward`
+        (baseline_data.rating, baseline_data.title, baseline_data.description, true)
+    } else {
+        let legacy_data: LegacyMovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())
+            .map_err(|_| ReviewError::NotLegacyAccount)?;
+
+        if !legacy_data.is_initialized || !(1..=5).contains(&legacy_data.rating) {
+            msg!("Account does not look like a legacy review");
+            return Err(ReviewError::NotLegacyAccount.into());
+        }
+
+        (legacy_data.rating, legacy_data.title, legacy_data.description, false)
+    };
+
+    msg!("Legacy review title: {}", title);
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[reviewer.key.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    if pda != *pda_account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let capacity = review_capacity(pda_account);
+
+    validate_review_size(&title, &description, None, &[], capacity)?;
+
+    let rent = get_rent()?;
+    let rent_lamports = rent.minimum_balance(capacity);
+
+    if pda_account.lamports() < rent_lamports {
+        invoke(
+            &system_instruction::transfer(
+                reviewer.key, 
+                pda_account.key, 
+                checked_sub(rent_lamports, pda_account.lamports())?,
+            ), 
+            &[
+                reviewer.clone(),
+                pda_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    if pda_account.data_len() < MovieAccountState::LEN {
+        pda_account.resize(MovieAccountState::LEN)?;
+    }
+
+    let account_data = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        version: MovieAccountState::VERSION,
+        reviewer: *reviewer.key,
+        rating,
+        language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+        watched_at: 0,
+        created_at: 0,
+        reactions: [0; MovieAccountState::REACTION_KINDS],
+        comment_moderation: MovieAccountState::COMMENTS_OPEN,
+        reward_minted,
+        tight_size: false,
+        is_sponsored: false,
+        title,
+        description,
+        poster_uri: None,
+        tags: Vec::new(),
+    };
+
+    msg!("Serializing account");
+    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+    msg!("Review migrated to version {}", MovieAccountState::VERSION);
+
+    Ok(())
+}
+
+fn has_review_discriminator(data: &[u8]) -> bool {
+    let discriminator = MovieAccountState::DISCRIMINATOR.as_bytes();
+
+    data.len() >= 4 + discriminator.len()
+        && data[..4] == (discriminator.len() as u32).to_le_bytes()
+        && &data[4..4 + discriminator.len()] == discriminator
+}
+
+// Baseline reviews carry the same discriminator and can still happen to
+// parse as the current struct, so a review only counts as current once its
+// stored version, reviewer and title re-derive its address
+fn is_current_review(program_id: &Pubkey, pda_account: &AccountInfo) -> bool {
+    let Ok(review) = try_from_slice_unchecked::<MovieAccountState>(&pda_account.data.borrow()) else {
+        return false;
+    };
+
+    // A misread title may be too long to be a seed
+    let pda = Pubkey::try_find_program_address(
+        &[review.reviewer.as_ref(), MovieAccountState::title_seed(&review.title).as_bytes()], 
+        program_id
+    );
+
+    review.version == MovieAccountState::VERSION
+        && pda.is_some_and(|(pda, _bump_seed)| pda == *pda_account.key)
+}
+
+pub fn initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Initializing program config...");
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let program_data = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Only the upgrade authority may claim the admin role, so the config
+    // can't be front-run by whoever calls this first after a deploy
+    if program_upgrade_authority(program_id, program_data)? != Some(*admin.key) {
+        msg!("Admin must be the program upgrade authority");
+        return Err(ReviewError::Unauthorized.into());
+    }
+
+    let (pda, bump_seed) = Pubkey::find_program_address(&[b"config"], program_id);
+    if pda != *pda_config.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(ProgramConfig::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            admin.key, 
+            pda_config.key, 
+            rent_lamports, 
+            ProgramConfig::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            admin.clone(),
+            pda_config.clone(),
+            system_program.clone(),
+        ], 
+        &[&[b"config", &[bump_seed]]],
+    )?;
+
+    let config = ProgramConfig {
+        discriminator: ProgramConfig::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        admin: *admin.key,
+        reward_mint: Pubkey::default(),
+        claim_rewards: false,
+        clawback_period: ProgramConfig::DEFAULT_CLAWBACK_PERIOD,
+        skip_revoked_rewards: false,
+        callback_program_id: None,
+        min_description_len_for_reward: 0,
+        min_reviews_for_auto_verify: ProgramConfig::DEFAULT_MIN_REVIEWS_FOR_AUTO_VERIFY,
+        max_reward_amount: ProgramConfig::DEFAULT_MAX_REWARD_AMOUNT,
+        comment_mint: None,
+    };
+
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    msg!("Config admin: {}", admin.key);
+
+    Ok(())
+}
+
+pub fn set_reward_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reward_mint: Pubkey,
+) -> ProgramResult {
+    msg!("Setting reward mint to {}", reward_mint);
+
+    require_accounts(accounts, 3)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let token_mint = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the reward mint");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if reward_mint != *token_mint.key || token_mint.owner != &TOKEN_PROGRAM_ID {
+        msg!("Incorrect token mint");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let (mint_auth_pda, _mint_auth_bump) = mint_auth_pda(program_id);
+
+    if mint_authority(&token_mint.data.borrow())? != COption::Some(mint_auth_pda) {
+        msg!("Mint authority must be {}", mint_auth_pda);
+        return Err(ReviewError::InvalidMintAuthority.into());
+    }
+
+    config.reward_mint = reward_mint;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Passing `None` sends comment rewards back to the reward mint. A new comment
+// mint must be passed as the third account, with the program's `token_auth`
+// PDA as its mint authority.
+pub fn set_comment_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment_mint: Option<Pubkey>,
+) -> ProgramResult {
+    match comment_mint {
+        Some(comment_mint) => msg!("Setting comment mint to {}", comment_mint),
+        None => msg!("Clearing comment mint"),
+    }
+
+    require_accounts(accounts, if comment_mint.is_some() { 3 } else { 2 })?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the comment mint");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    // Claimed rewards are pooled per user and paid out in `reward_mint`
+    if comment_mint.is_some() && config.claim_rewards {
+        msg!("Disable claim rewards before setting a comment mint");
+        return Err(ReviewError::ConflictingRewardSettings.into());
+    }
+
+    if let Some(comment_mint) = comment_mint {
+        let token_mint = next_account_info(account_info_iter)?;
+
+        if comment_mint != *token_mint.key || token_mint.owner != &TOKEN_PROGRAM_ID {
+            msg!("Incorrect token mint");
+            return Err(ReviewError::IncorrectAccount.into());
+        }
+
+        let (mint_auth_pda, _mint_auth_bump) = mint_auth_pda(program_id);
+
+        if mint_authority(&token_mint.data.borrow())? != COption::Some(mint_auth_pda) {
+            msg!("Mint authority must be {}", mint_auth_pda);
+            return Err(ReviewError::InvalidMintAuthority.into());
+        }
+    }
+
+    config.comment_mint = comment_mint;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// SPL tokens freeze token accounts, not mints, so this freezes (or thaws) one
+// holder's account of the PDA mint; `mint_to` into a frozen account fails.
+// Mints initialized before the `freeze_auth` PDA became their freeze
+// authority have none and cannot be frozen.
+pub fn set_token_account_frozen(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    freeze: bool,
+) -> ProgramResult {
+    msg!("{} token account", if freeze { "Freezing" } else { "Thawing" });
+
+    require_accounts(accounts, 6)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let token_mint = next_account_info(account_info_iter)?;
+    let token_account = next_account_info(account_info_iter)?;
+    let freeze_auth = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can freeze token accounts");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if mint_pda(program_id).0 != *token_mint.key {
+        msg!("Incorrect token mint account");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if TOKEN_PROGRAM_ID != *token_program.key {
+        msg!("Incorrect token program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let (freeze_auth_pda, freeze_auth_bump) = freeze_auth_pda(program_id);
+    if freeze_auth_pda != *freeze_auth.key {
+        msg!("Incorrect freeze auth account");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let instruction = if freeze {
+        token::instruction::freeze_account(
+            token_program.key, 
+            token_account.key, 
+            token_mint.key, 
+            freeze_auth.key, 
+            &[],
+        )?
+    } else {
+        token::instruction::thaw_account(
+            token_program.key, 
+            token_account.key, 
+            token_mint.key, 
+            freeze_auth.key, 
+            &[],
+        )?
+    };
+
+    invoke_signed(
+        &instruction, 
+        &[
+            token_account.clone(),
+            token_mint.clone(),
+            freeze_auth.clone(),
+        ], 
+        &[&[FREEZE_AUTH_SEED, &[freeze_auth_bump]]],
+    )?;
+
+    Ok(())
+}
+
+fn load_config(program_id: &Pubkey, pda_config: &AccountInfo) -> Result<ProgramConfig, ProgramError> {
+    let (pda, _bump_seed) = Pubkey::find_program_address(&[b"config"], program_id);
+    if pda != *pda_config.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_config.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let config: ProgramConfig = try_from_slice_unchecked(&pda_config.data.borrow())?;
+    if !config.is_initialized() {
+        msg!("Config is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(config)
+}
+
+// Effective reward settings. Without a config, rewards are pushed on every
+// write, and an unset mint resolves to `pda_mint`, the derived PDA mint.
+fn reward_config(
+    program_id: &Pubkey,
+    pda_config: &AccountInfo,
+    pda_mint: &Pubkey,
+) -> Result<ProgramConfig, ProgramError> {
+    let mut config = if pda_config.data_is_empty() {
+        let (pda, _bump_seed) = Pubkey::find_program_address(&[b"config"], program_id);
+        if pda != *pda_config.key {
+            msg!("Invalid seeds for PDA");
+            return Err(ReviewError::InvalidPDA.into());
+        }
+
+        ProgramConfig {
+            discriminator: ProgramConfig::DISCRIMINATOR.to_string(),
+            is_initialized: false,
+            admin: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            claim_rewards: false,
+            clawback_period: ProgramConfig::DEFAULT_CLAWBACK_PERIOD,
+            skip_revoked_rewards: false,
+            callback_program_id: None,
+            min_description_len_for_reward: 0,
+            min_reviews_for_auto_verify: ProgramConfig::DEFAULT_MIN_REVIEWS_FOR_AUTO_VERIFY,
+            max_reward_amount: ProgramConfig::DEFAULT_MAX_REWARD_AMOUNT,
+            comment_mint: None,
+        }
+    } else {
+        load_config(program_id, pda_config)?
+    };
+
+    if config.reward_mint == Pubkey::default() {
+        config.reward_mint = *pda_mint;
+    }
+
+    Ok(config)
+}
+
+pub fn set_claim_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    msg!("Setting claim rewards to {}", enabled);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the reward model");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    // Claimed rewards are pooled per user and paid out in `reward_mint`
+    if enabled && config.comment_mint.is_some() {
+        msg!("Clear the comment mint before enabling claim rewards");
+        return Err(ReviewError::ConflictingRewardSettings.into());
+    }
+
+    config.claim_rewards = enabled;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn set_skip_revoked_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    msg!("Setting skip revoked rewards to {}", enabled);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the reward model");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    config.skip_revoked_rewards = enabled;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn set_callback_program(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    callback_program_id: Option<Pubkey>,
+) -> ProgramResult {
+    match callback_program_id {
+        Some(callback_program_id) => msg!("Setting callback program to {}", callback_program_id),
+        None => msg!("Clearing callback program"),
+    }
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can set the callback program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if callback_program_id == Some(*program_id) {
+        msg!("The program cannot be its own callback");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    config.callback_program_id = callback_program_id;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn set_min_description_len_for_reward(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_len: u32,
+) -> ProgramResult {
+    msg!("Setting minimum description length for rewards to {} bytes", min_len);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the reward threshold");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    config.min_description_len_for_reward = min_len;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn set_auto_verify_threshold(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u8,
+) -> ProgramResult {
+    msg!("Setting auto-verify threshold to {} reviews", threshold);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the auto-verify threshold");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    config.min_reviews_for_auto_verify = threshold;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn set_max_reward_amount(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_amount: u64,
+) -> ProgramResult {
+    msg!("Setting maximum reward to {} base units", max_amount);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the maximum reward");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    config.max_reward_amount = max_amount;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+fn check_reward_cap(config: &ProgramConfig, amount: u64) -> ProgramResult {
+    if amount > config.max_reward_amount {
+        msg!("Reward of {} base units exceeds the maximum of {}", amount, config.max_reward_amount);
+        return Err(ReviewError::RewardTooLarge.into());
+    }
+
+    Ok(())
+}
+
+// Whether the reward was minted. A revoked mint authority is only an error
+// when the config does not ask for rewards to be skipped.
+fn tolerate_revoked_authority(config: &ProgramConfig, minted: ProgramResult) -> Result<bool, ProgramError> {
+    match minted {
+        Ok(()) => Ok(true),
+        Err(err) if config.skip_revoked_rewards && err == ReviewError::MintAuthorityRevoked.into() => {
+            msg!("Skipping reward: mint authority revoked");
+            Ok(false)
+        },
+        Err(err) => Err(err),
+    }
+}
+
+pub fn backfill_reward(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Backfilling review reward...");
+
+    require_accounts(accounts, 7)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let token_mint = next_account_info(account_info_iter)?;
+    let mint_auth = next_account_info(account_info_iter)?;
+    let user_ata = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can backfill rewards");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if pda_account.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
+    if !account_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[account_data.reviewer.as_ref(), MovieAccountState::title_seed(&account_data.title).as_bytes()], 
+        program_id
+    );
+    if pda != *pda_account.key {
+        return Err(invalid_pda(2, &pda, pda_account.key));
+    }
+
+    if account_data.reward_minted {
+        msg!("Reward already minted for this review");
+        return Err(ReviewError::RewardAlreadyMinted.into());
+    }
+
+    account_data.reward_minted = true;
+    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+
+    let reward_addresses = reward_addresses(program_id);
+    let reward_config = reward_config(program_id, pda_config, &reward_addresses.mint)?;
+
+    check_reward_cap(&reward_config, REVIEW_REWARD)?;
+
+    mint_review_reward(
+        &reward_addresses,
+        &account_data.reviewer,
+        &reward_config.reward_mint,
+        token_mint,
+        mint_auth,
+        user_ata,
+        token_program,
+    )
+}
+
+pub fn set_clawback_period(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    seconds: i64,
+) -> ProgramResult {
+    msg!("Setting clawback period to {} seconds", seconds);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    let mut config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can change the clawback period");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    config.clawback_period = seconds;
+    config.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+fn accrue_reward<'a>(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda_tracker: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let (tracker, tracker_bump) = Pubkey::find_program_address(
+        &[b"rewards", user.as_ref()], 
+        program_id
+    );
+
+    if tracker != *pda_tracker.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_tracker.data_is_empty() {
+        msg!("Creating reward tracker");
+        let rent_lamports = get_rent()?.minimum_balance(RewardTracker::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key, 
+                pda_tracker.key, 
+                rent_lamports, 
+                RewardTracker::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                payer.clone(),
+                pda_tracker.clone(),
+                system_program.clone(),
+            ], 
+            &[&[b"rewards", user.as_ref(), &[tracker_bump]]],
+        )?;
+    } else if pda_tracker.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut tracker_data: RewardTracker = try_from_slice_unchecked(&pda_tracker.data.borrow())?;
+
+    if !tracker_data.is_initialized() {
+        tracker_data.discriminator = RewardTracker::DISCRIMINATOR.to_string();
+        tracker_data.is_initialized = true;
+        tracker_data.user = *user;
+    }
+
+    tracker_data.unclaimed_rewards = checked_add(tracker_data.unclaimed_rewards, amount)?;
+    msg!("Accrued {} reward units, {} unclaimed", amount, tracker_data.unclaimed_rewards);
+
+    tracker_data.serialize(&mut &mut pda_tracker.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn claim_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Claiming rewards...");
+
+    require_accounts(accounts, 7)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let user = next_account_info(account_info_iter)?;
+    let pda_tracker = next_account_info(account_info_iter)?;
+    let token_mint = next_account_info(account_info_iter)?;
+    let mint_auth = next_account_info(account_info_iter)?;
+    let user_ata = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let config = next_account_info(account_info_iter)?;
+
+    if !user.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (tracker, _tracker_bump) = Pubkey::find_program_address(
+        &[b"rewards", user.key.as_ref()], 
+        program_id
+    );
+
+    if tracker != *pda_tracker.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_tracker.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut tracker_data: RewardTracker = try_from_slice_unchecked(&pda_tracker.data.borrow())?;
+
+    if !tracker_data.is_initialized() {
+        msg!("Reward tracker is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let amount = tracker_data.unclaimed_rewards;
+
+    if amount == 0 {
+        msg!("No rewards to claim");
+        return Ok(());
+    }
+
+    tracker_data.unclaimed_rewards = 0;
+    tracker_data.serialize(&mut &mut pda_tracker.data.borrow_mut()[..])?;
+
+    let reward_addresses = reward_addresses(program_id);
+
+    msg!("Minting {} reward units to User ATA", amount);
+    mint_reward(
+        &reward_addresses,
+        user.key,
+        &reward_config(program_id, config, &reward_addresses.mint)?.reward_mint,
+        token_mint,
+        mint_auth,
+        user_ata,
+        token_program,
+        amount,
+    )
+}
+
+pub fn delete_movie_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+) -> ProgramResult {
+    msg!("Deleting movie review...");
+    msg!("Title: {}", title);
+
+    require_accounts(accounts, 8)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let token_mint = next_account_info(account_info_iter)?;
+    // With claim rewards enabled this is the reviewer's `RewardTracker`
+    let user_ata = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+
+    if !reviewer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if pda_account.owner != program_id || pda_counter.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[reviewer.key.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    if pda != *pda_account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let (counter, _counter_bump) = Pubkey::find_program_address(
+        &[pda_account.key.as_ref(), b"comment"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
+    if !account_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let counter_data: MovieCommentCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+
+    let (mint_pda, _mint_bump) = mint_pda(program_id);
+    let reward_config = reward_config(program_id, config, &mint_pda)?;
+
+    // Deleting soon after posting claws the review reward back so that
+    // add/delete cycles cannot be used to farm tokens. Reviews that were
+    // never paid have nothing to give back.
+    let clawback = account_data.reward_minted
+        && checked_sub(get_clock()?.unix_timestamp, account_data.created_at)? < reward_config.clawback_period;
+    if clawback && reward_config.claim_rewards {
+        debit_review_reward(program_id, reviewer.key, user_ata)?;
+    } else if clawback {
+        burn_review_reward(
+            reviewer,
+            &reward_config.reward_mint,
+            token_mint,
+            user_ata,
+            token_program,
+        )?;
+    }
+
+    let (stats, _stats_bump) = Pubkey::find_program_address(&[b"stats"], program_id);
+    if stats != *pda_stats.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    // Reviews that predate the stats account were never counted
+    if !pda_stats.data_is_empty() {
+        if pda_stats.owner != program_id {
+            return Err(ProgramError::InvalidAccountOwner)
+        }
+
+        let mut stats_data: GlobalStats = try_from_slice_unchecked(&pda_stats.data.borrow())?;
+        stats_data.total_reviews = stats_data.total_reviews.saturating_sub(1);
+        stats_data.total_comments = stats_data.total_comments.saturating_sub(counter_data.counter);
+        if clawback {
+            stats_data.total_tokens_minted = stats_data.total_tokens_minted.saturating_sub(REVIEW_REWARD);
+        }
+        stats_data.serialize(&mut &mut pda_stats.data.borrow_mut()[..])?;
+    }
+
+    // Comments outlive the review, so their counter has to as well
+    if counter_data.counter == 0 {
+        close_account(pda_counter, reviewer)?;
+    } else {
+        msg!("Keeping comment counter for {} comments", counter_data.counter);
+    }
+    close_account(pda_account, reviewer)?;
+    msg!("Review closed");
+
+    Ok(())
+}
+
+// Titles are part of the review seeds, so a rename moves the review and its
+// comment counter to PDAs derived from the new title. Existing comments stay
+// seeded by the old review; if there are any, the old review is replaced by a
+// `ReviewTombstone` pointing at the new one instead of being closed.
+pub fn rename_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    new_title: String,
+) -> ProgramResult {
+    msg!("Renaming movie review...");
+    msg!("Title: {}", title);
+    msg!("New title: {}", new_title);
+
+    require_accounts(accounts, 6)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let new_pda_account = next_account_info(account_info_iter)?;
+    let new_pda_counter = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let mut account_data = load_owned_review(program_id, reviewer, pda_account, &title)?;
+
+    let (counter, _counter_bump) = Pubkey::find_program_address(
+        &[pda_account.key.as_ref(), b"comment"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_counter.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let counter_data: MovieCommentCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+
+    let (new_pda, new_bump) = Pubkey::find_program_address(
+        &[reviewer.key.as_ref(), MovieAccountState::title_seed(&new_title).as_bytes()], 
+        program_id
+    );
+    if new_pda != *new_pda_account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if !new_pda_account.data_is_empty() {
+        msg!("A review with the new title already exists");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    // The renamed review is a new account under the current cap
+    validate_review_size(
+        &new_title,
+        &account_data.description,
+        account_data.poster_uri.as_deref(),
+        &account_data.tags,
+        MovieAccountState::LEN,
+    )?;
+
+    account_data.title = new_title;
+
+    let rent = get_rent()?;
+
+    create_review_account(
+        program_id,
+        reviewer,
+        new_pda_account,
+        system_program,
+        &rent,
+        new_bump,
+        account_data,
+    )?;
+
+    let new_counter = Pubkey::find_program_address(
+        &[new_pda_account.key.as_ref(), b"comment"], 
+        program_id
+    );
+
+    create_comment_counter(
+        program_id,
+        reviewer,
+        new_pda_account.key,
+        new_counter,
+        new_pda_counter,
+        system_program,
+        &rent,
+    )?;
+
+    let mut new_counter_data: MovieCommentCounter = try_from_slice_unchecked(&new_pda_counter.data.borrow())?;
+    new_counter_data.counter = new_counter_data.counter.max(counter_data.counter);
+    new_counter_data.serialize(&mut &mut new_pda_counter.data.borrow_mut()[..])?;
+
+    close_account(pda_counter, reviewer)?;
+
+    if counter_data.counter == 0 {
+        close_account(pda_account, reviewer)?;
+        msg!("Old review closed");
+
+        return Ok(());
+    }
+
+    pda_account.data.borrow_mut().fill(0);
+    pda_account.resize(ReviewTombstone::LEN)?;
+
+    let refund = pda_account.lamports().saturating_sub(rent.minimum_balance(ReviewTombstone::LEN));
+
+    **pda_account.try_borrow_mut_lamports()? = checked_sub(pda_account.lamports(), refund)?;
+    **reviewer.try_borrow_mut_lamports()? = checked_add(reviewer.lamports(), refund)?;
+
+    let tombstone = ReviewTombstone {
+        discriminator: ReviewTombstone::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        renamed_to: *new_pda_account.key,
+    };
+
+    tombstone.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+    msg!("Old review left as a tombstone for {} comments", counter_data.counter);
+
+    Ok(())
+}
+
+// Claim-mode counterpart of `burn_review_reward`; the reward is taken back
+// out of the reviewer's unclaimed balance
+fn debit_review_reward(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    pda_tracker: &AccountInfo,
+) -> ProgramResult {
+    let (tracker, _tracker_bump) = Pubkey::find_program_address(
+        &[b"rewards", reviewer.as_ref()], 
+        program_id
+    );
+    if tracker != *pda_tracker.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_tracker.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut tracker_data: RewardTracker = try_from_slice_unchecked(&pda_tracker.data.borrow())?;
+
+    if tracker_data.unclaimed_rewards < REVIEW_REWARD {
+        msg!("Reviewer has {} unclaimed, {} needed for clawback", tracker_data.unclaimed_rewards, REVIEW_REWARD);
+        return Err(ReviewError::RewardClawbackFailed.into());
+    }
+
+    msg!("Debiting review reward");
+    tracker_data.unclaimed_rewards = checked_sub(tracker_data.unclaimed_rewards, REVIEW_REWARD)?;
+    tracker_data.serialize(&mut &mut pda_tracker.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+fn burn_review_reward<'a>(
+    reviewer: &AccountInfo<'a>,
+    reward_mint: &Pubkey,
+    token_mint: &AccountInfo<'a>,
+    user_ata: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if reward_mint != token_mint.key {
+        msg!("Incorrect token mint");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if get_associated_token_address_with_program_id(reviewer.key, token_mint.key, &TOKEN_PROGRAM_ID) != *user_ata.key {
+        msg!("Incorrect ATA for {}", reviewer.key);
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if TOKEN_PROGRAM_ID != *token_program.key {
+        msg!("Incorrect token program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let balance = if user_ata.owner == token_program.key && !user_ata.data_is_empty() {
+        token_account_balance(&user_ata.data.borrow())?
+    } else {
+        0
+    };
+
+    if balance < REVIEW_REWARD {
+        msg!("Reviewer holds {} tokens, {} needed for clawback", balance, REVIEW_REWARD);
+        return Err(ReviewError::RewardClawbackFailed.into());
+    }
+
+    msg!("Burning review reward");
+    invoke(
+        &token::instruction::burn(
+            token_program.key, 
+            user_ata.key, 
+            token_mint.key, 
+            reviewer.key, 
+            &[], 
+            REVIEW_REWARD,
+        )?, 
+        &[user_ata.clone(), token_mint.clone(), reviewer.clone()],
+    )
+}
+
+// Loads the `[b"stats"]` account, creating it at `payer`'s expense on the
+// first write that reaches it
+fn load_global_stats<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda_stats: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<GlobalStats, ProgramError> {
+    let (stats, stats_bump) = Pubkey::find_program_address(&[b"stats"], program_id);
+    if stats != *pda_stats.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_stats.data_is_empty() {
+        let rent_lamports = get_rent()?.minimum_balance(GlobalStats::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key, 
+                pda_stats.key, 
+                rent_lamports, 
+                GlobalStats::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                payer.clone(),
+                pda_stats.clone(),
+                system_program.clone(),
+            ], 
+            &[&[b"stats", &[stats_bump]]],
+        )?;
+        msg!("Global stats created");
+
+        return Ok(GlobalStats {
+            discriminator: GlobalStats::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            total_reviews: 0,
+            total_comments: 0,
+            total_tokens_minted: 0,
+            total_reviewers: 0,
+        });
+    }
+
+    if pda_stats.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let stats_data: GlobalStats = try_from_slice_unchecked(&pda_stats.data.borrow())?;
+    if !stats_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(stats_data)
+}
+
+fn load_reviewer_profile<'a>(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda_profile: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<ReviewerProfile, ProgramError> {
+    let (profile, profile_bump) = Pubkey::find_program_address(
+        &[b"profile", reviewer.as_ref()], 
+        program_id
+    );
+    if profile != *pda_profile.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_profile.data_is_empty() {
+        let rent_lamports = get_rent()?.minimum_balance(ReviewerProfile::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key, 
+                pda_profile.key, 
+                rent_lamports, 
+                ReviewerProfile::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                payer.clone(),
+                pda_profile.clone(),
+                system_program.clone(),
+            ], 
+            &[&[b"profile", reviewer.as_ref(), &[profile_bump]]],
+        )?;
+        msg!("Reviewer profile created");
+
+        return Ok(ReviewerProfile {
+            discriminator: ReviewerProfile::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            reviewer: *reviewer,
+            review_count: 0,
+            has_first_badge: false,
+            is_verified: false,
+            collection_count: 0,
+        });
+    }
+
+    if pda_profile.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let profile_data: ReviewerProfile = try_from_slice_unchecked(&pda_profile.data.borrow())?;
+    if !profile_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(profile_data)
+}
+
+// Admin repair for drifted `GlobalStats`. The remaining accounts are reviews
+// and comment counters; the totals are overwritten with what they hold, so
+// they must be all of the program's. `total_reviewers` becomes the number of
+// distinct reviewers, and `total_tokens_minted` is left as is.
+pub fn recompute_program_stats(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Recomputing program stats...");
+
+    require_accounts(accounts, 3)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+
+    let candidates = account_info_iter.as_slice();
+
+    let config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can recompute stats");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if candidates.len() > MAX_RECOMPUTE_ACCOUNTS {
+        msg!("At most {} accounts can be passed", MAX_RECOMPUTE_ACCOUNTS);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (stats, _stats_bump) = Pubkey::find_program_address(&[b"stats"], program_id);
+    if stats != *pda_stats.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_stats.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut stats_data: GlobalStats = try_from_slice_unchecked(&pda_stats.data.borrow())?;
+    if !stats_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let mut seen: Vec<&Pubkey> = Vec::with_capacity(candidates.len());
+    let mut reviewers: Vec<Pubkey> = Vec::new();
+    let mut total_reviews: u64 = 0;
+    let mut total_counters: u64 = 0;
+    let mut total_comments: u64 = 0;
+
+    for account in candidates {
+        if account.owner != program_id || seen.contains(&account.key) {
+            continue;
+        }
+        seen.push(account.key);
+
+        let data = account.data.borrow();
+        let Ok(discriminator) = try_from_slice_unchecked::<String>(&data) else {
+            continue;
+        };
+
+        if discriminator == MovieAccountState::DISCRIMINATOR {
+            let Ok(review) = try_from_slice_unchecked::<MovieAccountState>(&data) else {
+                continue;
+            };
+
+            if !review.is_initialized() {
+                continue;
+            }
+
+            total_reviews = checked_add(total_reviews, 1)?;
+            if !reviewers.contains(&review.reviewer) {
+                reviewers.push(review.reviewer);
+            }
+        } else if discriminator == MovieCommentCounter::DISCRIMINATOR {
+            let Ok(counter) = try_from_slice_unchecked::<MovieCommentCounter>(&data) else {
+                continue;
+            };
+
+            if !counter.is_initialized() {
+                continue;
+            }
+
+            total_counters = checked_add(total_counters, 1)?;
+            total_comments = checked_add(total_comments, counter.counter)?;
+        }
+    }
+
+    msg!(
+        "Reviews: {} -> {}, comments: {} -> {} across {} counters",
+        stats_data.total_reviews,
+        total_reviews,
+        stats_data.total_comments,
+        total_comments,
+        total_counters
+    );
+
+    stats_data.total_reviews = total_reviews;
+    stats_data.total_comments = total_comments;
+    stats_data.total_reviewers = reviewers.len() as u64;
+
+    stats_data.serialize(&mut &mut pda_stats.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Mints a one-off badge to a reviewer who has posted exactly one review. The
+// badge is its own zero-decimal mint at `[b"badge", reviewer]`; the mint
+// authority is dropped after the single token, and `token_auth` keeps the
+// freeze authority.
+pub fn mint_first_review_badge(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reviewer: Pubkey,
+) -> ProgramResult {
+    msg!("Minting {} for {}", ReviewerProfile::FIRST_REVIEW_BADGE_NAME, reviewer);
+
+    require_accounts(accounts, 9)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let payer = next_account_info(account_info_iter)?;
+    let reviewer_account = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let badge_mint = next_account_info(account_info_iter)?;
+    let mint_auth = next_account_info(account_info_iter)?;
+    let badge_ata = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let ata_program = next_account_info(account_info_iter)?;
+
+    if !payer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if *reviewer_account.key != reviewer {
+        msg!("Reviewer account does not match {}", reviewer);
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if pda_profile.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (profile, _profile_bump) = Pubkey::find_program_address(
+        &[b"profile", reviewer.as_ref()], 
+        program_id
+    );
+    if profile != *pda_profile.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let mut profile_data: ReviewerProfile = try_from_slice_unchecked(&pda_profile.data.borrow())?;
+    if !profile_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if profile_data.has_first_badge {
+        msg!("Reviewer already holds the badge");
+        return Err(ReviewError::BadgeAlreadyMinted.into());
+    }
+
+    if profile_data.review_count != 1 {
+        msg!("Reviewer has posted {} reviews", profile_data.review_count);
+        return Err(ReviewError::BadgeNotEarned.into());
+    }
+
+    let (badge_mint_pda, badge_mint_bump) = Pubkey::find_program_address(
+        &[b"badge", reviewer.as_ref()], 
+        program_id
+    );
+    if badge_mint_pda != *badge_mint.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let (mint_auth_pda, mint_auth_bump) = mint_auth_pda(program_id);
+    if mint_auth_pda != *mint_auth.key {
+        msg!("Incorrect mint auth account");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if get_associated_token_address_with_program_id(&reviewer, badge_mint.key, &TOKEN_PROGRAM_ID) != *badge_ata.key {
+        msg!("Incorrect ATA for {}", reviewer);
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if TOKEN_PROGRAM_ID != *token_program.key {
+        msg!("Incorrect token program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if ASSOCIATED_TOKEN_PROGRAM_ID != *ata_program.key {
+        msg!("Incorrect associated token program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    #[cfg(not(feature = "token-2022"))]
+    let mint_len = token::state::Mint::LEN;
+    #[cfg(feature = "token-2022")]
+    let mint_len = token::extension::ExtensionType::try_calculate_account_len::<token::state::Mint>(&[])?;
+
+    let badge_mint_seeds: &[&[u8]] = &[b"badge", reviewer.as_ref(), &[badge_mint_bump]];
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key, 
+            badge_mint.key, 
+            get_rent()?.minimum_balance(mint_len), 
+            checked_cast(mint_len)?, 
+            token_program.key,
+        ), 
+        &[
+            payer.clone(),
+            badge_mint.clone(),
+            system_program.clone(),
+        ], 
+        &[badge_mint_seeds],
+    )?;
+
+    invoke(
+        &initialize_mint2(
+            token_program.key, 
+            badge_mint.key, 
+            mint_auth.key, 
+            Some(mint_auth.key), 
+            0,
+        )?, 
+        std::slice::from_ref(badge_mint),
+    )?;
+
+    invoke(
+        &create_associated_token_account_idempotent(
+            payer.key, 
+            &reviewer, 
+            badge_mint.key, 
+            token_program.key,
+        ), 
+        &[
+            payer.clone(),
+            badge_ata.clone(),
+            reviewer_account.clone(),
+            badge_mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            ata_program.clone(),
+        ],
+    )?;
+
+    let mint_auth_seeds: &[&[u8]] = &[TOKEN_AUTH_SEED, &[mint_auth_bump]];
+
+    invoke_signed(
+        &token::instruction::mint_to(
+            token_program.key, 
+            badge_mint.key, 
+            badge_ata.key, 
+            mint_auth.key, 
+            &[], 
+            1
+        )?, 
+        &[badge_mint.clone(), badge_ata.clone(), mint_auth.clone()], 
+        &[mint_auth_seeds],
+    )?;
+
+    // Supply is fixed at one from here on
+    invoke_signed(
+        &set_authority(
+            token_program.key, 
+            badge_mint.key, 
+            None, 
+            AuthorityType::MintTokens, 
+            mint_auth.key, 
+            &[],
+        )?, 
+        &[badge_mint.clone(), mint_auth.clone()], 
+        &[mint_auth_seeds],
+    )?;
+
+    profile_data.has_first_badge = true;
+    profile_data.serialize(&mut &mut pda_profile.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Logs `count` consecutive comments starting at `start_index`, one
+// `COMMENT:<index>:<commenter>:<text>` line each. The comment PDAs follow the
+// reviewer and review accounts in index order.
+pub fn fetch_comments(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    review_title: String,
+    start_index: u64,
+    count: u8,
+) -> ProgramResult {
+    msg!("Fetching {} comments from index {}", count, start_index);
+
+    require_accounts(accounts, 2 + usize::from(count))?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[reviewer.key.as_ref(), MovieAccountState::title_seed(&review_title).as_bytes()], 
+        program_id
+    );
+    if review != *pda_review.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    for offset in 0..u64::from(count) {
+        let index = checked_add(start_index, offset)?;
+        let pda_comment = next_account_info(account_info_iter)?;
+
+        let (comment, _comment_bump) = Pubkey::find_program_address(
+            &[pda_review.key.as_ref(), index.to_be_bytes().as_ref()], 
+            program_id
+        );
+        if comment != *pda_comment.key {
+            msg!("Invalid seeds for comment {}", index);
+            return Err(ReviewError::InvalidPDA.into());
+        }
+
+        if pda_comment.owner != program_id {
+            return Err(ProgramError::InvalidAccountOwner)
+        }
+
+        let comment_data: MovieComment = try_from_slice_unchecked(&pda_comment.data.borrow())?;
+        if !comment_data.is_initialized() {
+            msg!("Account is not initialized");
+            return Err(ReviewError::UninitializedAccount.into());
+        }
+
+        msg!("COMMENT:{}:{}:{}", index, comment_data.commenter, comment_data.comment);
+    }
+
+    Ok(())
+}
+
+// Hashes the ELF held by this program's ProgramData account and compares it
+// to `expected_hash`. The account is zero-padded up to its maximum length, so
+// trailing zeros are left out, as `solana-verify get-program-hash` does.
+pub fn verify_program_version(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    expected_hash: [u8; 32],
+) -> ProgramResult {
+    msg!("Verifying program version...");
+
+    require_accounts(accounts, 1)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let program_data = next_account_info(account_info_iter)?;
+
+    if get_program_data_address(program_id) != *program_data.key {
+        msg!("Incorrect program data account");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if *program_data.owner != bpf_loader_upgradeable::ID {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let data = program_data.data.borrow();
+    let elf = data
+        .get(UpgradeableLoaderState::size_of_programdata_metadata()..)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let elf_len = elf.iter().rposition(|&byte| byte != 0).map_or(0, |last| last + 1);
+
+    let deployed_hash = hash(&elf[..elf_len]);
+    msg!("Deployed program hash: {}", deployed_hash);
+
+    if deployed_hash.to_bytes() != expected_hash {
+        msg!("Expected program hash: {}", Hash::new_from_array(expected_hash));
+        return Err(ReviewError::VersionMismatch.into());
+    }
+
+    Ok(())
+}
+
+// Starts a two-step handover of the upgrade authority. The first proposal
+// also moves the on-chain upgrade authority to the `[b"authority"]` PDA, so
+// the pending authority can accept without the proposer signing again.
+pub fn propose_authority_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_authority: Pubkey,
+) -> ProgramResult {
+    msg!("Proposing upgrade authority transfer to {}", new_authority);
+
+    require_accounts(accounts, 5)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let authority = next_account_info(account_info_iter)?;
+    let pda_authority = next_account_info(account_info_iter)?;
+    let program_data = next_account_info(account_info_iter)?;
+    let loader_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !authority.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (record_pda, record_bump) = Pubkey::find_program_address(&[b"authority"], program_id);
+    if record_pda != *pda_authority.key {
+        return Err(invalid_pda(1, &record_pda, pda_authority.key));
+    }
+
+    if bpf_loader_upgradeable::ID != *loader_program.key {
+        msg!("Incorrect loader program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let deployed_authority = program_upgrade_authority(program_id, program_data)?;
+
+    let mut record = if pda_authority.data_is_empty() {
+        if deployed_authority != Some(*authority.key) {
+            msg!("Only the upgrade authority can propose a transfer");
+            return Err(ReviewError::IncorrectAccount.into());
+        }
+
+        let rent_lamports = get_rent()?.minimum_balance(ProgramAuthorityRecord::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key, 
+                pda_authority.key, 
+                rent_lamports, 
+                ProgramAuthorityRecord::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                authority.clone(),
+                pda_authority.clone(),
+                system_program.clone(),
+            ], 
+            &[&[b"authority", &[record_bump]]],
+        )?;
+        msg!("Authority record created");
+
+        ProgramAuthorityRecord {
+            discriminator: ProgramAuthorityRecord::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            upgrade_authority: *authority.key,
+            pending_authority: None,
+        }
+    } else {
+        load_authority_record(program_id, pda_authority)?
+    };
+
+    if record.upgrade_authority != *authority.key {
+        msg!("Only the upgrade authority can propose a transfer");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    // After an accepted transfer the new authority holds it directly
+    if deployed_authority != Some(record_pda) {
+        if deployed_authority != Some(*authority.key) {
+            msg!("Only the upgrade authority can propose a transfer");
+            return Err(ReviewError::IncorrectAccount.into());
+        }
+
+        invoke(
+            &set_upgrade_authority(
+                program_id, 
+                authority.key, 
+                Some(&record_pda)
+            ),
+            &[
+                program_data.clone(),
+                authority.clone(),
+                pda_authority.clone(),
+                loader_program.clone(),
+            ],
+        )?;
+    }
+
+    record.pending_authority = Some(new_authority);
+    record.serialize(&mut &mut pda_authority.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Completes a proposed handover: the PDA signs the upgrade authority over to
+// the pending authority, which becomes the recorded authority.
+pub fn accept_authority_transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    msg!("Accepting upgrade authority transfer...");
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let new_authority = next_account_info(account_info_iter)?;
+    let pda_authority = next_account_info(account_info_iter)?;
+    let program_data = next_account_info(account_info_iter)?;
+    let loader_program = next_account_info(account_info_iter)?;
+
+    if !new_authority.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (record_pda, record_bump) = Pubkey::find_program_address(&[b"authority"], program_id);
+    if record_pda != *pda_authority.key {
+        return Err(invalid_pda(1, &record_pda, pda_authority.key));
+    }
+
+    if bpf_loader_upgradeable::ID != *loader_program.key {
+        msg!("Incorrect loader program");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let mut record = load_authority_record(program_id, pda_authority)?;
+
+    if record.pending_authority != Some(*new_authority.key) {
+        msg!("Only the pending authority can accept the transfer");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if program_upgrade_authority(program_id, program_data)? != Some(record_pda) {
+        msg!("Authority PDA does not hold the upgrade authority");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    invoke_signed(
+        &set_upgrade_authority(
+            program_id, 
+            pda_authority.key, 
+            Some(new_authority.key)
+        ),
+        &[
+            program_data.clone(),
+            pda_authority.clone(),
+            new_authority.clone(),
+            loader_program.clone(),
+        ],
+        &[&[b"authority", &[record_bump]]],
+    )?;
+
+    record.upgrade_authority = *new_authority.key;
+    record.pending_authority = None;
+    record.serialize(&mut &mut pda_authority.data.borrow_mut()[..])?;
+
+    msg!("Upgrade authority is now {}", new_authority.key);
+
+    Ok(())
+}
+
+fn load_authority_record(
+    program_id: &Pubkey,
+    pda_authority: &AccountInfo,
+) -> Result<ProgramAuthorityRecord, ProgramError> {
+    if pda_authority.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let record: ProgramAuthorityRecord = try_from_slice_unchecked(&pda_authority.data.borrow())?;
+    if !record.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(record)
+}
+
+// Upgrade authority stored in the program's ProgramData account, `None` once
+// the program is immutable. The metadata is bincode: a u32 `ProgramData` tag,
+// the deploy slot, then an optional authority.
+fn program_upgrade_authority(
+    program_id: &Pubkey,
+    program_data: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    if get_program_data_address(program_id) != *program_data.key {
+        msg!("Incorrect program data account");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if *program_data.owner != bpf_loader_upgradeable::ID {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    const PROGRAM_DATA_TAG: u32 = 3;
+
+    let data = program_data.data.borrow();
+    let metadata = data
+        .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    if metadata[..4] != PROGRAM_DATA_TAG.to_le_bytes() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    match metadata[12] {
+        0 => Ok(None),
+        1 => Ok(Some(Pubkey::try_from(&metadata[13..45]).unwrap())),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+// Counts each whitespace-separated, lowercased word of the review title.
+// Once the cloud holds `MAX_ENTRIES` tags, new ones are dropped. A
+// `TagCloudRecord` is left per review so it can only be counted once.
+pub fn update_tag_cloud(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+) -> ProgramResult {
+    msg!("Updating tag cloud with {}", title);
+
+    require_accounts(accounts, 5)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let pda_tag_cloud = next_account_info(account_info_iter)?;
+    let pda_record = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let account_data = load_owned_review(program_id, reviewer, pda_account, &title)?;
+
+    let (record, record_bump) = Pubkey::find_program_address(
+        &[pda_account.key.as_ref(), b"tagcloud"], 
+        program_id
+    );
+    if record != *pda_record.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if !pda_record.data_is_empty() {
+        msg!("Review has already been counted");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(TagCloudRecord::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            reviewer.key, 
+            pda_record.key, 
+            rent_lamports, 
+            TagCloudRecord::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            reviewer.clone(),
+            pda_record.clone(),
+            system_program.clone(),
+        ], 
+        &[&[pda_account.key.as_ref(), b"tagcloud", &[record_bump]]],
+    )?;
+
+    let record_data = TagCloudRecord {
+        discriminator: TagCloudRecord::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        review: *pda_account.key,
+    };
+
+    record_data.serialize(&mut &mut pda_record.data.borrow_mut()[..])?;
+
+    let mut tag_cloud = load_tag_cloud(program_id, reviewer, pda_tag_cloud, system_program)?;
+
+    for tag in account_data.title.split_whitespace().map(str::to_lowercase) {
+        if let Some((_, count)) = tag_cloud.entries.iter_mut().find(|(existing, _)| *existing == tag) {
+            *count = checked_add(*count, 1)?;
+        } else if tag.len() > TagCloud::MAX_TAG_LEN {
+            msg!("Skipping tag longer than {} bytes", TagCloud::MAX_TAG_LEN);
+        } else if tag_cloud.entries.len() >= TagCloud::MAX_ENTRIES {
+            msg!("Tag cloud is full, skipping {}", tag);
+        } else {
+            tag_cloud.entries.push((tag, 1));
+        }
+    }
+
+    tag_cloud.serialize(&mut &mut pda_tag_cloud.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+fn load_tag_cloud<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda_tag_cloud: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<TagCloud, ProgramError> {
+    let (tag_cloud, tag_cloud_bump) = Pubkey::find_program_address(&[b"tagcloud"], program_id);
+    if tag_cloud != *pda_tag_cloud.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_tag_cloud.data_is_empty() {
+        let rent_lamports = get_rent()?.minimum_balance(TagCloud::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key, 
+                pda_tag_cloud.key, 
+                rent_lamports, 
+                TagCloud::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                payer.clone(),
+                pda_tag_cloud.clone(),
+                system_program.clone(),
+            ], 
+            &[&[b"tagcloud", &[tag_cloud_bump]]],
+        )?;
+        msg!("Tag cloud created");
+
+        return Ok(TagCloud {
+            discriminator: TagCloud::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            entries: Vec::new(),
+        });
+    }
+
+    if pda_tag_cloud.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let tag_cloud_data: TagCloud = try_from_slice_unchecked(&pda_tag_cloud.data.borrow())?;
+    if !tag_cloud_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(tag_cloud_data)
+}
+
+// Drains `account` into `destination` and zeroes its data so a later
+// instruction in the same transaction cannot read stale state from it
+fn close_account<'a>(account: &AccountInfo<'a>, destination: &AccountInfo<'a>) -> ProgramResult {
+    let lamports = account.lamports();
+
+    **destination.try_borrow_mut_lamports()? = checked_add(destination.lamports(), lamports)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.data.borrow_mut().fill(0);
+    account.resize(0)?;
+    account.assign(&system_program::ID);
+
+    Ok(())
+}
+
+pub fn view_audit_log(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+) -> ProgramResult {
+    msg!("Viewing audit log...");
+
+    require_accounts(accounts, 3)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[reviewer.key.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    if pda != *pda_account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let (audit, _audit_bump) = Pubkey::find_program_address(
+        &[pda_account.key.as_ref(), b"audit"], 
+        program_id
+    );
+    if audit != *pda_audit.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_audit.data_is_empty() {
+        msg!("Review has not been updated yet");
+        return Ok(());
+    }
+
+    if pda_audit.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let audit_data: AuditLog = try_from_slice_unchecked(&pda_audit.data.borrow())?;
+
+    for (index, entry) in audit_data.entries.iter().enumerate() {
+        msg!(
+            "AUDIT:{}:{}:{}:{}",
+            index,
+            entry.changed_at,
+            entry.rating,
+            Hash::new_from_array(entry.description_hash),
+        );
+    }
+
+    Ok(())
+}
+
+pub fn create_series(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+) -> ProgramResult {
+    msg!("Creating review series...");
+    msg!("Name: {}", name);
+
+    require_accounts(accounts, 3)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let owner = next_account_info(account_info_iter)?;
+    let pda_series = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !owner.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let name_hash = hash(name.as_bytes()).to_bytes();
+
+    let (pda, bump_seed) = Pubkey::find_program_address(
+        &[owner.key.as_ref(), b"series", name_hash.as_ref()], 
+        program_id
+    );
+    if pda != *pda_series.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(ReviewSeries::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            owner.key, 
+            pda_series.key, 
+            rent_lamports, 
+            ReviewSeries::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            owner.clone(),
+            pda_series.clone(),
+            system_program.clone(),
+        ], 
+        &[&[owner.key.as_ref(), b"series", name_hash.as_ref(), &[bump_seed]]],
+    )?;
+
+    let series = ReviewSeries {
+        discriminator: ReviewSeries::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        owner: *owner.key,
+        name,
+        parts: Vec::new(),
+        total_parts: 0,
+        is_finalized: false,
+    };
+
+    series.serialize(&mut &mut pda_series.data.borrow_mut()[..])?;
+    msg!("Series created: {}", pda_series.key);
+
+    Ok(())
+}
+
+pub fn add_to_series(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    series_name: String,
+    review_title: String,
+) -> ProgramResult {
+    msg!("Adding review to series...");
+    msg!("Series: {}", series_name);
+    msg!("Review: {}", review_title);
+
+    require_accounts(accounts, 3)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let owner = next_account_info(account_info_iter)?;
+    let pda_series = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+
+    let mut series = load_series(program_id, owner, pda_series, &series_name)?;
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[owner.key.as_ref(), MovieAccountState::title_seed(&review_title).as_bytes()], 
+        program_id
+    );
+    if review != *pda_review.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_review.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if !review_data.is_initialized() {
+        msg!("Review is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if series.parts.contains(pda_review.key) {
+        msg!("Review is already part of the series");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if series.parts.len() >= ReviewSeries::MAX_PARTS {
+        msg!("Series cannot hold more than {} parts", ReviewSeries::MAX_PARTS);
+        return Err(ReviewError::SeriesFull.into());
+    }
+
+    series.parts.push(*pda_review.key);
+    msg!("Series parts: {}", series.parts.len());
+
+    series.serialize(&mut &mut pda_series.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn finalize_series(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    series_name: String,
+) -> ProgramResult {
+    msg!("Finalizing review series...");
+    msg!("Series: {}", series_name);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let owner = next_account_info(account_info_iter)?;
+    let pda_series = next_account_info(account_info_iter)?;
+
+    let mut series = load_series(program_id, owner, pda_series, &series_name)?;
+
+    series.total_parts = checked_cast(series.parts.len())?;
+    series.is_finalized = true;
+    msg!("Series finalized with {} parts", series.total_parts);
+
+    series.serialize(&mut &mut pda_series.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Loads a series the signer owns and can still modify
+fn load_series(
+    program_id: &Pubkey,
+    owner: &AccountInfo,
+    pda_series: &AccountInfo,
+    series_name: &str,
+) -> Result<ReviewSeries, ProgramError> {
+    if !owner.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if pda_series.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[owner.key.as_ref(), b"series", hash(series_name.as_bytes()).as_ref()], 
+        program_id
+    );
+    if pda != *pda_series.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let series: ReviewSeries = try_from_slice_unchecked(&pda_series.data.borrow())?;
+    if !series.is_initialized() {
+        msg!("Series is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if series.owner != *owner.key {
+        msg!("Series belongs to another owner");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if series.is_finalized {
+        msg!("Series is finalized");
+        return Err(ReviewError::SeriesFinalized.into());
+    }
+
+    Ok(series)
+}
+
+pub fn create_collection(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    description: String,
+) -> ProgramResult {
+    msg!("Creating review collection...");
+    msg!("Name: {}", name);
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let owner = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let pda_collection = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !owner.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut profile = load_reviewer_profile(program_id, owner.key, owner, pda_profile, system_program)?;
+    let collection_id = profile.collection_count;
+
+    let (pda, bump_seed) = Pubkey::find_program_address(
+        &[owner.key.as_ref(), b"collection", &collection_id.to_le_bytes()], 
+        program_id
+    );
+    if pda != *pda_collection.key {
+        msg!("Collection {} expected at {}", collection_id, pda);
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(ReviewCollection::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            owner.key, 
+            pda_collection.key, 
+            rent_lamports, 
+            ReviewCollection::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            owner.clone(),
+            pda_collection.clone(),
+            system_program.clone(),
+        ], 
+        &[&[owner.key.as_ref(), b"collection", &collection_id.to_le_bytes(), &[bump_seed]]],
+    )?;
+
+    let collection = ReviewCollection {
+        discriminator: ReviewCollection::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        owner: *owner.key,
+        collection_id,
+        name,
+        description,
+        review_count: 0,
+        review_pdas: Vec::new(),
+    };
+
+    collection.serialize(&mut &mut pda_collection.data.borrow_mut()[..])?;
+    msg!("Collection {} created: {}", collection_id, pda_collection.key);
+
+    profile.collection_count = checked_add(profile.collection_count, 1)?;
+    profile.serialize(&mut &mut pda_profile.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn add_review_to_collection(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    collection_id: u64,
+    review_title: String,
+) -> ProgramResult {
+    msg!("Adding review to collection {}...", collection_id);
+    msg!("Review: {}", review_title);
+
+    require_accounts(accounts, 3)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let owner = next_account_info(account_info_iter)?;
+    let pda_collection = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+
+    let mut collection = load_collection(program_id, owner, pda_collection, collection_id)?;
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[owner.key.as_ref(), MovieAccountState::title_seed(&review_title).as_bytes()], 
+        program_id
+    );
+    if review != *pda_review.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_review.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if !review_data.is_initialized() {
+        msg!("Review is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if collection.review_pdas.contains(pda_review.key) {
+        msg!("Review is already in the collection");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if collection.review_pdas.len() >= ReviewCollection::MAX_REVIEWS {
+        msg!("Collection cannot hold more than {} reviews", ReviewCollection::MAX_REVIEWS);
+        return Err(ReviewError::CollectionFull.into());
+    }
+
+    collection.review_pdas.push(*pda_review.key);
+    collection.review_count = checked_cast(collection.review_pdas.len())?;
+    msg!("Collection reviews: {}", collection.review_count);
+
+    collection.serialize(&mut &mut pda_collection.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Takes the review by title only, so reviews that were since deleted or
+// renamed can still be removed
+pub fn remove_review_from_collection(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    collection_id: u64,
+    review_title: String,
+) -> ProgramResult {
+    msg!("Removing review from collection {}...", collection_id);
+    msg!("Review: {}", review_title);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let owner = next_account_info(account_info_iter)?;
+    let pda_collection = next_account_info(account_info_iter)?;
+
+    let mut collection = load_collection(program_id, owner, pda_collection, collection_id)?;
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[owner.key.as_ref(), MovieAccountState::title_seed(&review_title).as_bytes()], 
+        program_id
+    );
+
+    let Some(position) = collection.review_pdas.iter().position(|pda| *pda == review) else {
+        msg!("Review is not in the collection");
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    collection.review_pdas.remove(position);
+    collection.review_count = checked_cast(collection.review_pdas.len())?;
+    msg!("Collection reviews: {}", collection.review_count);
+
+    collection.serialize(&mut &mut pda_collection.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Loads a collection the signer owns
+fn load_collection(
+    program_id: &Pubkey,
+    owner: &AccountInfo,
+    pda_collection: &AccountInfo,
+    collection_id: u64,
+) -> Result<ReviewCollection, ProgramError> {
+    if !owner.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if pda_collection.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[owner.key.as_ref(), b"collection", &collection_id.to_le_bytes()], 
+        program_id
+    );
+    if pda != *pda_collection.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let collection: ReviewCollection = try_from_slice_unchecked(&pda_collection.data.borrow())?;
+    if !collection.is_initialized() {
+        msg!("Collection is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if collection.owner != *owner.key {
+        msg!("Collection belongs to another owner");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    Ok(collection)
+}
+
+// Sends `message` from the signer to `recipient`. The sender's counter for
+// this recipient is created with the first message.
+pub fn send_direct_message(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    recipient: Pubkey,
+    message: String,
+) -> ProgramResult {
+    msg!("Sending direct message...");
+    msg!("Recipient: {}", recipient);
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let sender = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let pda_message = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !sender.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (counter, counter_bump) = Pubkey::find_program_address(
+        &[sender.key.as_ref(), recipient.as_ref(), b"dm_count"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let mut counter_data = if pda_counter.data_is_empty() {
+        let rent_lamports = get_rent()?.minimum_balance(DirectMessageCounter::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                sender.key, 
+                pda_counter.key, 
+                rent_lamports, 
+                DirectMessageCounter::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                sender.clone(),
+                pda_counter.clone(),
+                system_program.clone(),
+            ], 
+            &[&[sender.key.as_ref(), recipient.as_ref(), b"dm_count", &[counter_bump]]],
+        )?;
+
+        DirectMessageCounter {
+            discriminator: DirectMessageCounter::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            count: 0,
+        }
+    } else {
+        if pda_counter.owner != program_id {
+            return Err(ProgramError::InvalidAccountOwner)
+        }
+
+        let counter_data: DirectMessageCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+        if !counter_data.is_initialized() {
+            msg!("Account is not initialized");
+            return Err(ReviewError::UninitializedAccount.into());
+        }
+
+        counter_data
+    };
+
+    let message_index = counter_data.count;
+
+    let (pda, bump_seed) = Pubkey::find_program_address(
+        &[sender.key.as_ref(), recipient.as_ref(), &message_index.to_le_bytes(), b"dm"], 
+        program_id
+    );
+    if pda != *pda_message.key {
+        msg!("Message {} expected at {}", message_index, pda);
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(DirectMessage::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            sender.key, 
+            pda_message.key, 
+            rent_lamports, 
+            DirectMessage::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            sender.clone(),
+            pda_message.clone(),
+            system_program.clone(),
+        ], 
+        &[&[sender.key.as_ref(), recipient.as_ref(), &message_index.to_le_bytes(), b"dm", &[bump_seed]]],
+    )?;
+
+    let message_data = DirectMessage {
+        discriminator: DirectMessage::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        sender: *sender.key,
+        recipient,
+        message_index,
+        content: message,
+        read: false,
+        created_at: get_clock()?.unix_timestamp,
+    };
+
+    message_data.serialize(&mut &mut pda_message.data.borrow_mut()[..])?;
+
+    counter_data.count = checked_add(counter_data.count, 1)?;
+
+    counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Only the recipient can mark a message read; marking it again is a no-op
+pub fn mark_message_read(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    sender: Pubkey,
+    message_index: u64,
+) -> ProgramResult {
+    msg!("Marking message {} read", message_index);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let recipient = next_account_info(account_info_iter)?;
+    let pda_message = next_account_info(account_info_iter)?;
+
+    if !recipient.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[sender.as_ref(), recipient.key.as_ref(), &message_index.to_le_bytes(), b"dm"], 
+        program_id
+    );
+    if pda != *pda_message.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_message.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut message_data: DirectMessage = try_from_slice_unchecked(&pda_message.data.borrow())?;
+    if !message_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if message_data.read {
+        msg!("Message already read");
+        return Ok(());
+    }
+
+    message_data.read = true;
+
+    message_data.serialize(&mut &mut pda_message.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn react_to_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    reviewer: Pubkey,
+    reaction: u8,
+) -> ProgramResult {
+    msg!("Reacting to review...");
+    msg!("Title: {}", title);
+    msg!("Reaction: {}", reaction);
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reactor = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_reaction = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !reactor.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if pda_review.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[reviewer.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    if review != *pda_review.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let mut review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if !review_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let (record, record_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), reactor.key.as_ref()], 
+        program_id
+    );
+    if record != *pda_reaction.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_reaction.data_is_empty() {
+        let rent_lamports = get_rent()?.minimum_balance(ReactionRecord::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                reactor.key, 
+                pda_reaction.key, 
+                rent_lamports, 
+                ReactionRecord::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                reactor.clone(),
+                pda_reaction.clone(),
+                system_program.clone(),
+            ], 
+            &[&[pda_review.key.as_ref(), reactor.key.as_ref(), &[record_bump]]],
+        )?;
+    } else if pda_reaction.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut record_data: ReactionRecord = try_from_slice_unchecked(&pda_reaction.data.borrow())?;
+
+    if record_data.is_initialized() {
+        if record_data.reaction == reaction {
+            msg!("Reaction unchanged");
+            return Ok(());
+        }
+
+        let previous = &mut review_data.reactions[record_data.reaction as usize];
+        *previous = previous.saturating_sub(1);
+    } else {
+        record_data.discriminator = ReactionRecord::DISCRIMINATOR.to_string();
+        record_data.is_initialized = true;
+        record_data.review = *pda_review.key;
+        record_data.reactor = *reactor.key;
+    }
+
+    let current = &mut review_data.reactions[reaction as usize];
+    *current = current.saturating_add(1);
+    record_data.reaction = reaction;
+
+    msg!("Reactions: {:?}", review_data.reactions);
+
+    review_data.serialize(&mut &mut pda_review.data.borrow_mut()[..])?;
+    record_data.serialize(&mut &mut pda_reaction.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn nominate_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reviewer: Pubkey,
+    title: String,
+    category: String,
+) -> ProgramResult {
+    msg!("Nominating review...");
+    msg!("Title: {}", title);
+    msg!("Category: {}", category);
+
+    require_accounts(accounts, 5)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let nominator = next_account_info(account_info_iter)?;
+    let nominator_review = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_nomination = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    verify_reviewer(program_id, nominator, nominator_review)?;
+
+    if pda_review.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[reviewer.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    if review != *pda_review.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if !review_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let year = year_from_unix_timestamp(get_clock()?.unix_timestamp);
+
+    let (nomination, nomination_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"nominate", &year.to_le_bytes()], 
+        program_id
+    );
+    if nomination != *pda_nomination.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if !pda_nomination.data_is_empty() {
+        msg!("Review is already nominated for {}", year);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(AwardNomination::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            nominator.key, 
+            pda_nomination.key, 
+            rent_lamports, 
+            AwardNomination::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            nominator.clone(),
+            pda_nomination.clone(),
+            system_program.clone(),
+        ], 
+        &[&[pda_review.key.as_ref(), b"nominate", &year.to_le_bytes(), &[nomination_bump]]],
+    )?;
+
+    let nomination_data = AwardNomination {
+        discriminator: AwardNomination::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        review: *pda_review.key,
+        year,
+        nominated_by: *nominator.key,
+        category,
+        votes: 0,
+        is_winner: false,
+    };
+
+    nomination_data.serialize(&mut &mut pda_nomination.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn vote_for_nomination(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reviewer: Pubkey,
+    title: String,
+    year: u16,
+) -> ProgramResult {
+    msg!("Voting for nomination...");
+    msg!("Title: {}", title);
+    msg!("Year: {}", year);
+
+    require_accounts(accounts, 5)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let voter = next_account_info(account_info_iter)?;
+    let voter_review = next_account_info(account_info_iter)?;
+    let pda_nomination = next_account_info(account_info_iter)?;
+    let pda_vote = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    verify_reviewer(program_id, voter, voter_review)?;
+
+    if pda_nomination.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[reviewer.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    let (nomination, _nomination_bump) = Pubkey::find_program_address(
+        &[review.as_ref(), b"nominate", &year.to_le_bytes()], 
+        program_id
+    );
+    if nomination != *pda_nomination.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let mut nomination_data: AwardNomination = try_from_slice_unchecked(&pda_nomination.data.borrow())?;
+    if !nomination_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if year_from_unix_timestamp(get_clock()?.unix_timestamp) != nomination_data.year {
+        msg!("Voting for {} has closed", nomination_data.year);
+        return Err(ReviewError::VotingClosed.into());
+    }
+
+    let (vote, vote_bump) = Pubkey::find_program_address(
+        &[pda_nomination.key.as_ref(), voter.key.as_ref()], 
+        program_id
+    );
+    if vote != *pda_vote.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if !pda_vote.data_is_empty() {
+        msg!("Voter has already voted for this nomination");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(NominationVote::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            voter.key, 
+            pda_vote.key, 
+            rent_lamports, 
+            NominationVote::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            voter.clone(),
+            pda_vote.clone(),
+            system_program.clone(),
+        ], 
+        &[&[pda_nomination.key.as_ref(), voter.key.as_ref(), &[vote_bump]]],
+    )?;
+
+    let vote_data = NominationVote {
+        discriminator: NominationVote::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        nomination: *pda_nomination.key,
+        voter: *voter.key,
+    };
+
+    nomination_data.votes = nomination_data.votes.saturating_add(1);
+
+    msg!("Votes: {}", nomination_data.votes);
+
+    vote_data.serialize(&mut &mut pda_vote.data.borrow_mut()[..])?;
+    nomination_data.serialize(&mut &mut pda_nomination.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// The nominations competing for the award are passed as the remaining
+// accounts; the one with the most votes wins, earliest on a tie
+pub fn declare_award_winner(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    category: String,
+    year: u16,
+) -> ProgramResult {
+    msg!("Declaring award winner...");
+    msg!("Category: {}", category);
+    msg!("Year: {}", year);
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_award = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let nominations = account_info_iter.as_slice();
+
+    let config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can declare award winners");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if year >= year_from_unix_timestamp(get_clock()?.unix_timestamp) {
+        msg!("Voting for {} is still open", year);
+        return Err(ReviewError::VotingStillOpen.into());
+    }
+
+    if nominations.is_empty() {
+        msg!("At least one nomination must be passed");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let (award, award_bump) = Pubkey::find_program_address(
+        &[b"award", &year.to_le_bytes(), category.as_bytes()], 
+        program_id
+    );
+    if award != *pda_award.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if !pda_award.data_is_empty() {
+        msg!("Award has already been declared");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let mut winner: Option<(&AccountInfo, AwardNomination)> = None;
+
+    for pda_nomination in nominations {
+        if pda_nomination.owner != program_id {
+            return Err(ProgramError::InvalidAccountOwner)
+        }
+
+        let nomination_data: AwardNomination = try_from_slice_unchecked(&pda_nomination.data.borrow())?;
+        if !nomination_data.is_initialized() {
+            msg!("Account is not initialized");
+            return Err(ReviewError::UninitializedAccount.into());
+        }
+
+        let (nomination, _nomination_bump) = Pubkey::find_program_address(
+            &[nomination_data.review.as_ref(), b"nominate", &nomination_data.year.to_le_bytes()], 
+            program_id
+        );
+        if nomination != *pda_nomination.key {
+            msg!("Invalid seeds for PDA");
+            return Err(ReviewError::InvalidPDA.into());
+        }
+
+        if nomination_data.category != category || nomination_data.year != year {
+            msg!("Nomination {} is not for this award", pda_nomination.key);
+            return Err(ReviewError::IncorrectAccount.into());
+        }
+
+        let leads = match &winner {
+            Some((_, leader)) => nomination_data.votes > leader.votes,
+            None => true,
+        };
+
+        if leads {
+            winner = Some((pda_nomination, nomination_data));
+        }
+    }
+
+    let (pda_winner, mut winner_data) = winner.unwrap();
+
+    msg!("Winner: {} with {} votes", winner_data.review, winner_data.votes);
+
+    let rent_lamports = get_rent()?.minimum_balance(Award::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            admin.key, 
+            pda_award.key, 
+            rent_lamports, 
+            Award::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            admin.clone(),
+            pda_award.clone(),
+            system_program.clone(),
+        ], 
+        &[&[b"award", &year.to_le_bytes(), category.as_bytes(), &[award_bump]]],
+    )?;
+
+    let award_data = Award {
+        discriminator: Award::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        category,
+        year,
+        nomination: *pda_winner.key,
+        votes: winner_data.votes,
+    };
+
+    winner_data.is_winner = true;
+
+    award_data.serialize(&mut &mut pda_award.data.borrow_mut()[..])?;
+    winner_data.serialize(&mut &mut pda_winner.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn set_comment_moderation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    mode: u8,
+) -> ProgramResult {
+    msg!("Setting comment moderation to {}", mode);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+
+    let mut account_data = load_owned_review(program_id, reviewer, pda_account, &title)?;
+
+    account_data.comment_moderation = mode;
+    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Sponsorship is a one-way disclosure: there is no instruction to clear it
+pub fn mark_sponsored(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+) -> ProgramResult {
+    msg!("Marking review as sponsored...");
+    msg!("Title: {}", title);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+
+    let mut account_data = load_owned_review(program_id, reviewer, pda_account, &title)?;
+
+    if account_data.is_sponsored {
+        msg!("Review is already marked as sponsored");
+        return Err(ReviewError::AlreadySponsored.into());
+    }
+
+    account_data.is_sponsored = true;
+    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Holds a comment on a `COMMENTS_REQUIRE_APPROVAL` review in the commenter's
+// `PendingComment`, passed in place of the comment account. It gets an index,
+// and counts towards the stats, only once `ApprovePendingComment` stores it;
+// comments that go through approval are not rewarded.
+fn submit_pending_comment<'a>(
+    program_id: &Pubkey,
+    commenter: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    pda_review: &AccountInfo<'a>,
+    pda_pending: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    comment: &str,
+) -> ProgramResult {
+    let (pending, pending_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), commenter.key.as_ref(), b"pending"], 
+        program_id
+    );
+    if pending != *pda_pending.key {
+        return Err(invalid_pda(3, &pending, pda_pending.key));
+    }
+
+    if !pda_pending.data_is_empty() {
+        msg!("A comment from this commenter is already awaiting approval");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let account_len = PendingComment::get_account_size(comment.to_string())?;
+    let rent_lamports = get_rent()?.minimum_balance(account_len);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key, 
+            pda_pending.key, 
+            rent_lamports, 
+            checked_cast(account_len)?, 
+            program_id
+        ), 
+        &[
+            payer.clone(),
+            pda_pending.clone(),
+            system_program.clone(),
+        ], 
+        &[&[pda_review.key.as_ref(), commenter.key.as_ref(), b"pending", &[pending_bump]]],
+    )?;
+
+    let pending_data = PendingComment {
+        discriminator: PendingComment::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        review: *pda_review.key,
+        submitter: *commenter.key,
+        comment: comment.to_string(),
+        created_at: get_clock()?.unix_timestamp,
+    };
+
+    pending_data.serialize(&mut &mut pda_pending.data.borrow_mut()[..])?;
+    msg!("Comment awaiting approval: {}", pda_pending.key);
+
+    Ok(())
+}
+
+// Stores `submitter`'s pending comment at the next comment index, paid for by
+// the reviewer, and refunds the pending account's rent to `submitter`
+pub fn approve_pending_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    submitter: Pubkey,
+    title: String,
+) -> ProgramResult {
+    msg!("Approving pending comment from {}", submitter);
+
+    require_accounts(accounts, 8)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let pda_pending = next_account_info(account_info_iter)?;
+    let pda_comment = next_account_info(account_info_iter)?;
+    let submitter_account = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    load_owned_review(program_id, reviewer, pda_review, &title)?;
+
+    if *submitter_account.key != submitter {
+        msg!("Submitter account does not match {}", submitter);
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let pending_data = load_pending_comment(program_id, pda_review, pda_pending, submitter_account)?;
+
+    let (counter, _counter_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"comment"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let mut counter_data: MovieCommentCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+    if !counter_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let (comment, comment_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), counter_data.counter.to_be_bytes().as_ref()], 
+        program_id
+    );
+    if comment != *pda_comment.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let account_len = MovieComment::get_account_size(pending_data.comment.clone())?;
+    let rent_lamports = get_rent()?.minimum_balance(account_len);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            reviewer.key, 
+            pda_comment.key, 
+            rent_lamports, 
+            checked_cast(account_len)?, 
+            program_id
+        ), 
+        &[
+            reviewer.clone(),
+            pda_comment.clone(),
+            system_program.clone(),
+        ], 
+        &[&[
+            pda_review.key.as_ref(),
+            counter_data.counter.to_be_bytes().as_ref(),
+            &[comment_bump],
+        ]]
+    )?;
+
+    let comment_data = MovieComment {
+        discriminator: MovieComment::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        review: *pda_review.key,
+        commenter: submitter,
+        comment: pending_data.comment,
+        count: counter_data.counter,
+        created_at: pending_data.created_at,
+    };
+
+    comment_data.serialize(&mut &mut pda_comment.data.borrow_mut()[..])?;
+    msg!("Comment {} approved: {}", counter_data.counter, pda_comment.key);
+
+    counter_data.counter = checked_add(counter_data.counter, 1)?;
+    counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
+
+    let mut stats = load_global_stats(program_id, reviewer, pda_stats, system_program)?;
+    stats.total_comments = checked_add(stats.total_comments, 1)?;
+    stats.serialize(&mut &mut pda_stats.data.borrow_mut()[..])?;
+
+    close_account(pda_pending, submitter_account)
+}
+
+// Drops `submitter`'s pending comment without storing it. The reason is only
+// logged, and the rent goes back to `submitter` rather than the reviewer.
+pub fn reject_pending_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    submitter: Pubkey,
+    title: String,
+    reason: String,
+) -> ProgramResult {
+    msg!("Rejecting pending comment from {}", submitter);
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_pending = next_account_info(account_info_iter)?;
+    let submitter_account = next_account_info(account_info_iter)?;
+
+    load_owned_review(program_id, reviewer, pda_review, &title)?;
+
+    if *submitter_account.key != submitter {
+        msg!("Submitter account does not match {}", submitter);
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    load_pending_comment(program_id, pda_review, pda_pending, submitter_account)?;
+
+    if !reason.is_empty() {
+        msg!("Rejection reason: {}", reason);
+    }
+
+    close_account(pda_pending, submitter_account)
+}
+
+// Loads `submitter`'s pending comment on `pda_review`; the review itself is
+// checked by the caller
+fn load_pending_comment(
+    program_id: &Pubkey,
+    pda_review: &AccountInfo,
+    pda_pending: &AccountInfo,
+    submitter: &AccountInfo,
+) -> Result<PendingComment, ProgramError> {
+    let (pending, _pending_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), submitter.key.as_ref(), b"pending"], 
+        program_id
+    );
+    if pending != *pda_pending.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_pending.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let pending_data: PendingComment = try_from_slice_unchecked(&pda_pending.data.borrow())?;
+    if !pending_data.is_initialized() {
+        msg!("No comment from {} is awaiting approval", submitter.key);
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(pending_data)
+}
+
+pub fn approve_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    comment_index: u64,
+) -> ProgramResult {
+    msg!("Approving comment {}", comment_index);
+
+    require_accounts(accounts, 5)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let pda_comment = next_account_info(account_info_iter)?;
+    let pda_approval = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    load_owned_review(program_id, reviewer, pda_account, &title)?;
+
+    verify_comment(program_id, pda_account.key, comment_index, pda_comment)?;
+
+    let (approval, approval_bump) = Pubkey::find_program_address(
+        &[pda_comment.key.as_ref(), b"approval"], 
+        program_id
+    );
+    if approval != *pda_approval.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if !pda_approval.data_is_empty() {
+        msg!("Comment already approved");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let rent_lamports = get_rent()?.minimum_balance(CommentApproval::LEN);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            reviewer.key, 
+            pda_approval.key, 
+            rent_lamports, 
+            CommentApproval::LEN.try_into().unwrap(), 
+            program_id
+        ), 
+        &[
+            reviewer.clone(),
+            pda_approval.clone(),
+            system_program.clone(),
+        ], 
+        &[&[pda_comment.key.as_ref(), b"approval", &[approval_bump]]],
+    )?;
+
+    let approval_data = CommentApproval {
+        discriminator: CommentApproval::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        comment: *pda_comment.key,
+        is_approved: true,
+    };
+
+    approval_data.serialize(&mut &mut pda_approval.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Only the latest text is stored; the previous one is logged with a
+// `PREVIOUS_COMMENT:` prefix so indexers can rebuild the edit history.
+pub fn update_comment_text(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    comment_index: u64,
+    comment: String,
+) -> ProgramResult {
+    msg!("Updating comment {}", comment_index);
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let commenter = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_comment = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !commenter.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature)
+    }
+
+    let review_data = load_review_by_title(program_id, pda_review, &title)?;
+
+    if review_data.comment_moderation == MovieAccountState::COMMENTS_LOCKED {
+        msg!("Comments are locked on this review");
+        return Err(ReviewError::CommentsLocked.into());
+    }
+
+    let mut comment_data = verify_comment(program_id, pda_review.key, comment_index, pda_comment)?;
+
+    if comment_data.commenter != *commenter.key {
+        msg!("Only the commenter can update a comment");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let account_len = MovieComment::get_account_size(comment.clone())?;
+
+    if pda_comment.data_len() < account_len {
+        let rent_lamports = get_rent()?.minimum_balance(account_len);
+
+        if pda_comment.lamports() < rent_lamports {
+            invoke(
+                &system_instruction::transfer(
+                    commenter.key, 
+                    pda_comment.key, 
+                    checked_sub(rent_lamports, pda_comment.lamports())?,
+                ), 
+                &[
+                    commenter.clone(),
+                    pda_comment.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+
+        pda_comment.resize(account_len)?;
+    }
+
+    msg!("PREVIOUS_COMMENT:{}", comment_data.comment);
+
+    comment_data.comment = comment;
+
+    comment_data.serialize(&mut &mut pda_comment.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Admin repair for a drifted comment counter. The review's comments are
+// passed as the remaining accounts and the counter is set to how many of
+// them are initialized comments of this review.
+pub fn sync_counter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+) -> ProgramResult {
+    msg!("Syncing comment counter...");
+    msg!("Title: {}", title);
+
+    require_accounts(accounts, 4)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let admin = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+
+    let comments = account_info_iter.as_slice();
+
+    let config = load_config(program_id, pda_config)?;
+
+    if !admin.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if config.admin != *admin.key {
+        msg!("Only the config admin can sync comment counters");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    if comments.len() > MAX_SYNC_COMMENTS {
+        msg!("At most {} comments can be passed", MAX_SYNC_COMMENTS);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if pda_review.owner != program_id || pda_counter.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if !review_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[review_data.reviewer.as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+        program_id
+    );
+    if review != *pda_review.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let (counter, _counter_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"comment"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let mut counter_data: MovieCommentCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+    if !counter_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let mut counted: Vec<&Pubkey> = Vec::with_capacity(comments.len());
+
+    for pda_comment in comments {
+        if pda_comment.owner != program_id || counted.contains(&pda_comment.key) {
+            continue;
+        }
+
+        let Ok(comment_data) = try_from_slice_unchecked::<MovieComment>(&pda_comment.data.borrow()) else {
+            continue;
+        };
+
+        if !comment_data.is_initialized()
+            || comment_data.discriminator != MovieComment::DISCRIMINATOR
+            || comment_data.review != *pda_review.key
+        {
+            continue;
+        }
+
+        counted.push(pda_comment.key);
+    }
+
+    let count = counted.len() as u64;
+
+    if counter_data.counter != count {
+        msg!("{}: counter was {}, found {} comments", ReviewError::CounterSyncMismatch, counter_data.counter, count);
+    }
+
+    counter_data.counter = count;
+    counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn update_merkle_root(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    root: [u8; 32],
+    num_comments: u64,
+) -> ProgramResult {
+    msg!("Updating comment Merkle root...");
+    msg!("Title: {}", title);
+    msg!("Comments: {}", num_comments);
+
+    require_accounts(accounts, 5)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let authority = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_merkle = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !authority.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let review_data = load_review_by_title(program_id, pda_review, &title)?;
+
+    // Without a config there is no admin, only the reviewer may update
+    let is_admin = !pda_config.data_is_empty()
+        && load_config(program_id, pda_config)?.admin == *authority.key;
+
+    if review_data.reviewer != *authority.key && !is_admin {
+        msg!("Only the reviewer or the config admin can update the Merkle root");
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    let (merkle, merkle_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"merkle"], 
+        program_id
+    );
+    if merkle != *pda_merkle.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_merkle.data_is_empty() {
+        let rent_lamports = get_rent()?.minimum_balance(CommentMerkleRoot::LEN);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                authority.key, 
+                pda_merkle.key, 
+                rent_lamports, 
+                CommentMerkleRoot::LEN.try_into().unwrap(), 
+                program_id
+            ), 
+            &[
+                authority.clone(),
+                pda_merkle.clone(),
+                system_program.clone(),
+            ], 
+            &[&[pda_review.key.as_ref(), b"merkle", &[merkle_bump]]],
+        )?;
+    } else if pda_merkle.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let merkle_data = CommentMerkleRoot {
+        discriminator: CommentMerkleRoot::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        review: *pda_review.key,
+        root,
+        num_comments,
+    };
+
+    merkle_data.serialize(&mut &mut pda_merkle.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+pub fn verify_comment_inclusion(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    comment_pda: Pubkey,
+    proof: Vec<[u8; 32]>,
+) -> ProgramResult {
+    msg!("Verifying comment inclusion...");
+    msg!("Title: {}", title);
+    msg!("Comment: {}", comment_pda);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_merkle = next_account_info(account_info_iter)?;
+
+    load_review_by_title(program_id, pda_review, &title)?;
+
+    let (merkle, _merkle_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"merkle"], 
+        program_id
+    );
+    if merkle != *pda_merkle.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_merkle.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let merkle_data: CommentMerkleRoot = try_from_slice_unchecked(&pda_merkle.data.borrow())?;
+    if !merkle_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if merkle_root_from_proof(&comment_pda, &proof) != merkle_data.root {
+        msg!("Proof does not lead to the stored root");
+        return Err(ReviewError::CommentNotIncluded.into());
+    }
+
+    msg!("Comment is included");
+
+    Ok(())
+}
+
+// Permissionless repair for a counter that fell behind its comments. The
+// remaining accounts are the comment PDAs from the stored counter onwards;
+// the counter moves past every one that is already an initialized comment
+// of this review and stops at the first gap, so it never moves backwards.
+pub fn sync_comment_counter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+) -> ProgramResult {
+    msg!("Syncing comment counter forward...");
+    msg!("Title: {}", title);
+
+    require_accounts(accounts, 2)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+
+    let candidates = account_info_iter.as_slice();
+
+    if candidates.len() > MAX_SYNC_COMMENTS {
+        msg!("At most {} comments can be passed", MAX_SYNC_COMMENTS);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    load_review_by_title(program_id, pda_review, &title)?;
+
+    let (counter, _counter_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"comment"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if pda_counter.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let mut counter_data: MovieCommentCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+    if !counter_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let stored = counter_data.counter;
+
+    for pda_comment in candidates {
+        let (comment, _comment_bump) = Pubkey::find_program_address(
+            &[pda_review.key.as_ref(), counter_data.counter.to_be_bytes().as_ref()], 
+            program_id
+        );
+        if comment != *pda_comment.key {
+            msg!("Expected comment {} at {}", counter_data.counter, comment);
+            return Err(ReviewError::InvalidPDA.into());
+        }
+
+        if verify_comment(program_id, pda_review.key, counter_data.counter, pda_comment).is_err() {
+            break;
+        }
+
+        counter_data.counter = checked_add(counter_data.counter, 1)?;
+    }
+
+    if counter_data.counter == stored {
+        msg!("Counter already in sync at {}", stored);
+        return Ok(());
+    }
+
+    msg!("Counter moved from {} to {}", stored, counter_data.counter);
+
+    counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Repair tooling for the config admin or the review's reviewer: copies
+// comment `old_index` into the PDA for `new_index` and closes the old account,
+// refunding its rent to the authority. `new_index` must be below the counter
+// and not hold an account. The comment's `CommentApproval` is keyed by the
+// old PDA and does not move, so an approved comment must be approved again.
+pub fn compact_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    old_index: u64,
+    new_index: u64,
+) -> ProgramResult {
+    msg!("Moving comment {} to {}", old_index, new_index);
+
+    require_accounts(accounts, 7)?;
+
+    let account_info_iter = &mut accounts.iter();
+
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let old_comment = next_account_info(account_info_iter)?;
+    let new_comment = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    if !authority.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if pda_review.owner != program_id || pda_counter.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if !review_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if review_data.reviewer != *authority.key {
+        let config = load_config(program_id, pda_config)?;
+
+        if config.admin != *authority.key {
+            msg!("Only the config admin or the reviewer can move comments");
+            return Err(ReviewError::IncorrectAccount.into());
+        }
+    }
+
+    let (counter, _counter_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), b"comment"], 
+        program_id
+    );
+    if counter != *pda_counter.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let counter_data: MovieCommentCounter = try_from_slice_unchecked(&pda_counter.data.borrow())?;
+    if !counter_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if new_index >= counter_data.counter {
+        msg!("Index {} is past the comment counter {}", new_index, counter_data.counter);
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut comment_data = verify_comment(program_id, pda_review.key, old_index, old_comment)?;
+
+    let (comment, comment_bump) = Pubkey::find_program_address(
+        &[pda_review.key.as_ref(), new_index.to_be_bytes().as_ref()], 
+        program_id
+    );
+    if comment != *new_comment.key {
+        return Err(invalid_pda(5, &comment, new_comment.key));
+    }
+
+    if !new_comment.data_is_empty() || new_comment.owner == program_id {
+        msg!("Comment index {} is taken", new_index);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let account_len = old_comment.data_len();
+    let rent_lamports = get_rent()?.minimum_balance(account_len);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            authority.key, 
+            new_comment.key, 
+            rent_lamports, 
+            checked_cast(account_len)?, 
+            program_id
+        ), 
+        &[
+            authority.clone(),
+            new_comment.clone(),
+            system_program.clone(),
+        ], 
+        &[&[
+            pda_review.key.as_ref(),
+            new_index.to_be_bytes().as_ref(),
+            &[comment_bump],
+        ]]
+    )?;
+
+    comment_data.count = new_index;
+
+    comment_data.serialize(&mut &mut new_comment.data.borrow_mut()[..])?;
+
+    close_account(old_comment, authority)?;
+
+    Ok(())
+}
+
+// Loads comment `index` of `review`. The account must be the comment PDA
+// derived from both, and the `review` stored in it must be the same key.
+pub fn verify_comment(
+    program_id: &Pubkey,
+    review: &Pubkey,
+    index: u64,
+    account: &AccountInfo,
+) -> Result<MovieComment, ProgramError> {
+    let (comment, _comment_bump) = Pubkey::find_program_address(
+        &[review.as_ref(), index.to_be_bytes().as_ref()], 
+        program_id
+    );
+    if comment != *account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    if account.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let comment_data: MovieComment = try_from_slice_unchecked(&account.data.borrow())?;
+    if !comment_data.is_initialized() {
+        msg!("Comment is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    if comment_data.review != *review {
+        msg!("Comment belongs to review {}", comment_data.review);
+        return Err(ReviewError::IncorrectAccount.into());
+    }
+
+    Ok(comment_data)
+}
+
+// Loads a review whose reviewer is not a signer, checking the PDA against
+// the stored reviewer and `title`
+fn load_review_by_title(
+    program_id: &Pubkey,
+    pda_review: &AccountInfo,
+    title: &str,
+) -> Result<MovieAccountState, ProgramError> {
+    if pda_review.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())?;
+    if !review_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    let (review, _review_bump) = Pubkey::find_program_address(
+        &[review_data.reviewer.as_ref(), MovieAccountState::title_seed(title).as_bytes()], 
+        program_id
+    );
+    if review != *pda_review.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    Ok(review_data)
+}
+
+// Loads a review after checking that `reviewer` signed and owns it
+fn load_owned_review(
+    program_id: &Pubkey,
+    reviewer: &AccountInfo,
+    pda_account: &AccountInfo,
+    title: &str,
+) -> Result<MovieAccountState, ProgramError> {
+    if !reviewer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if pda_account.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let (pda, _bump_seed) = Pubkey::find_program_address(
+        &[reviewer.key.as_ref(), MovieAccountState::title_seed(title).as_bytes()], 
+        program_id
+    );
+    if pda != *pda_account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(ReviewError::InvalidPDA.into());
+    }
+
+    let account_data: MovieAccountState = try_from_slice_unchecked(&pda_account.data.borrow())?;
+    if !account_data.is_initialized() {
+        msg!("Account is not initialized");
+        return Err(ReviewError::UninitializedAccount.into());
+    }
+
+    Ok(account_data)
+}
+
+// Nominating and voting are limited to signers who have written a review
+fn verify_reviewer(
+    program_id: &Pubkey,
+    reviewer: &AccountInfo,
+    pda_review: &AccountInfo,
+) -> ProgramResult {
+    if !reviewer.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if pda_review.owner != program_id {
+        return Err(ProgramError::InvalidAccountOwner)
+    }
+
+    let review_data: MovieAccountState = try_from_slice_unchecked(&pda_review.data.borrow())
+        .map_err(|_| ReviewError::NotVerifiedReviewer)?;
+    if review_data.discriminator != MovieAccountState::DISCRIMINATOR
+        || !review_data.is_initialized()
+        || review_data.reviewer != *reviewer.key
+    {
+        msg!("Signer has not written a review");
+        return Err(ReviewError::NotVerifiedReviewer.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "token-2022"))]
+fn token_account_balance(data: &[u8]) -> Result<u64, ProgramError> {
+    Ok(token::state::Account::unpack(data)?.amount)
+}
+
+#[cfg(feature = "token-2022")]
+fn token_account_balance(data: &[u8]) -> Result<u64, ProgramError> {
+    use token::extension::StateWithExtensions;
+
+    Ok(StateWithExtensions::<token::state::Account>::unpack(data)?.base.amount)
+}
+
+#[cfg(not(feature = "token-2022"))]
+fn mint_authority(data: &[u8]) -> Result<COption<Pubkey>, ProgramError> {
+    Ok(token::state::Mint::unpack(data)?.mint_authority)
+}
+
+#[cfg(feature = "token-2022")]
+fn mint_authority(data: &[u8]) -> Result<COption<Pubkey>, ProgramError> {
+    use token::extension::StateWithExtensions;
+
+    Ok(StateWithExtensions::<token::state::Mint>::unpack(data)?.base.mint_authority)
+}
+
+// Checked at the top of each handler so a short account list fails before
+// any work, rather than at the first missing `next_account_info`
+fn require_accounts(accounts: &[AccountInfo], count: usize) -> ProgramResult {
+    if accounts.len() < count {
+        msg!("Expected at least {} accounts, got {}", count, accounts.len());
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    Ok(())
+}
+
+// `InvalidPDA`, with the expected and actual keys written to return data
+// when the `error-context` feature is enabled
+#[cfg_attr(not(feature = "error-context"), allow(unused_variables))]
+fn invalid_pda(account_index: u8, expected: &Pubkey, actual: &Pubkey) -> ProgramError {
+    msg!("Invalid seeds for PDA");
+
+    #[cfg(feature = "error-context")]
+    solana_program::program::set_return_data(&borsh::to_vec(&ErrorContext {
+        error_code: ReviewError::InvalidPDA as u32,
+        account_index,
+        expected: *expected,
+        actual: *actual,
+    }).unwrap());
+
+    ReviewError::InvalidPDA.into()
+}
+
+fn is_valid_language_code(language_code: &[u8; 2]) -> bool {
+    language_code.iter().all(|c| c.is_ascii_lowercase())
+}
+
+// Folds a proof into a root; sibling pairs are hashed in sorted order so
+// the proof does not need to encode left/right positions
+fn merkle_root_from_proof(comment_pda: &Pubkey, proof: &[[u8; 32]]) -> [u8; 32] {
+    proof.iter().fold(hash(comment_pda.as_ref()).to_bytes(), |node, sibling| {
+        let (left, right) = if node <= *sibling { (&node, sibling) } else { (sibling, &node) };
+        hashv(&[left, right]).to_bytes()
+    })
+}
+
+// Gregorian year of a unix timestamp (days-to-civil conversion)
+fn year_from_unix_timestamp(unix_timestamp: i64) -> u16 {
+    let days = unix_timestamp.div_euclid(86_400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // Years start in March here, so January and February belong to the next one
+    let march_based_month = (5 * day_of_year + 2) / 153;
+
+    (era * 400 + year_of_era + i64::from(march_based_month >= 10)) as u16
+}
+
+fn comment_reward(count: u64) -> u64 {
+    COMMENT_BASE_REWARD
+        .saturating_sub(count.saturating_mul(COMMENT_REWARD_STEP))
+        .max(COMMENT_MIN_REWARD)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::instruction::{AddMovieReviewPayload, CommentPayload, MovieReviewPayload, ReviewLanguagePayload};
+    use crate::test_utils::*;
+
+    use solana_program::{clock::Clock, native_token::sol_to_lamports};
+
+    use solana_program_test::*;
+
+    use test_case::test_case;
+
+    use solana_sdk::{
+        account::Account,
+        signature::{Keypair, Signer},
+        instruction::InstructionError,
+        system_program,
+        transaction::{Transaction, TransactionError},
+    };
+
+    #[cfg(not(feature = "token-2022"))]
+    fn mint_supply(data: &[u8]) -> u64 {
+        token::state::Mint::unpack(data).unwrap().supply
+    }
+
+    #[cfg(feature = "token-2022")]
+    fn mint_supply(data: &[u8]) -> u64 {
+        use token::extension::StateWithExtensions;
+
+        StateWithExtensions::<token::state::Mint>::unpack(data).unwrap().base.supply
+    }
+
+    #[tokio::test]
+    async fn test_initialize_mint_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program",
+            program_id,
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (_mint, _mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let transaction_result = banks_client.process_transaction(transaction).await;
+
+        assert!(transaction_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_movie_review_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+        let title = String::from("Captain America");
+        let rating: u8 = 3;
+        let description =  String::from("Liked the movie");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title, 
+            rating, 
+            description, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
+            Some(&payer.pubkey())
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let transaction_result = banks_client.process_transaction(transaction).await;
+
+        assert!(transaction_result.is_ok());
+
+        // 10 tokens at 9 decimals
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), 10_000_000_000);
+
+        let mint_account = banks_client.get_account(mint).await.unwrap().unwrap();
+        assert_eq!(mint_supply(&mint_account.data), 10_000_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_update_movie_review_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(),
+            &program_id
+        );
+
+        let title = String::from("Captain America");
+        let rating: u8 = 3;
+        let description = String::from("Liked the movie");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            rating, 
+            description, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
+            Some(&payer.pubkey())
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let new_rating: u8 = 2;
+        let new_description =  String::from("Didn't like the movie");
+        
+        let update_movie_review_ix = create_update_movie_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            new_rating, 
+            new_description,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[update_movie_review_ix], 
+            Some(&payer.pubkey())
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let transaction_result = banks_client.process_transaction(transaction).await;
+
+        assert!(transaction_result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_add_comment_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+        let rating: u8 = 3;
+        let description = String::from("Liked the movie");
+        
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            rating, 
+            description, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), 10_000_000_000);
+
+        let comment = String::from("Totally agree!");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+
+        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        
+        let add_comment_ix = create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id,
+            title.clone(),
+            comment, 
+            counter_data.counter, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_comment_ix], 
+            Some(&payer.pubkey())
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let transaction_result = banks_client.process_transaction(transaction).await;
+
+        assert!(transaction_result.is_ok());
+
+        // The first comment on a review earns 5 tokens on top of the review's 10
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), 15_000_000_000);
+
+        let mint_account = banks_client.get_account(mint).await.unwrap().unwrap();
+        assert_eq!(mint_supply(&mint_account.data), 15_000_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_add_comment_from_second_wallet() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        let commenter = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let payer_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let commenter_ata = get_associated_token_address_with_program_id(
+            &commenter.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &commenter.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    payer_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+
+        let add_comment_ix = create_add_comment_on_review_ix(
+            commenter.pubkey(), 
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            String::from("Totally agree!"), 
+            0, 
+            mint, 
+            mint_auth, 
+            commenter_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_comment_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &commenter], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 1);
+
+        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), &0u64.to_be_bytes()], 
+            &program_id
+        );
+
+        let comment_account = banks_client.get_account(comment_pda).await.unwrap().unwrap();
+        let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+        assert_eq!(comment_data.commenter, commenter.pubkey());
+
+        // The commenter is rewarded, not the reviewer
+        let commenter_ata_account = banks_client.get_account(commenter_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&commenter_ata_account.data).unwrap(), sol_to_lamports(5.0));
+
+        let payer_ata_account = banks_client.get_account(payer_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&payer_ata_account.data).unwrap(), sol_to_lamports(10.0));
+    }
+
+    #[tokio::test]
+    async fn test_review_language_filter() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let english_review_ix = create_add_movie_review_with_language_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Captain America"), 
+            3, 
+            String::from("Liked the movie"), 
+            *b"en",
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let french_review_ix = create_add_movie_review_with_language_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Amelie"), 
+            5, 
+            String::from("Un chef-d'oeuvre"), 
+            *b"fr",
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, english_review_ix, french_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (english_pda, _) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Captain America"], 
+            &program_id
+        );
+        let (french_pda, _) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Amelie"], 
+            &program_id
+        );
+
+        let offset = MovieAccountState::LANGUAGE_CODE_OFFSET;
+        let mut accounts = Vec::new();
+        for pda in [english_pda, french_pda] {
+            accounts.push((pda, banks_client.get_account(pda).await.unwrap().unwrap()));
+        }
+
+        // Same comparison a getProgramAccounts memcmp filter performs
+        let filter = |language_code: &[u8; 2]| -> Vec<Pubkey> {
+            accounts.iter()
+                .filter(|(_, account)| &account.data[offset..offset + 2] == language_code)
+                .map(|(pda, _)| *pda)
+                .collect()
+        };
+
+        assert_eq!(filter(b"en"), vec![english_pda]);
+        assert_eq!(filter(b"fr"), vec![french_pda]);
+
+        let french_data: MovieAccountState = try_from_slice_unchecked(&accounts[1].1.data).unwrap();
+        assert_eq!(french_data.language_code, *b"fr");
+        assert_eq!(french_data.title, "Amelie");
+    }
+
+    #[tokio::test]
+    async fn test_change_review_language_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let change_language_ix = create_change_review_language_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            *b"de",
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix, change_language_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+
+        assert_eq!(review_data.language_code, *b"de");
+    }
+
+    #[test]
+    fn test_malformed_payloads_are_rejected() {
+        let program_id = Pubkey::new_unique();
+
+        let empty_title = borsh::to_vec(&(0u8, AddMovieReviewPayload {
+            title: String::new(),
+            rating: 3,
+            description: String::from("Liked the movie"),
+            language_code: *b"en",
+            watched_at: 0,
+            tight_size: false,
+            is_sponsored: false,
+            poster_uri: None,
+            tags: Vec::new(),
+        })).unwrap();
+
+        let long_title = borsh::to_vec(&(0u8, AddMovieReviewPayload {
+            title: "a".repeat(MAX_SEED_LEN + 1),
+            rating: 3,
+            description: String::from("Liked the movie"),
+            language_code: *b"en",
+            watched_at: 0,
+            tight_size: false,
+            is_sponsored: false,
+            poster_uri: None,
+            tags: Vec::new(),
+        })).unwrap();
+
+        let empty_update_title = borsh::to_vec(&(1u8, MovieReviewPayload {
+            title: String::new(),
+            rating: 3,
+            description: String::from("Liked the movie"),
+            poster_uri: None,
+            tags: None,
+        })).unwrap();
+
+        let empty_comment = borsh::to_vec(&(2u8, CommentPayload {
+            comment: String::new(),
+        })).unwrap();
+
+        let empty_language_title = borsh::to_vec(&(95u8, ReviewLanguagePayload {
+            title: String::new(),
+            language_code: *b"en",
+        })).unwrap();
+
+        let payloads: Vec<Vec<u8>> = vec![
+            vec![],
+            vec![0],
+            vec![1, 255, 255, 255, 255],
+            vec![2, 1, 0],
+            vec![95],
+            vec![200],
+            empty_title,
+            long_title,
+            empty_update_title,
+            empty_comment,
+            empty_language_title,
+        ];
+
+        for payload in payloads {
+            assert_eq!(
+                process_instruction(&program_id, &[], &payload),
+                Err(ProgramError::InvalidInstructionData),
+                "payload {:?} was not rejected",
+                payload
+            );
+        }
+    }
+
+    #[cfg(not(feature = "token-2022"))]
+    #[tokio::test]
+    async fn test_gift_tokens_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let recipient = Pubkey::new_unique();
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let create_recipient_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &recipient, 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let recipient_ata = get_associated_token_address_with_program_id(
+            &recipient, &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Captain America"), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let gift_tokens_ix = create_gift_tokens_ix(
+            payer.pubkey(), 
+            program_id, 
+            recipient, 
+            sol_to_lamports(4.0), 
+            mint,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, create_recipient_ata_ix, add_movie_review_ix, gift_tokens_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let user_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        let user_token_account = token::state::Account::unpack(&user_account.data).unwrap();
+        assert_eq!(user_token_account.amount, sol_to_lamports(6.0));
+
+        let recipient_account = banks_client.get_account(recipient_ata).await.unwrap().unwrap();
+        let recipient_token_account = token::state::Account::unpack(&recipient_account.data).unwrap();
+        assert_eq!(recipient_token_account.amount, sol_to_lamports(4.0));
+
+        let gift_too_much_ix = create_gift_tokens_ix(
+            payer.pubkey(), 
+            program_id, 
+            recipient, 
+            sol_to_lamports(7.0), 
+            mint,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[gift_too_much_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        assert!(banks_client.process_transaction(transaction).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_add_movie_review_watched_at() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let clock = banks_client.get_sysvar::<Clock>().await.unwrap();
+        let watched_at = clock.unix_timestamp - 86_400;
+
+        let past_review_ix = create_add_movie_review_with_payload_ix(
+            payer.pubkey(), 
+            program_id, 
+            AddMovieReviewPayload {
+                watched_at,
+                ..AddMovieReviewPayload::new(
+                    String::from("Captain America"), 
+                    3, 
+                    String::from("Liked the movie"),
+                )
+            },
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, past_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Captain America"], 
+            &program_id
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.watched_at, watched_at);
+
+        let future_review_ix = create_add_movie_review_with_payload_ix(
+            payer.pubkey(), 
+            program_id, 
+            AddMovieReviewPayload {
+                watched_at: clock.unix_timestamp + 86_400,
+                ..AddMovieReviewPayload::new(
+                    String::from("Thor"), 
+                    4, 
+                    String::from("Not out yet"),
+                )
+            },
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[future_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let error = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(ReviewError::InvalidWatchedDate as u32)
+            )
+        );
+    }
+
+    fn account_info<'a>(
+        key: &'a Pubkey,
+        is_signer: bool,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+        owner: &'a Pubkey,
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, is_signer, true, lamports, data, owner, false, 0)
+    }
+
+    #[test]
+    fn test_validate_review_inputs_errors() {
+        let now = 1_700_000_000;
+        let title = "Captain America";
+        let description = "Liked the movie";
+
+        assert!(validate_review_inputs(title, 3, description, None, &[], b"en", now, now).is_ok());
+
+        assert_eq!(
+            validate_review_inputs(title, 0, description, None, &[], b"en", now, now),
+            Err(ReviewError::InvalidRating.into())
+        );
+        assert_eq!(
+            validate_review_inputs(title, 6, description, None, &[], b"en", now, now),
+            Err(ReviewError::InvalidRating.into())
+        );
+        assert_eq!(
+            validate_review_inputs(title, 3, &"a".repeat(MovieAccountState::LEN), None, &[], b"en", now, now),
+            Err(ReviewError::InvalidDataLength.into())
+        );
+        assert_eq!(
+            validate_review_inputs(title, 3, description, None, &[], b"EN", now, now),
+            Err(ReviewError::InvalidLanguageCode.into())
+        );
+        assert_eq!(
+            validate_review_inputs(title, 3, description, None, &[], b"en", now + 1, now),
+            Err(ReviewError::InvalidWatchedDate.into())
+        );
+    }
+
+    #[test]
+    fn test_validate_review_size() {
+        let fixed_len = MovieAccountState::get_account_size(String::new(), String::new(), None, Vec::new()).unwrap();
+        let room = MovieAccountState::LEN - fixed_len;
+
+        assert!(validate_review_size("Dune", &"a".repeat(room - 4), None, &[], MovieAccountState::LEN).is_ok());
+        assert_eq!(
+            validate_review_size("Dune", &"a".repeat(room - 3), None, &[], MovieAccountState::LEN),
+            Err(ReviewError::InvalidDataLength.into())
+        );
+        assert_eq!(
+            validate_review_size(&"a".repeat(room + 1), "", None, &[], MovieAccountState::LEN),
+            Err(ReviewError::InvalidDataLength.into())
+        );
+
+        // Accounts allocated above `LEN` keep their extra room
+        assert!(validate_review_size("Dune", &"a".repeat(room - 3), None, &[], MovieAccountState::LEN + 1).is_ok());
+    }
+
+    // Natively the panic unwinds out of the processor. Under SBF the runtime
+    // aborts the program and reports `ProgramFailedToComplete`, not a
+    // `Custom` code.
+    #[cfg(feature = "test-panic")]
+    #[test]
+    #[should_panic(expected = "intentional test panic")]
+    fn test_test_panic() {
+        let _ = process_instruction(&Pubkey::new_unique(), &[], &[255]);
+    }
+
+    #[test]
+    fn test_create_comment_counter_rejects_wrong_pda() {
+        let program_id = Pubkey::new_unique();
+        let review = Pubkey::new_unique();
+
+        let initializer_key = Pubkey::new_unique();
+        let counter_key = Pubkey::new_unique();
+        let system_program_key = system_program::ID;
+
+        let (mut initializer_lamports, mut counter_lamports, mut system_lamports) = (0, 0, 0);
+        let (mut initializer_data, mut counter_data, mut system_data) = (vec![], vec![], vec![]);
+
+        let initializer = account_info(&initializer_key, true, &mut initializer_lamports, &mut initializer_data, &system_program_key);
+        let pda_counter = account_info(&counter_key, false, &mut counter_lamports, &mut counter_data, &system_program_key);
+        let system_program = account_info(&system_program_key, false, &mut system_lamports, &mut system_data, &system_program_key);
+
+        assert_eq!(
+            create_comment_counter(
+                &program_id,
+                &initializer,
+                &review,
+                Pubkey::find_program_address(&[review.as_ref(), b"comment"], &program_id),
+                &pda_counter,
+                &system_program,
+                &Rent::default(),
+            ),
+            Err(ReviewError::InvalidPDA.into())
+        );
+    }
+
+    #[test]
+    fn test_mint_reward_rejects_wrong_accounts() {
+        let program_id = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+
+        let (mint_key, _) = mint_pda(&program_id);
+        let (mint_auth_key, _) = mint_auth_pda(&program_id);
+        let ata_key = get_associated_token_address_with_program_id(
+            &recipient, &mint_key, &TOKEN_PROGRAM_ID
+        );
+        let token_program_key = TOKEN_PROGRAM_ID;
+        let wrong_key = Pubkey::new_unique();
+
+        let check = |mint: &Pubkey, auth: &Pubkey, ata: &Pubkey, token_program: &Pubkey| {
+            let (mut l1, mut l2, mut l3, mut l4) = (0, 0, 0, 0);
+            let (mut d1, mut d2, mut d3, mut d4) = (vec![], vec![], vec![], vec![]);
+
+            mint_reward(
+                &reward_addresses(&program_id),
+                &recipient,
+                &mint_key,
+                &account_info(mint, false, &mut l1, &mut d1, &token_program_key),
+                &account_info(auth, false, &mut l2, &mut d2, &program_id),
+                &account_info(ata, false, &mut l3, &mut d3, &token_program_key),
+                &account_info(token_program, false, &mut l4, &mut d4, &token_program_key),
+                sol_to_lamports(10.0),
+            )
+        };
+
+        assert_eq!(
+            check(&wrong_key, &mint_auth_key, &ata_key, &token_program_key),
+            Err(ReviewError::IncorrectAccount.into())
+        );
+        assert_eq!(
+            check(&mint_key, &wrong_key, &ata_key, &token_program_key),
+            Err(ReviewError::InvalidPDA.into())
+        );
+        assert_eq!(
+            check(&mint_key, &mint_auth_key, &wrong_key, &token_program_key),
+            Err(ReviewError::IncorrectAccount.into())
+        );
+        assert_eq!(
+            check(&mint_key, &mint_auth_key, &ata_key, &wrong_key),
+            Err(ReviewError::IncorrectAccount.into())
+        );
+    }
+
+    #[test]
+    fn test_verify_comment_matches_review() {
+        let program_id = Pubkey::new_unique();
+        let review = Pubkey::new_unique();
+        let other_review = Pubkey::new_unique();
+
+        let (comment_key, _) = Pubkey::find_program_address(
+            &[review.as_ref(), &1u64.to_be_bytes()], &program_id
+        );
+
+        let comment_data = |review: Pubkey| borsh::to_vec(&MovieComment {
+            discriminator: MovieComment::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            review,
+            commenter: Pubkey::new_unique(),
+            comment: String::from("Totally agree!"),
+            count: 1,
+            created_at: 0,
+        }).unwrap();
+
+        let check = |index: u64, mut data: Vec<u8>| {
+            let mut lamports = 0;
+            let comment = account_info(&comment_key, false, &mut lamports, &mut data, &program_id);
+
+            verify_comment(&program_id, &review, index, &comment).map(|comment| comment.review)
+        };
+
+        assert_eq!(check(1, comment_data(review)), Ok(review));
+        assert_eq!(
+            check(0, comment_data(review)),
+            Err(ReviewError::InvalidPDA.into())
+        );
+        assert_eq!(
+            check(1, comment_data(other_review)),
+            Err(ReviewError::IncorrectAccount.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_reviews_by_range_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut instructions = vec![init_mint_ix, create_ata_ix];
+        let mut reviews = Vec::new();
+
+        for (title, rating) in [("Alien", 1), ("Brazil", 2), ("Casablanca", 3), ("Dune", 4), ("Eraserhead", 5)] {
+            instructions.push(create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                String::from(title), 
+                rating, 
+                String::from("Watched it"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ));
+
+            let (review_pda, _review_bump) = Pubkey::find_program_address(
+                &[payer.pubkey().as_ref(), title.as_bytes()], 
+                &program_id
+            );
+            reviews.push(review_pda);
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let query_ix = create_query_reviews_by_range_ix(program_id, 2, 4, 10, &reviews);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[query_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+        let logs = simulation.simulation_details.unwrap().logs;
+
+        let matched: Vec<&String> = logs.iter()
+            .filter(|log| log.contains("REVIEW:"))
+            .collect();
+
+        assert_eq!(matched.len(), 3);
+        for (review, title) in reviews[1..4].iter().zip(["Brazil", "Casablanca", "Dune"]) {
+            assert!(matched.iter().any(|log| log.contains(&review.to_string()) && log.ends_with(title)));
+        }
+    }
+
+    #[cfg(feature = "token-2022")]
+    #[tokio::test]
+    async fn test_reward_token_is_non_transferable() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let recipient = Pubkey::new_unique();
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let create_recipient_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &recipient, 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Captain America"), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, create_recipient_ata_ix, add_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let user_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&user_account.data).unwrap(), sol_to_lamports(10.0));
+
+        let gift_tokens_ix = create_gift_tokens_ix(
+            payer.pubkey(), 
+            program_id, 
+            recipient, 
+            sol_to_lamports(4.0), 
+            mint,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[gift_tokens_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        assert!(banks_client.process_transaction(transaction).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_create_atas_instruction() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, _mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let users = vec![Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+
+        let batch_ix = create_batch_create_atas_ix(
+            payer.pubkey(), 
+            program_id, 
+            users.clone(), 
+            mint,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, batch_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        for user in users {
+            let user_ata = get_associated_token_address_with_program_id(
+                &user, &mint, &TOKEN_PROGRAM_ID
+            );
+
+            let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+
+            assert_eq!(ata_account.owner, TOKEN_PROGRAM_ID);
+            assert_eq!(token_account_balance(&ata_account.data).unwrap(), 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_legacy_review() {
+        let program_id = Pubkey::new_unique();
+        let reviewer = Keypair::new();
+
+        let title = String::from("Captain America");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[reviewer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let legacy_data = borsh::to_vec(&LegacyMovieAccountState {
+            rating: 4,
+            title: title.clone(),
+            description: String::from("Liked the movie"),
+            is_initialized: true,
+        }).unwrap();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        program_test.add_account(
+            review_pda, 
+            Account {
+                lamports: Rent::default().minimum_balance(legacy_data.len()),
+                data: legacy_data,
+                owner: program_id,
+                ..Account::default()
+            },
+        );
+        program_test.add_account(
+            reviewer.pubkey(), 
+            Account {
+                lamports: sol_to_lamports(1.0),
+                owner: system_program::ID,
+                ..Account::default()
+            },
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let migrate_ix = create_migrate_legacy_review_ix(
+            reviewer.pubkey(), 
+            program_id, 
+            title.clone(),
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            std::slice::from_ref(&migrate_ix), 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), MovieAccountState::LEN);
+        assert!(review_account.lamports >= Rent::default().minimum_balance(MovieAccountState::LEN));
+
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.discriminator, MovieAccountState::DISCRIMINATOR);
+        assert_eq!(review_data.version, MovieAccountState::VERSION);
+        assert_eq!(review_data.reviewer, reviewer.pubkey());
+        assert_eq!(review_data.rating, 4);
+        assert_eq!(review_data.title, title);
+        assert_eq!(review_data.description, "Liked the movie");
+
+        let update_movie_review_ix = create_update_movie_instruction(
+            reviewer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            2, 
+            String::from("Didn't like the movie"),
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[update_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.rating, 2);
+
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[migrate_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+    }
+
+    #[test]
+    fn test_comment_reward_decay() {
+        assert_eq!(comment_reward(0), COMMENT_BASE_REWARD);
+        assert_eq!(comment_reward(1), COMMENT_BASE_REWARD - COMMENT_REWARD_STEP);
+
+        let mut previous = comment_reward(0);
+        for count in 1..20 {
+            let reward = comment_reward(count);
+            assert!(reward <= previous);
+            assert!(reward >= COMMENT_MIN_REWARD);
+            previous = reward;
+        }
+
+        assert_eq!(comment_reward(u64::MAX), COMMENT_MIN_REWARD);
+    }
+
+    #[tokio::test]
+    async fn test_comment_reward_decreases_per_comment() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        let mut previous_balance = token_account_balance(&ata_account.data).unwrap();
+
+        let mut rewards = Vec::new();
+
+        for count in 0..12u64 {
+            let add_comment_ix = create_add_comment_instruction(
+                payer.pubkey(), 
+                program_id,
+                title.clone(),
+                format!("Comment {}", count), 
+                count, 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID,
+            );
+
+            let mut transaction = Transaction::new_with_payer(
+                &[add_comment_ix], 
+                Some(&payer.pubkey())
+            );
+
+            transaction.sign(&[&payer], recent_blockhash);
+
+            banks_client.process_transaction(transaction).await.unwrap();
+
+            let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+            let balance = token_account_balance(&ata_account.data).unwrap();
+            rewards.push(balance - previous_balance);
+            previous_balance = balance;
+        }
+
+        assert_eq!(rewards[0], COMMENT_BASE_REWARD);
+        assert!(rewards.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(rewards[1] < rewards[0]);
+        assert_eq!(*rewards.last().unwrap(), COMMENT_MIN_REWARD);
+    }
+
+    #[tokio::test]
+    async fn test_add_movie_review_with_sponsor() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        let reviewer = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &reviewer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &reviewer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_sponsored_add_movie_review_ix(
+            reviewer.pubkey(), 
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let reviewer_account = banks_client.get_account(reviewer.pubkey()).await.unwrap().unwrap();
+        assert_eq!(reviewer_account.lamports, sol_to_lamports(1.0));
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[reviewer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.reviewer, reviewer.pubkey());
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), sol_to_lamports(10.0));
+    }
+
+    #[test]
+    fn test_audit_log_keeps_latest_entries() {
+        let mut audit_log = AuditLog {
+            discriminator: AuditLog::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            entries: Vec::new(),
+        };
+
+        for changed_at in 0..(AuditLog::MAX_ENTRIES as i64 + 3) {
+            audit_log.push(AuditEntry {
+                changed_at,
+                rating: 3,
+                description_hash: [0; 32],
+            });
+        }
+
+        assert_eq!(audit_log.entries.len(), AuditLog::MAX_ENTRIES);
+        assert_eq!(audit_log.entries[0].changed_at, 3);
+        assert_eq!(
+            audit_log.entries.last().unwrap().changed_at, 
+            AuditLog::MAX_ENTRIES as i64 + 2
+        );
+        assert!(borsh::to_vec(&audit_log).unwrap().len() <= AuditLog::LEN);
+    }
+
+    #[tokio::test]
+    async fn test_update_movie_review_audit_log() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let updates = [
+            (1, "Changed my mind"),
+            (4, "Better on a second watch"),
+            (5, "A classic"),
+        ];
+
+        let mut expected_timestamps = Vec::new();
+
+        for (slot, (rating, description)) in (100..).step_by(100).zip(updates) {
+            context.warp_to_slot(slot).unwrap();
+            let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+            expected_timestamps.push(clock.unix_timestamp);
+
+            let update_movie_review_ix = create_update_movie_instruction(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                rating, 
+                String::from(description),
+            );
+
+            let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+            let mut transaction = Transaction::new_with_payer(
+                &[update_movie_review_ix], 
+                Some(&payer.pubkey()),
+            );
+
+            transaction.sign(&[&payer], recent_blockhash);
+
+            context.banks_client.process_transaction(transaction).await.unwrap();
+        }
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let (audit_pda, _audit_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"audit"], 
+            &program_id
+        );
+
+        let audit_account = context.banks_client.get_account(audit_pda).await.unwrap().unwrap();
+        let audit_data: AuditLog = try_from_slice_unchecked(&audit_account.data).unwrap();
+
+        assert_eq!(audit_data.entries.len(), 3);
+
+        for ((entry, (rating, description)), changed_at) in audit_data.entries
+            .iter()
+            .zip(updates)
+            .zip(expected_timestamps)
+        {
+            assert_eq!(entry.changed_at, changed_at);
+            assert_eq!(entry.rating, rating);
+            assert_eq!(entry.description_hash, hash(description.as_bytes()).to_bytes());
+        }
+
+        let view_audit_log_ix = create_view_audit_log_ix(
+            payer.pubkey(), 
+            program_id, 
+            title,
+        );
+
+        let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[view_audit_log_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+        simulation.result.unwrap().unwrap();
+
+        let logs = simulation.simulation_details.unwrap().logs;
+        let audit_lines = logs.iter().filter(|line| line.contains("AUDIT:")).count();
+
+        assert_eq!(audit_lines, 3);
+    }
+
+    #[tokio::test]
+    async fn test_add_comment_with_sponsor() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        let commenter = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &commenter.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &commenter.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_sponsored_add_movie_review_ix(
+            commenter.pubkey(), 
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let add_comment_ix = create_sponsored_add_comment_ix(
+            commenter.pubkey(), 
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            String::from("Totally agree!"), 
+            0, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix, add_comment_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer, &commenter], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let commenter_account = banks_client.get_account(commenter.pubkey()).await.unwrap().unwrap();
+        assert_eq!(commenter_account.lamports, sol_to_lamports(1.0));
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[commenter.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), &0u64.to_be_bytes()], 
+            &program_id
+        );
+
+        let comment_account = banks_client.get_account(comment_pda).await.unwrap().unwrap();
+        let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+        assert_eq!(comment_data.commenter, commenter.pubkey());
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(
+            token_account_balance(&ata_account.data).unwrap(), 
+            sol_to_lamports(10.0) + COMMENT_BASE_REWARD
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_series_instructions() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let titles = [String::from("A New Hope"), String::from("The Empire Strikes Back")];
+        let series_name = String::from("Star Wars");
+
+        let mut instructions = vec![init_mint_ix, create_ata_ix];
+
+        for title in &titles {
+            instructions.push(create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                5, 
+                String::from("Classic"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut instructions = vec![
+            create_series_ix(payer.pubkey(), program_id, series_name.clone()),
+        ];
+
+        for title in &titles {
+            instructions.push(create_add_to_series_ix(
+                payer.pubkey(), 
+                program_id, 
+                series_name.clone(), 
+                title.clone(),
+            ));
+        }
+
+        instructions.push(create_finalize_series_ix(payer.pubkey(), program_id, series_name.clone()));
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let series_pda = find_series_pda(&payer.pubkey(), &program_id, &series_name);
+
+        let series_account = banks_client.get_account(series_pda).await.unwrap().unwrap();
+        let series: ReviewSeries = try_from_slice_unchecked(&series_account.data).unwrap();
+
+        let expected_parts: Vec<Pubkey> = titles
+            .iter()
+            .map(|title| {
+                Pubkey::find_program_address(
+                    &[payer.pubkey().as_ref(), title.as_bytes()], 
+                    &program_id
+                ).0
+            })
+            .collect();
+
+        assert_eq!(series.owner, payer.pubkey());
+        assert_eq!(series.name, series_name);
+        assert_eq!(series.parts, expected_parts);
+        assert_eq!(series.total_parts, 2);
+        assert!(series.is_finalized);
+
+        let add_to_finalized_ix = create_add_to_series_ix(
+            payer.pubkey(), 
+            program_id, 
+            series_name, 
+            titles[0].clone(),
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_to_finalized_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::SeriesFinalized as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_configured_reward_mint_defaults_to_pda_mint() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Captain America"), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix, 
+                create_initialize_config_ix(payer.pubkey(), program_id), 
+                create_ata_ix, 
+                add_movie_review_ix,
+            ], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(find_config_pda(&program_id)).await.unwrap().unwrap();
+        let config: ProgramConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(config.admin, payer.pubkey());
+        assert_eq!(config.reward_mint, Pubkey::default());
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), sol_to_lamports(10.0));
+    }
+
+    #[cfg(not(feature = "token-2022"))]
+    #[tokio::test]
+    async fn test_configured_external_reward_mint() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (pda_mint, mint_auth) = (
+            mint_pda(&program_id).0,
+            mint_auth_pda(&program_id).0,
+        );
+
+        let rent = banks_client.get_rent().await.unwrap();
+
+        // An existing token whose mint authority has been handed to the program,
+        // and one that still belongs to someone else
+        let external_mint = Keypair::new();
+        let foreign_mint = Keypair::new();
+
+        let mut instructions = vec![create_initialize_config_ix(payer.pubkey(), program_id)];
+
+        for (mint, authority) in [(&external_mint, mint_auth), (&foreign_mint, payer.pubkey())] {
+            instructions.push(solana_sdk::system_instruction::create_account(
+                &payer.pubkey(), 
+                &mint.pubkey(), 
+                rent.minimum_balance(token::state::Mint::LEN), 
+                token::state::Mint::LEN as u64, 
+                &TOKEN_PROGRAM_ID,
+            ));
+            instructions.push(initialize_mint2(
+                &TOKEN_PROGRAM_ID, 
+                &mint.pubkey(), 
+                &authority, 
+                None, 
+                REWARD_MINT_DECIMALS,
+            ).unwrap());
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer, &external_mint, &foreign_mint], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_reward_mint_ix(payer.pubkey(), program_id, foreign_mint.pubkey())], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidMintAuthority as u32))
+        );
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &external_mint.pubkey(), 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &external_mint.pubkey(), &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Captain America"), 
+            3, 
+            String::from("Liked the movie"), 
+            external_mint.pubkey(), 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_reward_mint_ix(payer.pubkey(), program_id, external_mint.pubkey()), 
+                create_ata_ix, 
+                add_movie_review_ix,
+            ], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), sol_to_lamports(10.0));
+
+        // Rewards can no longer be minted from the derived PDA mint
+        let pda_user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &pda_mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Iron Man"), 
+            4, 
+            String::from("Great start"), 
+            pda_mint, 
+            mint_auth, 
+            pda_user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_claim_rewards() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        // No ATA exists yet; rewards accrue on the tracker instead
+        let tracker = find_reward_tracker_pda(&payer.pubkey(), &program_id);
+
+        let mut instructions = vec![
+            init_mint_ix,
+            create_initialize_config_ix(payer.pubkey(), program_id),
+            create_set_claim_rewards_ix(payer.pubkey(), program_id, true),
+            create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                3, 
+                String::from("Liked the movie"), 
+                mint, 
+                mint_auth, 
+                tracker, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ),
+        ];
+
+        for count in 0..2u64 {
+            instructions.push(create_add_comment_instruction(
+                payer.pubkey(), 
+                program_id,
+                title.clone(),
+                format!("Comment {}", count), 
+                count, 
+                mint, 
+                mint_auth, 
+                tracker, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID,
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let expected_rewards = REVIEW_REWARD + comment_reward(0) + comment_reward(1);
+
+        let tracker_account = banks_client.get_account(tracker).await.unwrap().unwrap();
+        let tracker_data: RewardTracker = try_from_slice_unchecked(&tracker_account.data).unwrap();
+        assert_eq!(tracker_data.user, payer.pubkey());
+        assert_eq!(tracker_data.unclaimed_rewards, expected_rewards);
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        // Rewards only go to the claimer's own ATA
+        let other_ata = get_associated_token_address_with_program_id(
+            &Keypair::new().pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_claim_rewards_ix(payer.pubkey(), program_id, mint, other_ata)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+
+        let tracker_account = banks_client.get_account(tracker).await.unwrap().unwrap();
+        let tracker_data: RewardTracker = try_from_slice_unchecked(&tracker_account.data).unwrap();
+        assert_eq!(tracker_data.unclaimed_rewards, expected_rewards);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_ata_ix, create_claim_rewards_ix(payer.pubkey(), program_id, mint, user_ata)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), expected_rewards);
+
+        let tracker_account = banks_client.get_account(tracker).await.unwrap().unwrap();
+        let tracker_data: RewardTracker = try_from_slice_unchecked(&tracker_account.data).unwrap();
+        assert_eq!(tracker_data.unclaimed_rewards, 0);
+
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_claim_rewards_ix(payer.pubkey(), program_id, mint, user_ata)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), expected_rewards);
+    }
+
+    #[tokio::test]
+    async fn test_react_to_review() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        let other_reactor = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix, 
+                create_ata_ix, 
+                add_movie_review_ix,
+                create_react_to_review_ix(payer.pubkey(), program_id, payer.pubkey(), title.clone(), 0),
+                create_react_to_review_ix(other_reactor.pubkey(), program_id, payer.pubkey(), title.clone(), 1),
+            ], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer, &other_reactor], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.reactions, [1, 1, 0, 0, 0, 0]);
+
+        // Switching reactions moves the count rather than adding a second one
+        let mut transaction = Transaction::new_with_payer(
+            &[create_react_to_review_ix(other_reactor.pubkey(), program_id, payer.pubkey(), title.clone(), 5)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer, &other_reactor], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.reactions, [1, 0, 0, 0, 0, 1]);
+
+        let (reaction_pda, _reaction_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), other_reactor.pubkey().as_ref()], 
+            &program_id
+        );
+
+        let reaction_account = banks_client.get_account(reaction_pda).await.unwrap().unwrap();
+        let reaction_data: ReactionRecord = try_from_slice_unchecked(&reaction_account.data).unwrap();
+        assert_eq!(reaction_data.reactor, other_reactor.pubkey());
+        assert_eq!(reaction_data.reaction, 5);
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_recreate_movie_review() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |rating: u8, description: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            rating, 
+            String::from(description), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_review_ix(3, "Liked the movie"), add_comment_ix(0)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Re-adding an open review is still rejected
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix(4, "Still like it")], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+
+        // Closing and re-adding within one transaction must not see stale data
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_delete_movie_review_ix(payer.pubkey(), program_id, title.clone(), mint, user_ata),
+                add_review_ix(1, "Didn't age well"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.rating, 1);
+        assert_eq!(review_data.description, "Didn't age well");
+
+        // The first comment outlived the old review, so numbering carries on
+        // after it rather than starting over at its address
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+
+        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 1);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_comment_ix(1)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), 1u64.to_be_bytes().as_ref()], 
+            &program_id
+        );
+
+        let comment_account = banks_client.get_account(comment_pda).await.unwrap().unwrap();
+        let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+        assert_eq!(comment_data.comment, "Comment 1");
+
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_delete_movie_review_ix(payer.pubkey(), program_id, title.clone(), mint, user_ata)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        assert!(banks_client.get_account(review_pda).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_review_reward_clawback() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from(title), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let delete_review_ix = |title: &str| create_delete_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            String::from(title), 
+            mint, 
+            user_ata,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_review_ix("Captain America"), add_review_ix("Iron Man")], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // Within the grace period the reward is burned
+        let mut transaction = Transaction::new_with_payer(
+            &[delete_review_ix("Captain America")], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = context.banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+
+        // Spending part of the remaining reward makes the clawback impossible
+        let burn_ix = token::instruction::burn(
+            &TOKEN_PROGRAM_ID, 
+            &user_ata, 
+            &mint, 
+            &payer.pubkey(), 
+            &[], 
+            1,
+        ).unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[burn_ix, delete_review_ix("Iron Man")], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(1, InstructionError::Custom(ReviewError::RewardClawbackFailed as u32))
+        );
+
+        // Once the grace period has passed the review can be deleted as-is
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += ProgramConfig::DEFAULT_CLAWBACK_PERIOD;
+        context.set_sysvar(&clock);
+
+        let recent_blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash).await.unwrap();
+
+        let burn_ix = token::instruction::burn(
+            &TOKEN_PROGRAM_ID, 
+            &user_ata, 
+            &mint, 
+            &payer.pubkey(), 
+            &[], 
+            1,
+        ).unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[burn_ix, delete_review_ix("Iron Man")], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = context.banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD - 1);
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Iron Man"], 
+            &program_id
+        );
+        assert!(context.banks_client.get_account(review_pda).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_comment_moderation_modes() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer.pubkey(), 
+            &payer.pubkey(), 
+            &mint, 
+            &TOKEN_PROGRAM_ID,
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id,
+            title.clone(),
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let set_mode_ix = |mode: u8| create_set_comment_moderation_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            mode,
+        );
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let approval_pda = |count: u64| {
+            let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+                &[review_pda.as_ref(), &count.to_be_bytes()], 
+                &program_id
+            );
+
+            Pubkey::find_program_address(&[comment_pda.as_ref(), b"approval"], &program_id).0
+        };
+
+        // Open: comments go straight through
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix, create_ata_ix, add_movie_review_ix, add_comment_ix(0)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // RequireApproval: the comment waits in a `PendingComment` until the
+        // reviewer approves it
+        let add_pending_comment_ix = create_add_pending_comment_ix(
+            payer.pubkey(), 
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            String::from("Comment 1"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[set_mode_ix(MovieAccountState::COMMENTS_REQUIRE_APPROVAL), add_pending_comment_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.comment_moderation, MovieAccountState::COMMENTS_REQUIRE_APPROVAL);
+
+        let comment_pda = Pubkey::find_program_address(&[review_pda.as_ref(), &1u64.to_be_bytes()], &program_id).0;
+        assert!(banks_client.get_account(comment_pda).await.unwrap().is_none());
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_approve_pending_comment_ix(payer.pubkey(), program_id, payer.pubkey(), title.clone(), 1)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        assert!(banks_client.get_account(comment_pda).await.unwrap().is_some());
+
+        // Comments stored before pending comments existed are approved in place
+        assert!(banks_client.get_account(approval_pda(1)).await.unwrap().is_none());
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_approve_comment_ix(payer.pubkey(), program_id, title.clone(), 1)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let approval_account = banks_client.get_account(approval_pda(1)).await.unwrap().unwrap();
+        let approval_data: CommentApproval = try_from_slice_unchecked(&approval_account.data).unwrap();
+        assert!(approval_data.is_approved);
+
+        // Locked: new comments are rejected
+        let mut transaction = Transaction::new_with_payer(
+            &[set_mode_ix(MovieAccountState::COMMENTS_LOCKED), add_comment_ix(2)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(1, InstructionError::Custom(ReviewError::CommentsLocked as u32))
+        );
+
+        // Back to Open
+        let mut transaction = Transaction::new_with_payer(
+            &[set_mode_ix(MovieAccountState::COMMENTS_OPEN), add_comment_ix(2)], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.comment_moderation, MovieAccountState::COMMENTS_OPEN);
+    }
+
+    #[cfg(feature = "error-context")]
+    #[tokio::test]
+    async fn test_invalid_pda_error_context() {
+        use borsh::BorshDeserialize;
+
+        use crate::error::ErrorContext;
+
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, _init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut add_movie_review_ix = create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let wrong_review = Pubkey::new_unique();
+        add_movie_review_ix.accounts[1].pubkey = wrong_review;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_movie_review_ix], 
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+
+        assert_eq!(
+            simulation.result.unwrap().unwrap_err(), 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        assert_eq!(return_data.program_id, program_id);
+
+        let context = ErrorContext::try_from_slice(&return_data.data).unwrap();
+        assert_eq!(
+            context, 
+            ErrorContext {
+                error_code: ReviewError::InvalidPDA as u32,
+                account_index: 1,
+                expected: review_pda,
+                actual: wrong_review,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backfill_reward() {
+        let program_id = Pubkey::new_unique();
+        let reviewer = Keypair::new();
+
+        let title = String::from("Captain America");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[reviewer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        // Legacy reviews predate the reward mint and were never paid
+        let legacy_data = borsh::to_vec(&LegacyMovieAccountState {
+            rating: 4,
+            title: title.clone(),
+            description: String::from("Liked the movie"),
+            is_initialized: true,
+        }).unwrap();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        program_test.add_account(
+            review_pda, 
+            Account {
+                lamports: Rent::default().minimum_balance(legacy_data.len()),
+                data: legacy_data,
+                owner: program_id,
+                ..Account::default()
+            },
+        );
+        program_test.add_account(
+            reviewer.pubkey(), 
+            Account {
+                lamports: sol_to_lamports(1.0),
+                owner: system_program::ID,
+                ..Account::default()
+            },
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let reviewer_ata = get_associated_token_address_with_program_id(
+            &reviewer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                create_migrate_legacy_review_ix(reviewer.pubkey(), program_id, title.clone()),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &reviewer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(!review_data.reward_minted);
+
+        let backfill_ix = create_backfill_reward_ix(
+            payer.pubkey(), 
+            program_id, 
+            review_pda, 
+            mint, 
+            reviewer_ata,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            std::slice::from_ref(&backfill_ix), 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(reviewer_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(review_data.reward_minted);
+
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[backfill_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::RewardAlreadyMinted as u32))
+        );
+
+        let ata_account = banks_client.get_account(reviewer_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+
+        // Reviews added after the mint existed were paid on creation
+        let payer_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let (payer_review_pda, _payer_review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    payer_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_backfill_reward_ix(
+                payer.pubkey(), 
+                program_id, 
+                payer_review_pda, 
+                mint, 
+                payer_ata,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::RewardAlreadyMinted as u32))
+        );
+
+        let ata_account = banks_client.get_account(payer_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+    }
+
+    #[test]
+    fn test_mint_pdas_match_seeds() {
+        let program_id = Pubkey::new_unique();
+
+        assert_eq!(
+            mint_pda(&program_id), 
+            Pubkey::find_program_address(&[b"token_mint"], &program_id)
+        );
+        assert_eq!(
+            mint_auth_pda(&program_id), 
+            Pubkey::find_program_address(&[b"token_auth"], &program_id)
+        );
+
+        let (mint, mint_auth, _init_mint_ix) = create_init_mint_ix(
+            Pubkey::new_unique(), &program_id
+        );
+        assert_eq!(mint, mint_pda(&program_id).0);
+        assert_eq!(mint_auth, mint_auth_pda(&program_id).0);
+    }
+
+    #[test]
+    fn test_year_from_unix_timestamp() {
+        assert_eq!(year_from_unix_timestamp(0), 1970);
+        assert_eq!(year_from_unix_timestamp(951_782_400), 2000);
+        assert_eq!(year_from_unix_timestamp(1_704_067_199), 2023);
+        assert_eq!(year_from_unix_timestamp(1_704_067_200), 2024);
+        assert_eq!(year_from_unix_timestamp(1_709_164_800), 2024);
+        assert_eq!(year_from_unix_timestamp(4_102_444_800), 2100);
+    }
+
+    #[tokio::test]
+    async fn test_review_awards() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        let voter = add_funded_keypair(&mut program_test);
+
+        let mut context = start_with_upgrade_authority(program_test, &program_id).await;
+        let payer = context.payer.insecure_clone();
+
+        // 2024-06-01
+        let year = 2024;
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = 1_717_200_000;
+        context.set_sysvar(&clock);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let payer_title = String::from("Captain America");
+        let voter_title = String::from("Iron Man");
+        let category = String::from("Best Review");
+
+        let (payer_review, _payer_review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), payer_title.as_bytes()], 
+            &program_id
+        );
+        let (voter_review, _voter_review_bump) = Pubkey::find_program_address(
+            &[voter.pubkey().as_ref(), voter_title.as_bytes()], 
+            &program_id
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &voter.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    payer_title.clone(), 
+                    4, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    get_associated_token_address_with_program_id(&payer.pubkey(), &mint, &TOKEN_PROGRAM_ID), 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                create_add_movie_review_ix(
+                    voter.pubkey(), 
+                    program_id, 
+                    voter_title.clone(), 
+                    5, 
+                    String::from("Loved the movie"), 
+                    mint, 
+                    mint_auth, 
+                    get_associated_token_address_with_program_id(&voter.pubkey(), &mint, &TOKEN_PROGRAM_ID), 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &voter], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // A reviewer must prove authorship with their own review
+        let mut transaction = Transaction::new_with_payer(
+            &[create_nominate_review_ix(
+                voter.pubkey(), 
+                program_id, 
+                payer_review, 
+                payer.pubkey(), 
+                payer_title.clone(), 
+                category.clone(), 
+                year,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &voter], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::NotVerifiedReviewer as u32))
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_nominate_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    payer_review, 
+                    voter.pubkey(), 
+                    voter_title.clone(), 
+                    category.clone(), 
+                    year,
+                ),
+                create_nominate_review_ix(
+                    voter.pubkey(), 
+                    program_id, 
+                    voter_review, 
+                    payer.pubkey(), 
+                    payer_title.clone(), 
+                    category.clone(), 
+                    year,
+                ),
+                create_vote_for_nomination_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    payer_review, 
+                    voter.pubkey(), 
+                    voter_title.clone(), 
+                    year,
+                ),
+                create_vote_for_nomination_ix(
+                    voter.pubkey(), 
+                    program_id, 
+                    voter_review, 
+                    voter.pubkey(), 
+                    voter_title.clone(), 
+                    year,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &voter], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let voter_nomination = find_nomination_pda(&voter_review, year, &program_id);
+        let payer_nomination = find_nomination_pda(&payer_review, year, &program_id);
+
+        let nomination_account = context.banks_client.get_account(voter_nomination).await.unwrap().unwrap();
+        let nomination_data: AwardNomination = try_from_slice_unchecked(&nomination_account.data).unwrap();
+        assert_eq!(nomination_data.review, voter_review);
+        assert_eq!(nomination_data.year, year);
+        assert_eq!(nomination_data.nominated_by, payer.pubkey());
+        assert_eq!(nomination_data.category, category);
+        assert_eq!(nomination_data.votes, 2);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_vote_for_nomination_ix(
+                payer.pubkey(), 
+                program_id, 
+                payer_review, 
+                voter.pubkey(), 
+                voter_title.clone(), 
+                year,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+
+        let declare_ix = create_declare_award_winner_ix(
+            payer.pubkey(), 
+            program_id, 
+            category.clone(), 
+            year, 
+            &[payer_nomination, voter_nomination],
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            std::slice::from_ref(&declare_ix), 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::VotingStillOpen as u32))
+        );
+
+        // 2025-01-01
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = 1_735_689_600;
+        context.set_sysvar(&clock);
+
+        let recent_blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_vote_for_nomination_ix(
+                payer.pubkey(), 
+                program_id, 
+                voter_review, 
+                payer.pubkey(), 
+                payer_title.clone(), 
+                year,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::VotingClosed as u32))
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            std::slice::from_ref(&declare_ix), 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let award_pda = find_award_pda(&category, year, &program_id);
+        let award_account = context.banks_client.get_account(award_pda).await.unwrap().unwrap();
+        let award_data: Award = try_from_slice_unchecked(&award_account.data).unwrap();
+        assert_eq!(award_data.category, category);
+        assert_eq!(award_data.year, year);
+        assert_eq!(award_data.nomination, voter_nomination);
+        assert_eq!(award_data.votes, 2);
+
+        let nomination_account = context.banks_client.get_account(voter_nomination).await.unwrap().unwrap();
+        let nomination_data: AwardNomination = try_from_slice_unchecked(&nomination_account.data).unwrap();
+        assert!(nomination_data.is_winner);
+
+        let nomination_account = context.banks_client.get_account(payer_nomination).await.unwrap().unwrap();
+        let nomination_data: AwardNomination = try_from_slice_unchecked(&nomination_account.data).unwrap();
+        assert!(!nomination_data.is_winner);
+
+        let recent_blockhash = context.banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[declare_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_counter() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = start_with_upgrade_authority(program_test, &program_id).await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                add_comment_ix(0),
+                add_comment_ix(1),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // Corrupt the counter so it points past the existing comments
+        let mut counter_account = context.banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let mut counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 2);
+        counter_data.counter = 5;
+        counter_data.serialize(&mut &mut counter_account.data[..]).unwrap();
+        context.set_account(&counter_pda, &counter_account.into());
+
+        let comments: Vec<Pubkey> = (0..2u64)
+            .map(|count| Pubkey::find_program_address(
+                &[review_pda.as_ref(), &count.to_be_bytes()], 
+                &program_id
+            ).0)
+            .collect();
+
+        // Duplicates and accounts that are not comments of this review are ignored
+        let mut transaction = Transaction::new_with_payer(
+            &[create_sync_counter_ix(
+                payer.pubkey(), 
+                program_id, 
+                payer.pubkey(), 
+                title.clone(), 
+                &[comments[0], comments[1], comments[1], review_pda, Pubkey::new_unique()],
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let counter_account = context.banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 2);
+
+        // Comments continue from the repaired index
+        let mut transaction = Transaction::new_with_payer(
+            &[add_comment_ix(2)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let counter_account = context.banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 3);
+
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_sync_counter_ix(
+                stranger.pubkey(), 
+                program_id, 
+                payer.pubkey(), 
+                title, 
+                &comments,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+    }
+
+    #[cfg(not(feature = "token-2022"))]
+    #[tokio::test]
+    async fn test_revoked_mint_authority() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = start_with_upgrade_authority(program_test, &program_id).await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Captain America"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // Move the mint authority away from the `token_auth` PDA
+        let mut mint_account = context.banks_client.get_account(mint).await.unwrap().unwrap();
+        let mut mint_state = token::state::Mint::unpack(&mint_account.data).unwrap();
+        mint_state.mint_authority = COption::Some(Pubkey::new_unique());
+        token::state::Mint::pack(mint_state, &mut mint_account.data).unwrap();
+        context.set_account(&mint, &mint_account.into());
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix("Iron Man")], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::MintAuthorityRevoked as u32))
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_skip_revoked_rewards_ix(payer.pubkey(), program_id, true),
+                add_review_ix("Iron Man"),
+                create_add_comment_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Iron Man"), 
+                    String::from("Totally agree!"), 
+                    0, 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Iron Man"], 
+            &program_id
+        );
+
+        let review_account = context.banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(!review_data.reward_minted);
+
+        // Only the review created before the revocation was paid
+        let ata_account = context.banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+    }
+
+    #[tokio::test]
+    async fn test_comment_created_at() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                add_comment_ix(0),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += 60;
+        context.set_sysvar(&clock);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_comment_ix(1)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut comments = Vec::new();
+        for count in 0..2u64 {
+            let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+                &[review_pda.as_ref(), &count.to_be_bytes()], 
+                &program_id
+            );
+
+            let comment_account = context.banks_client.get_account(comment_pda).await.unwrap().unwrap();
+            let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+            assert_eq!(comment_data.count, count);
+            comments.push(comment_data);
+        }
+
+        assert_ne!(comments[0].created_at, 0);
+        assert!(comments[1].created_at > comments[0].created_at);
+    }
+
+    #[tokio::test]
+    async fn test_global_stats() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            String::from("Captain America"), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Captain America"),
+                add_comment_ix(0),
+                add_comment_ix(1),
+                add_review_ix("Iron Man"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let stats_pda = find_stats_pda(&program_id);
+        let comment_rewards = comment_reward(0) + comment_reward(1);
+
+        let stats_account = banks_client.get_account(stats_pda).await.unwrap().unwrap();
+        let stats_data: GlobalStats = try_from_slice_unchecked(&stats_account.data).unwrap();
+        assert_eq!(stats_data.discriminator, GlobalStats::DISCRIMINATOR);
+        assert_eq!(stats_data.total_reviews, 2);
+        assert_eq!(stats_data.total_comments, 2);
+        assert_eq!(stats_data.total_reviewers, 1);
+        assert_eq!(stats_data.total_tokens_minted, 2 * REVIEW_REWARD + comment_rewards);
+
+        // Both deletions fall within the clawback period and burn the reward
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_delete_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Iron Man"), 
+                    mint, 
+                    user_ata,
+                ),
+                create_delete_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Captain America"), 
+                    mint, 
+                    user_ata,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let stats_account = banks_client.get_account(stats_pda).await.unwrap().unwrap();
+        let stats_data: GlobalStats = try_from_slice_unchecked(&stats_account.data).unwrap();
+        assert_eq!(stats_data.total_reviews, 0);
+        assert_eq!(stats_data.total_comments, 0);
+        assert_eq!(stats_data.total_reviewers, 1);
+        assert_eq!(stats_data.total_tokens_minted, comment_rewards);
+
+        // A reviewer who no longer holds any rewards is still not new
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                token::instruction::burn(
+                    &TOKEN_PROGRAM_ID, 
+                    &user_ata, 
+                    &mint, 
+                    &payer.pubkey(), 
+                    &[], 
+                    comment_rewards,
+                ).unwrap(),
+                add_review_ix("Thor"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let stats_account = banks_client.get_account(stats_pda).await.unwrap().unwrap();
+        let stats_data: GlobalStats = try_from_slice_unchecked(&stats_account.data).unwrap();
+        assert_eq!(stats_data.total_reviews, 1);
+        assert_eq!(stats_data.total_reviewers, 1);
+    }
+
+    #[tokio::test]
+    async fn test_comment_merkle_inclusion() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let comments: Vec<Pubkey> = (0..2u64)
+            .map(|count| Pubkey::find_program_address(
+                &[review_pda.as_ref(), &count.to_be_bytes()], 
+                &program_id
+            ).0)
+            .collect();
+
+        // Two-leaf tree: root = hash(sorted(hash(comment 0), hash(comment 1)))
+        let leaves: Vec<[u8; 32]> = comments.iter()
+            .map(|comment| hash(comment.as_ref()).to_bytes())
+            .collect();
+        let root = if leaves[0] <= leaves[1] {
+            hashv(&[&leaves[0], &leaves[1]]).to_bytes()
+        } else {
+            hashv(&[&leaves[1], &leaves[0]]).to_bytes()
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                add_comment_ix(0),
+                add_comment_ix(1),
+                create_update_merkle_root_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    payer.pubkey(), 
+                    title.clone(), 
+                    root, 
+                    2,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let merkle_account = banks_client.get_account(find_merkle_root_pda(&review_pda, &program_id)).await.unwrap().unwrap();
+        let merkle_data: CommentMerkleRoot = try_from_slice_unchecked(&merkle_account.data).unwrap();
+        assert_eq!(merkle_data.review, review_pda);
+        assert_eq!(merkle_data.root, root);
+        assert_eq!(merkle_data.num_comments, 2);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_verify_comment_inclusion_ix(
+                    program_id, 
+                    payer.pubkey(), 
+                    title.clone(), 
+                    comments[0], 
+                    vec![leaves[1]],
+                ),
+                create_verify_comment_inclusion_ix(
+                    program_id, 
+                    payer.pubkey(), 
+                    title.clone(), 
+                    comments[1], 
+                    vec![leaves[0]],
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_verify_comment_inclusion_ix(
+                program_id, 
+                payer.pubkey(), 
+                title.clone(), 
+                Pubkey::new_unique(), 
+                vec![leaves[1]],
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::CommentNotIncluded as u32))
+        );
+
+        // Only the reviewer or the admin may replace the root
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_merkle_root_ix(
+                stranger.pubkey(), 
+                program_id, 
+                payer.pubkey(), 
+                title, 
+                [0; 32], 
+                0,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_comment_counter() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = start_with_upgrade_authority(program_test, &program_id).await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+
+        let comments: Vec<Pubkey> = (0..4u64)
+            .map(|count| Pubkey::find_program_address(
+                &[review_pda.as_ref(), &count.to_be_bytes()], 
+                &program_id
+            ).0)
+            .collect();
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                add_comment_ix(0),
+                add_comment_ix(1),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // Rewind the counter so it lags behind the existing comments
+        let mut counter_account = context.banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let mut counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        counter_data.counter = 0;
+        counter_data.serialize(&mut &mut counter_account.data[..]).unwrap();
+        context.set_account(&counter_pda, &counter_account.into());
+
+        // The next comment PDA is already taken, so commenting is blocked
+        let mut transaction = Transaction::new_with_payer(
+            &[add_comment_ix(0)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        assert!(context.banks_client.process_transaction(transaction).await.is_err());
+
+        // Candidates must follow on from the stored counter
+        let mut transaction = Transaction::new_with_payer(
+            &[create_sync_comment_counter_ix(
+                program_id, 
+                payer.pubkey(), 
+                title.clone(), 
+                &[comments[1], comments[0]],
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+
+        // Anyone can repair; the walk stops at the first missing comment
+        let stranger = Keypair::new();
+        context.set_account(
+            &stranger.pubkey(), 
+            &Account {
+                lamports: sol_to_lamports(1.0),
+                owner: system_program::ID,
+                ..Account::default()
+            }.into(),
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_sync_comment_counter_ix(
+                program_id, 
+                payer.pubkey(), 
+                title.clone(), 
+                &comments,
+            )], 
+            Some(&stranger.pubkey()),
+        );
+        transaction.sign(&[&stranger], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let counter_account = context.banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 2);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_comment_ix(2)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // Already in sync: the counter does not move
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_sync_comment_counter_ix(
+                program_id, 
+                payer.pubkey(), 
+                title, 
+                &comments[3..],
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let counter_account = context.banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 3);
+    }
+
+    #[tokio::test]
+    async fn test_update_comment_text() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), &0u64.to_be_bytes()], 
+            &program_id
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                create_add_comment_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    String::from("Totally agree!"), 
+                    0, 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // The new text is longer than the old one, so the account has to grow
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_comment_text_ix(
+                payer.pubkey(), 
+                program_id, 
+                payer.pubkey(), 
+                title.clone(), 
+                0, 
+                String::from("Totally agree, best movie of the year!"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let result = banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+        assert!(result.result.is_ok());
+
+        let logs = result.metadata.unwrap().log_messages;
+        assert!(logs.iter().any(|line| line.ends_with("PREVIOUS_COMMENT:Totally agree!")));
+
+        let comment_account = banks_client.get_account(comment_pda).await.unwrap().unwrap();
+        let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+        assert_eq!(comment_data.comment, "Totally agree, best movie of the year!");
+        assert_eq!(comment_data.count, 0);
+
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_comment_text_ix(
+                stranger.pubkey(), 
+                program_id, 
+                payer.pubkey(), 
+                title, 
+                0, 
+                String::from("Hijacked"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_review_account_sizing() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str, tight_size: bool| create_add_movie_review_with_payload_ix(
+            payer.pubkey(), 
+            program_id, 
+            AddMovieReviewPayload {
+                tight_size,
+                ..AddMovieReviewPayload::new(title.to_string(), 3, String::from("Liked the movie"))
+            }, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let review_pda = |title: &str| Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        ).0;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Captain America", true),
+                add_review_ix("Iron Man", false),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let tight_len = MovieAccountState::get_account_size(
+            String::from("Captain America"), 
+            String::from("Liked the movie"),
+            None,
+            Vec::new(),
+        ).unwrap();
+
+        let review_account = banks_client.get_account(review_pda("Captain America")).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), tight_len);
+        assert_eq!(review_account.lamports, Rent::default().minimum_balance(tight_len));
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(review_data.tight_size);
+
+        let review_account = banks_client.get_account(review_pda("Iron Man")).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), MovieAccountState::LEN);
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(!review_data.tight_size);
+
+        // A longer description grows the tight account; the padded one keeps its size
+        let description = String::from("Liked the movie, especially the elevator fight scene");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_update_movie_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Captain America"), 
+                    4, 
+                    description.clone(),
+                ),
+                create_update_movie_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Iron Man"), 
+                    4, 
+                    description.clone(),
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let grown_len = MovieAccountState::get_account_size(String::from("Captain America"), description.clone(), None, Vec::new()).unwrap();
+
+        let review_account = banks_client.get_account(review_pda("Captain America")).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), grown_len);
+        assert_eq!(review_account.lamports, Rent::default().minimum_balance(grown_len));
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.description, description);
+        assert_eq!(review_data.rating, 4);
+
+        let review_account = banks_client.get_account(review_pda("Iron Man")).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), MovieAccountState::LEN);
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.description, description);
+    }
+
+    #[tokio::test]
+    async fn test_rename_review() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let review_pda = |title: &str| Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        ).0;
+        let counter_pda = |review: &Pubkey| Pubkey::find_program_address(
+            &[review.as_ref(), b"comment"], 
+            &program_id
+        ).0;
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Captian America"),
+                add_review_ix("Iron Mna"),
+                create_add_comment_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Iron Mna"), 
+                    String::from("Totally agree!"), 
+                    0, 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Without comments the old review and counter are closed outright
+        let mut transaction = Transaction::new_with_payer(
+            &[create_rename_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                String::from("Captian America"), 
+                String::from("Captain America"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let old_review = review_pda("Captian America");
+        assert!(banks_client.get_account(old_review).await.unwrap().is_none());
+        assert!(banks_client.get_account(counter_pda(&old_review)).await.unwrap().is_none());
+
+        let new_review = review_pda("Captain America");
+        let review_account = banks_client.get_account(new_review).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.title, "Captain America");
+        assert_eq!(review_data.description, "Liked the movie");
+        assert_eq!(review_data.reviewer, payer.pubkey());
+
+        let counter_account = banks_client.get_account(counter_pda(&new_review)).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 0);
+
+        // With comments the old review stays behind as a tombstone
+        let mut transaction = Transaction::new_with_payer(
+            &[create_rename_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                String::from("Iron Mna"), 
+                String::from("Iron Man"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let old_review = review_pda("Iron Mna");
+        let new_review = review_pda("Iron Man");
+
+        let tombstone_account = banks_client.get_account(old_review).await.unwrap().unwrap();
+        assert_eq!(tombstone_account.data.len(), ReviewTombstone::LEN);
+        assert_eq!(tombstone_account.lamports, Rent::default().minimum_balance(ReviewTombstone::LEN));
+        let tombstone: ReviewTombstone = try_from_slice_unchecked(&tombstone_account.data).unwrap();
+        assert_eq!(tombstone.discriminator, ReviewTombstone::DISCRIMINATOR);
+        assert_eq!(tombstone.renamed_to, new_review);
+
+        assert!(banks_client.get_account(counter_pda(&old_review)).await.unwrap().is_none());
+
+        let counter_account = banks_client.get_account(counter_pda(&new_review)).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 1);
+
+        // The existing comment still points at the old review
+        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+            &[old_review.as_ref(), &0u64.to_be_bytes()], 
+            &program_id
+        );
+        let comment_account = banks_client.get_account(comment_pda).await.unwrap().unwrap();
+        let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+        assert_eq!(comment_data.review, old_review);
+
+        // Renaming onto a title that already has a review is rejected
+        let mut transaction = Transaction::new_with_payer(
+            &[create_rename_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                String::from("Captain America"), 
+                String::from("Iron Man"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(err, TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized));
+
+        let review_account = banks_client.get_account(review_pda("Captain America")).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.title, "Captain America");
+    }
+
+    // Stands in for a composing program registered as the review callback
+    fn mock_callback_processor(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let review = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        if review.key.as_ref() != instruction_data {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        msg!("CALLBACK:{}", review.key);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_review_callback_program() {
+        let program_id = Pubkey::new_unique();
+        let callback_program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        program_test.add_program(
+            "mock_callback", 
+            callback_program_id, 
+            processor!(mock_callback_processor)
+        );
+
+        let mut context = start_with_upgrade_authority(program_test, &program_id).await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str, callback: Option<Pubkey>| {
+            let mut ix = create_sponsored_add_movie_review_ix(
+                payer.pubkey(), 
+                payer.pubkey(), 
+                program_id, 
+                title.to_string(), 
+                3, 
+                String::from("Liked the movie"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            );
+            if let Some(callback) = callback {
+                ix.accounts.push(AccountMeta::new_readonly(callback, false));
+            }
+
+            ix
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // Only the admin can register a callback
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_callback_program_ix(stranger.pubkey(), program_id, Some(callback_program_id))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_callback_program_ix(payer.pubkey(), program_id, Some(callback_program_id))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = context.banks_client.get_account(find_config_pda(&program_id)).await.unwrap().unwrap();
+        let config_data: ProgramConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(config_data.callback_program_id, Some(callback_program_id));
+
+        // The callback receives the new review
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix("Captain America", Some(callback_program_id))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let result = context.banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+        assert!(result.result.is_ok());
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Captain America"], 
+            &program_id
+        );
+
+        let logs = result.metadata.unwrap().log_messages;
+        assert!(logs.iter().any(|line| line.ends_with(&format!("CALLBACK:{}", review_pda))));
+
+        // Any other program in the callback slot is rejected
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix("Iron Man", Some(system_program::ID))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+
+        // Once cleared, reviews no longer need the callback account
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_callback_program_ix(payer.pubkey(), program_id, None),
+                add_review_ix("Iron Man", None),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+    }
+
+    // Caller program that posts reviews for its `[b"reviewer"]` PDA. The first
+    // data byte selects whether it signs for the PDA, the rest is forwarded.
+    fn mock_caller_processor(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction_data: &[u8],
+    ) -> ProgramResult {
+        let (sign, data) = instruction_data.split_first().ok_or(ProgramError::InvalidInstructionData)?;
+        let (movie_program, inner_accounts) = accounts.split_first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let (reviewer, reviewer_bump) = Pubkey::find_program_address(&[b"reviewer"], program_id);
+
+        let instruction = Instruction {
+            program_id: *movie_program.key,
+            accounts: inner_accounts.iter()
+                .map(|account| AccountMeta {
+                    pubkey: *account.key,
+                    is_signer: account.is_signer || *account.key == reviewer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data: data.to_vec(),
+        };
+
+        if *sign == 1 {
+            invoke_signed(&instruction, accounts, &[&[b"reviewer", &[reviewer_bump]]])
+        } else {
+            invoke(&instruction, accounts)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_movie_review_via_cpi() {
+        let program_id = Pubkey::new_unique();
+        let caller_program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        program_test.add_program(
+            "mock_caller", 
+            caller_program_id, 
+            processor!(mock_caller_processor)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (reviewer_pda, _reviewer_bump) = Pubkey::find_program_address(
+            &[b"reviewer"], 
+            &caller_program_id
+        );
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let reviewer_ata = get_associated_token_address_with_program_id(
+            &reviewer_pda, &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let caller_ix = |sign: bool| {
+            let add_movie_review_ix = create_sponsored_add_movie_review_ix(
+                reviewer_pda, 
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                3, 
+                String::from("Liked the movie"), 
+                mint, 
+                mint_auth, 
+                reviewer_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            );
+
+            let mut accounts = vec![AccountMeta::new_readonly(program_id, false)];
+            accounts.extend(add_movie_review_ix.accounts.into_iter().map(|mut meta| {
+                // Only the caller program can sign for its PDA
+                if meta.pubkey == reviewer_pda {
+                    meta.is_signer = false;
+                }
+
+                meta
+            }));
+
+            let mut data = vec![sign as u8];
+            data.extend(add_movie_review_ix.data);
+
+            Instruction {
+                program_id: caller_program_id,
+                accounts,
+                data,
+            }
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &reviewer_pda, 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Without the PDA's seeds the runtime refuses the signer flag
+        let mut transaction = Transaction::new_with_payer(
+            &[caller_ix(false)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation)
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[caller_ix(true)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[reviewer_pda.as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.reviewer, reviewer_pda);
+        assert_eq!(review_data.title, title);
+
+        let ata_account = banks_client.get_account(reviewer_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+    }
+
+    #[tokio::test]
+    async fn test_sponsored_reviews() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let review_pda = |title: &str| Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        ).0;
+        let sponsored_review = review_pda("Captain America");
+        let other_review = review_pda("Iron Man");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_with_payload_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    AddMovieReviewPayload {
+                        is_sponsored: true,
+                        ..AddMovieReviewPayload::new(
+                            String::from("Captain America"), 
+                            5, 
+                            String::from("Paid to watch it"),
+                        )
+                    }, 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Iron Man"), 
+                    4, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(sponsored_review).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(review_data.is_sponsored);
+
+        let review_account = banks_client.get_account(other_review).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(!review_data.is_sponsored);
+
+        // Disclosure can be added later and survives updates
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_mark_sponsored_ix(payer.pubkey(), program_id, String::from("Iron Man")),
+                create_update_movie_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Iron Man"), 
+                    5, 
+                    String::from("Loved the movie"),
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(other_review).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(review_data.is_sponsored);
+
+        // There is no way back once a review is disclosed as sponsored
+        let mut transaction = Transaction::new_with_payer(
+            &[create_mark_sponsored_ix(payer.pubkey(), program_id, String::from("Captain America"))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::AlreadySponsored as u32))
+        );
+
+        let review_account = banks_client.get_account(sponsored_review).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(review_data.is_sponsored);
+    }
+
+    #[tokio::test]
+    async fn test_review_poster_uri() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str, poster_uri: Option<String>| create_add_movie_review_with_payload_ix(
+            payer.pubkey(), 
+            program_id, 
+            AddMovieReviewPayload {
+                tight_size: true,
+                poster_uri,
+                ..AddMovieReviewPayload::new(title.to_string(), 3, String::from("Liked the movie"))
+            }, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let review_pda = |title: &str| Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        ).0;
+
+        let poster = String::from("https://example.com/captain-america.jpg");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Captain America", Some(poster.clone())),
+                add_review_ix("Iron Man", None),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda("Captain America")).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.poster_uri, Some(poster.clone()));
+        assert_eq!(review_account.data.len(), review_data.account_len().unwrap());
+
+        // Unsupported schemes and oversized URIs are rejected
+        for poster_uri in [
+            String::from("http://example.com/poster.jpg"),
+            format!("ipfs://{}", "a".repeat(MovieAccountState::MAX_POSTER_URI_LEN)),
+        ] {
+            let mut transaction = Transaction::new_with_payer(
+                &[add_review_ix("Thor", Some(poster_uri))], 
+                Some(&payer.pubkey()),
+            );
+            transaction.sign(&[&payer], recent_blockhash);
+
+            let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+            assert_eq!(
+                err, 
+                TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidUri as u32))
+            );
+        }
+
+        // Adding a poster later grows the tight account to fit it
+        let iron_man_poster = String::from("ar://iron-man-poster");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_update_movie_with_poster_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Iron Man"), 
+                    4, 
+                    String::from("Liked the movie"),
+                    Some(iron_man_poster.clone()),
+                ),
+                create_update_movie_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Captain America"), 
+                    4, 
+                    String::from("Liked the movie"),
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let grown_len = MovieAccountState::get_account_size(
+            String::from("Iron Man"), 
+            String::from("Liked the movie"),
+            Some(iron_man_poster.clone()),
+            Vec::new(),
+        ).unwrap();
+
+        let review_account = banks_client.get_account(review_pda("Iron Man")).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), grown_len);
+        assert_eq!(review_account.lamports, Rent::default().minimum_balance(grown_len));
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.poster_uri, Some(iron_man_poster));
+
+        // Updating without a poster keeps the current one
+        let review_account = banks_client.get_account(review_pda("Captain America")).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.poster_uri, Some(poster));
+        assert_eq!(review_data.rating, 4);
+    }
+
+    #[tokio::test]
+    async fn test_min_description_len_for_reward() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str, description: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            4, 
+            description.to_string(), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_set_min_description_len_ix(payer.pubkey(), program_id, 15),
+                add_review_ix("Captain America", "Great"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Captain America"], 
+            &program_id
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.description, "Great");
+        assert!(!review_data.reward_minted);
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), 0);
+
+        // 14 chars but 16 bytes, so it clears the threshold
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix("Iron Man", "Très très bien")], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), sol_to_lamports(10.0));
+
+        // Only the admin can move the threshold
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_min_description_len_ix(stranger.pubkey(), program_id, 0)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_tag_cloud() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let titles = ["The Dark Knight", "The Dark Knight Rises", "Knight and Day"];
+
+        let mut instructions = vec![
+            init_mint_ix,
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer.pubkey(), 
+                &payer.pubkey(), 
+                &mint, 
+                &TOKEN_PROGRAM_ID,
+            ),
+        ];
+        for title in titles {
+            instructions.push(create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                title.to_string(), 
+                4, 
+                String::from("Liked the movie"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let update_ixs: Vec<Instruction> = titles
+            .iter()
+            .map(|title| create_update_tag_cloud_ix(payer.pubkey(), program_id, title.to_string()))
+            .collect();
+
+        let mut transaction = Transaction::new_with_payer(&update_ixs, Some(&payer.pubkey()));
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let tag_cloud_account = banks_client.get_account(find_tag_cloud_pda(&program_id)).await.unwrap().unwrap();
+        let tag_cloud: TagCloud = try_from_slice_unchecked(&tag_cloud_account.data).unwrap();
+
+        assert_eq!(
+            tag_cloud.entries, 
+            vec![
+                (String::from("the"), 2),
+                (String::from("dark"), 2),
+                (String::from("knight"), 3),
+                (String::from("rises"), 1),
+                (String::from("and"), 1),
+                (String::from("day"), 1),
+            ]
+        );
+
+        // Each review only counts once
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_tag_cloud_ix(payer.pubkey(), program_id, titles[0].to_string())], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+
+        let tag_cloud_account = banks_client.get_account(find_tag_cloud_pda(&program_id)).await.unwrap().unwrap();
+        let tag_cloud: TagCloud = try_from_slice_unchecked(&tag_cloud_account.data).unwrap();
+        assert_eq!(tag_cloud.entries[2], (String::from("knight"), 3));
+
+        // Only the reviewer can feed their review into the cloud
+        let stranger = Keypair::new();
+
+        let mut update_ix = create_update_tag_cloud_ix(payer.pubkey(), program_id, titles[0].to_string());
+        update_ix.accounts[0] = AccountMeta::new(stranger.pubkey(), true);
+
+        let mut transaction = Transaction::new_with_payer(&[update_ix], Some(&payer.pubkey()));
+        transaction.sign(&[&payer, &stranger], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+    }
+
+    #[cfg(feature = "normalize-titles")]
+    #[tokio::test]
+    async fn test_normalized_titles_share_a_review() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            4, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Inception"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"inception"], 
+            &program_id
+        );
+
+        // The original spelling is kept in the account
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.title, "Inception");
+
+        // A case variant resolves to the same review
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix(" INCEPTION ")], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_movie_instruction(
+                payer.pubkey(), 
+                program_id, 
+                String::from("inception"), 
+                2, 
+                String::from("Didn't hold up"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.title, "Inception");
+        assert_eq!(review_data.rating, 2);
+    }
+
+    #[tokio::test]
+    async fn test_mint_first_review_badge() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    String::from("Captain America"), 
+                    4, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let profile_pda = find_profile_pda(&payer.pubkey(), &program_id);
+
+        let profile_account = banks_client.get_account(profile_pda).await.unwrap().unwrap();
+        let profile: ReviewerProfile = try_from_slice_unchecked(&profile_account.data).unwrap();
+        assert_eq!(profile.reviewer, payer.pubkey());
+        assert_eq!(profile.review_count, 1);
+        assert!(!profile.has_first_badge);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_mint_first_review_badge_ix(payer.pubkey(), program_id, payer.pubkey())], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let badge_mint = find_badge_mint_pda(&payer.pubkey(), &program_id);
+        let badge_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &badge_mint, &TOKEN_PROGRAM_ID
+        );
+
+        let badge_ata_account = banks_client.get_account(badge_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&badge_ata_account.data).unwrap(), 1);
+
+        // No further badges can be minted
+        let badge_mint_account = banks_client.get_account(badge_mint).await.unwrap().unwrap();
+        assert_eq!(mint_authority(&badge_mint_account.data).unwrap(), COption::None);
+
+        let profile_account = banks_client.get_account(profile_pda).await.unwrap().unwrap();
+        let profile: ReviewerProfile = try_from_slice_unchecked(&profile_account.data).unwrap();
+        assert!(profile.has_first_badge);
+
+        // A second badge is refused
+        let mut transaction = Transaction::new_with_payer(
+            &[create_mint_first_review_badge_ix(payer.pubkey(), program_id, payer.pubkey())], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::BadgeAlreadyMinted as u32))
+        );
+
+        // A reviewer past their first review has missed the badge
+        let late_reviewer = Keypair::new();
+        let late_ata = get_associated_token_address_with_program_id(
+            &late_reviewer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut instructions = vec![
+            system_instruction::transfer(&payer.pubkey(), &late_reviewer.pubkey(), sol_to_lamports(1.0)),
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer.pubkey(), 
+                &late_reviewer.pubkey(), 
+                &mint, 
+                &TOKEN_PROGRAM_ID,
+            ),
+        ];
+        for title in ["Iron Man", "Thor"] {
+            instructions.push(create_add_movie_review_ix(
+                late_reviewer.pubkey(), 
+                program_id, 
+                title.to_string(), 
+                3, 
+                String::from("Fine"), 
+                mint, 
+                mint_auth, 
+                late_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        transaction.sign(&[&payer, &late_reviewer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_mint_first_review_badge_ix(payer.pubkey(), program_id, late_reviewer.pubkey())], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::BadgeNotEarned as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_comments() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let mut instructions = vec![
+            init_mint_ix,
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer.pubkey(), 
+                &payer.pubkey(), 
+                &mint, 
+                &TOKEN_PROGRAM_ID,
+            ),
+            create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                4, 
+                String::from("Liked the movie"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ),
+        ];
+        let comments = ["First!", "Totally agree", "Not for me", "Great soundtrack"];
+        for (count, comment) in comments.iter().enumerate() {
+            instructions.push(create_add_comment_instruction(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                comment.to_string(), 
+                count as u64, 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID,
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_fetch_comments_ix(payer.pubkey(), program_id, title.clone(), 1, 3)], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+        let logs = simulation.simulation_details.unwrap().logs;
+
+        let fetched: Vec<&String> = logs.iter()
+            .filter(|log| log.contains("COMMENT:"))
+            .collect();
+
+        assert_eq!(fetched.len(), 3);
+        for (log, (index, comment)) in fetched.iter().zip(comments.iter().enumerate().skip(1)) {
+            assert!(log.ends_with(&format!("COMMENT:{}:{}:{}", index, payer.pubkey(), comment)));
+        }
+
+        // A comment from outside the requested window is rejected
+        let mut fetch_ix = create_fetch_comments_ix(payer.pubkey(), program_id, title, 1, 3);
+        fetch_ix.accounts[3].pubkey = fetch_ix.accounts[2].pubkey;
+
+        let mut transaction = Transaction::new_with_payer(&[fetch_ix], Some(&payer.pubkey()));
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_program_version() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        // The native test processor has no deployed ELF, so stand-in bytes
+        // are placed in a ProgramData account, padded like a real deploy
+        let elf = b"\x7fELF movie review program".to_vec();
+
+        let mut program_data = vec![0; UpgradeableLoaderState::size_of_programdata_metadata()];
+        program_data[0] = 3;
+        program_data.extend_from_slice(&elf);
+        program_data.resize(program_data.len() + 64, 0);
+
+        program_test.add_account(
+            get_program_data_address(&program_id),
+            Account {
+                lamports: sol_to_lamports(1.0),
+                data: program_data,
+                owner: bpf_loader_upgradeable::ID,
+                ..Account::default()
+            },
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_verify_program_version_ix(program_id, hash(&elf).to_bytes())], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_verify_program_version_ix(program_id, hash(b"another build").to_bytes())], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::VersionMismatch as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_review_names_the_field() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let title = "a".repeat(MAX_SEED_LEN);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                4, 
+                String::from("Liked the movie"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            )], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // An oversized description only fits in a transaction on an update,
+        // which carries fewer accounts than an add
+        let room = MovieAccountState::LEN
+            - MovieAccountState::get_account_size(title.clone(), String::new(), None, Vec::new()).unwrap();
+
+        let cases = [
+            (
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    "a".repeat(MAX_SEED_LEN + 1), 
+                    4, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                "Title cannot be longer than",
+            ),
+            (
+                create_update_movie_instruction(
+                    payer.pubkey(), 
+                    program_id, 
+                    title, 
+                    4, 
+                    "a".repeat(room + 1),
+                ),
+                "Description is too long",
+            ),
+        ];
+
+        for (instruction, expected_log) in cases {
+            let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+            transaction.sign(&[&payer], recent_blockhash);
+
+            let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+            assert!(simulation.result.unwrap().is_err());
+
+            let logs = simulation.simulation_details.unwrap().logs;
+            assert!(logs.iter().any(|log| log.contains(expected_log)), "missing {:?} in {:?}", expected_log, logs);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authority_transfer() {
+        let program_id = Pubkey::new_unique();
+        let authority = Keypair::new();
+        let new_authority = Keypair::new();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        // Pays for the authority record on the first proposal
+        program_test.add_account(
+            authority.pubkey(),
+            Account {
+                lamports: sol_to_lamports(1.0),
+                owner: system_program::ID,
+                ..Account::default()
+            },
+        );
+
+        // ProgramData header naming `authority` as the upgrade authority
+        let mut program_data = vec![0; UpgradeableLoaderState::size_of_programdata_metadata()];
+        program_data[0] = 3;
+        program_data[12] = 1;
+        program_data[13..45].copy_from_slice(authority.pubkey().as_ref());
+
+        let program_data_address = get_program_data_address(&program_id);
+        program_test.add_account(
+            program_data_address,
+            Account {
+                lamports: sol_to_lamports(1.0),
+                data: program_data,
+                owner: bpf_loader_upgradeable::ID,
+                ..Account::default()
+            },
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let record_pda = find_authority_record_pda(&program_id);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_propose_authority_transfer_ix(authority.pubkey(), program_id, new_authority.pubkey())], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer, &authority], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let record_account = banks_client.get_account(record_pda).await.unwrap().unwrap();
+        let record: ProgramAuthorityRecord = try_from_slice_unchecked(&record_account.data).unwrap();
+        assert_eq!(record.upgrade_authority, authority.pubkey());
+        assert_eq!(record.pending_authority, Some(new_authority.pubkey()));
+
+        // The PDA holds the upgrade authority until the transfer is accepted
+        let program_data_account = banks_client.get_account(program_data_address).await.unwrap().unwrap();
+        assert_eq!(&program_data_account.data[13..45], record_pda.as_ref());
+
+        // Only the pending authority can accept
+        let mut transaction = Transaction::new_with_payer(
+            &[create_accept_authority_transfer_ix(payer.pubkey(), program_id)], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_accept_authority_transfer_ix(new_authority.pubkey(), program_id)], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer, &new_authority], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let record_account = banks_client.get_account(record_pda).await.unwrap().unwrap();
+        let record: ProgramAuthorityRecord = try_from_slice_unchecked(&record_account.data).unwrap();
+        assert_eq!(record.upgrade_authority, new_authority.pubkey());
+        assert_eq!(record.pending_authority, None);
+
+        let program_data_account = banks_client.get_account(program_data_address).await.unwrap().unwrap();
+        assert_eq!(program_data_account.data[12], 1);
+        assert_eq!(&program_data_account.data[13..45], new_authority.pubkey().as_ref());
+    }
+
+    #[cfg(feature = "anchor-events")]
+    #[tokio::test]
+    async fn test_review_added_event() {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        use borsh::BorshDeserialize;
+
+        use crate::events::{Event, ReviewAdded};
+
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    4, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let result = banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+        assert!(result.result.is_ok());
+
+        let logs = result.metadata.unwrap().log_messages;
+        let event_line = logs.iter()
+            .find(|line| line.starts_with("Program data: "))
+            .expect("no event logged");
+
+        let data = STANDARD.decode(event_line.trim_start_matches("Program data: ")).unwrap();
+
+        // Anchor's event discriminator
+        assert_eq!(data[..8], hash(b"event:ReviewAdded").to_bytes()[..8]);
+        assert_eq!(data[..8], ReviewAdded::discriminator());
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+            &program_id
+        );
+
+        assert_eq!(
+            ReviewAdded::try_from_slice(&data[8..]).unwrap(),
+            ReviewAdded {
+                reviewer: payer.pubkey(),
+                review: review_pda,
+                title,
+                rating: 4,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ping() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_ping_ix(program_id)], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let result = banks_client.process_transaction_with_metadata(transaction).await.unwrap();
+        assert!(result.result.is_ok());
+
+        let logs = result.metadata.unwrap().log_messages;
+        assert!(logs.iter().any(|line| line.ends_with("Pong")));
+    }
+
+    #[tokio::test]
+    async fn test_update_review_above_current_len() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        // Pays the fees and the audit log, keeping the update in one signature
+        let reviewer = add_funded_keypair(&mut program_test);
+
+        // A review allocated under an earlier, larger cap, holding more than
+        // the current `LEN` allows
+        let old_len = MovieAccountState::LEN + 200;
+        let title = "a".repeat(MAX_SEED_LEN);
+        let poster_uri = format!("ipfs://{}", "p".repeat(MovieAccountState::MAX_POSTER_URI_LEN - 7));
+
+        let review = MovieAccountState {
+            poster_uri: Some(poster_uri.clone()),
+            ..review_fixture(reviewer.pubkey(), &title, 4, &"a".repeat(800))
+        };
+        assert!(MovieAccountState::get_account_size(
+            title.clone(), review.description.clone(), review.poster_uri.clone(), Vec::new()
+        ).unwrap() > MovieAccountState::LEN);
+
+        let review_pda = find_review_pda(&reviewer.pubkey(), &title, &program_id);
+
+        program_test.add_account(
+            review_pda,
+            Account {
+                lamports: Rent::default().minimum_balance(old_len),
+                data: review_account_data(&review, old_len),
+                owner: program_id,
+                ..Account::default()
+            },
+        );
+
+        let (mut banks_client, _payer, recent_blockhash) = program_test.start().await;
+
+        // Also past `LEN` together with the kept title and poster
+        let description = "b".repeat(850);
+        assert!(MovieAccountState::get_account_size(
+            title.clone(), description.clone(), Some(poster_uri.clone()), Vec::new()
+        ).unwrap() > MovieAccountState::LEN);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_movie_instruction(
+                reviewer.pubkey(), 
+                program_id, 
+                title, 
+                2, 
+                description.clone(),
+            )], 
+            Some(&reviewer.pubkey())
+        );
+        transaction.sign(&[&reviewer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), old_len);
+
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.rating, 2);
+        assert_eq!(review_data.description, description);
+        assert_eq!(review_data.poster_uri, Some(poster_uri));
+    }
+
+    #[tokio::test]
+    async fn test_auto_verify_threshold() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            4, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                create_set_auto_verify_threshold_ix(payer.pubkey(), program_id, 2),
+                add_review_ix("Captain America"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let profile_pda = find_profile_pda(&payer.pubkey(), &program_id);
+
+        let profile_account = banks_client.get_account(profile_pda).await.unwrap().unwrap();
+        let profile: ReviewerProfile = try_from_slice_unchecked(&profile_account.data).unwrap();
+        assert_eq!(profile.review_count, 1);
+        assert!(!profile.is_verified);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix("Iron Man")], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let profile_account = banks_client.get_account(profile_pda).await.unwrap().unwrap();
+        let profile: ReviewerProfile = try_from_slice_unchecked(&profile_account.data).unwrap();
+        assert_eq!(profile.review_count, 2);
+        assert!(profile.is_verified);
+
+        // Only the admin can move the threshold
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_auto_verify_threshold_ix(stranger.pubkey(), program_id, 0)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+    }
+
+    // Where a rating enters the program in `test_rating_bounds`
+    #[derive(Clone, Copy, Debug)]
+    enum RatingPath {
+        Add,
+        Update,
+    }
+
+    // Adds "Captain America" at `rating`, or adds it at 3 and then updates
+    // it to `rating`, and returns the result of the rated transaction
+    async fn submit_rating(path: RatingPath, rating: u8) -> Result<(), TransactionError> {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+        let description = String::from("Liked the movie");
+
+        let add_review_ix = |rating: u8| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            rating, 
+            description.clone(), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let rated_ix = match path {
+            RatingPath::Add => add_review_ix(rating),
+            RatingPath::Update => {
+                let mut transaction = Transaction::new_with_payer(
+                    &[add_review_ix(3)], 
+                    Some(&payer.pubkey()),
+                );
+                transaction.sign(&[&payer], recent_blockhash);
+
+                banks_client.process_transaction(transaction).await.unwrap();
+
+                create_update_movie_instruction(payer.pubkey(), program_id, title.clone(), rating, description.clone())
+            },
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[rated_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.map_err(|err| err.unwrap())
+    }
+
+    #[test_case(RatingPath::Add, 0, false ; "add rating 0")]
+    #[test_case(RatingPath::Add, 1, true ; "add rating 1")]
+    #[test_case(RatingPath::Add, 3, true ; "add rating 3")]
+    #[test_case(RatingPath::Add, 5, true ; "add rating 5")]
+    #[test_case(RatingPath::Add, 6, false ; "add rating 6")]
+    #[test_case(RatingPath::Add, 255, false ; "add rating 255")]
+    #[test_case(RatingPath::Update, 0, false ; "update rating 0")]
+    #[test_case(RatingPath::Update, 1, true ; "update rating 1")]
+    #[test_case(RatingPath::Update, 3, true ; "update rating 3")]
+    #[test_case(RatingPath::Update, 5, true ; "update rating 5")]
+    #[test_case(RatingPath::Update, 6, false ; "update rating 6")]
+    #[test_case(RatingPath::Update, 255, false ; "update rating 255")]
+    #[tokio::test]
+    async fn test_rating_bounds(path: RatingPath, rating: u8, accepted: bool) {
+        let result = submit_rating(path, rating).await;
+
+        if accepted {
+            assert_eq!(result, Ok(()));
+        } else {
+            assert_eq!(
+                result, 
+                Err(TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidRating as u32)))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_review_collection_instructions() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let titles = [String::from("Iron Man"), String::from("Thor")];
+
+        let mut instructions = vec![init_mint_ix];
+
+        for title in &titles {
+            instructions.push(create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                4, 
+                String::from("Phase one"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut instructions = vec![
+            create_collection_ix(
+                payer.pubkey(), 
+                program_id, 
+                0, 
+                String::from("Marvel"), 
+                String::from("The Avengers, one by one"),
+            ),
+        ];
+
+        for title in &titles {
+            instructions.push(create_add_review_to_collection_ix(payer.pubkey(), program_id, 0, title.clone()));
+        }
+
+        instructions.push(create_remove_review_from_collection_ix(payer.pubkey(), program_id, 0, titles[0].clone()));
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let collection_pda = find_collection_pda(&payer.pubkey(), &program_id, 0);
+
+        let collection_account = banks_client.get_account(collection_pda).await.unwrap().unwrap();
+        let collection: ReviewCollection = try_from_slice_unchecked(&collection_account.data).unwrap();
+
+        let thor_pda = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), titles[1].as_bytes()], 
+            &program_id
+        ).0;
+
+        assert_eq!(collection.owner, payer.pubkey());
+        assert_eq!(collection.collection_id, 0);
+        assert_eq!(collection.name, "Marvel");
+        assert_eq!(collection.description, "The Avengers, one by one");
+        assert_eq!(collection.review_pdas, vec![thor_pda]);
+        assert_eq!(collection.review_count, 1);
+
+        let profile_account = banks_client.get_account(find_profile_pda(&payer.pubkey(), &program_id)).await.unwrap().unwrap();
+        let profile: ReviewerProfile = try_from_slice_unchecked(&profile_account.data).unwrap();
+        assert_eq!(profile.collection_count, 1);
+
+        // The next collection takes the next id
+        let mut transaction = Transaction::new_with_payer(
+            &[create_collection_ix(payer.pubkey(), program_id, 0, String::from("Marvel"), String::new())], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+
+        // Removing a review that is not in the collection fails
+        let mut transaction = Transaction::new_with_payer(
+            &[create_remove_review_from_collection_ix(payer.pubkey(), program_id, 0, titles[0].clone())], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(err, TransactionError::InstructionError(0, InstructionError::InvalidArgument));
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_collection_ix(payer.pubkey(), program_id, 1, String::from("Marvel"), String::new())], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let collection_account = banks_client.get_account(find_collection_pda(&payer.pubkey(), &program_id, 1)).await.unwrap().unwrap();
+        let collection: ReviewCollection = try_from_slice_unchecked(&collection_account.data).unwrap();
+        assert_eq!(collection.collection_id, 1);
+        assert!(collection.review_pdas.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_compact_comment() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Thor");
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        );
+
+        let comment_pda = |count: u64| Pubkey::find_program_address(
+            &[review_pda.as_ref(), &count.to_be_bytes()], 
+            &program_id
+        ).0;
+
+        let add_comment_ix = |count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    4, 
+                    String::from("Loud"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                add_comment_ix(0),
+                add_comment_ix(1),
+                add_comment_ix(2),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        // Leave a gap at index 1
+        context.set_account(&comment_pda(1), &Account::default().into());
+
+        let compact_ix = |old_index: u64, new_index: u64| create_compact_comment_ix(
+            payer.pubkey(), 
+            program_id, 
+            payer.pubkey(), 
+            title.clone(), 
+            old_index, 
+            new_index,
+        );
+
+        // Index 0 still holds a comment
+        let mut transaction = Transaction::new_with_payer(
+            &[compact_ix(2, 0)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(err, TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized));
+
+        let mut transaction = Transaction::new_with_payer(
+            &[compact_ix(2, 1)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let comment_account = context.banks_client.get_account(comment_pda(1)).await.unwrap().unwrap();
+        assert_eq!(comment_account.owner, program_id);
+
+        let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+        assert!(comment_data.is_initialized);
+        assert_eq!(comment_data.review, review_pda);
+        assert_eq!(comment_data.commenter, payer.pubkey());
+        assert_eq!(comment_data.comment, "Comment 2");
+        assert_eq!(comment_data.count, 1);
+
+        assert!(context.banks_client.get_account(comment_pda(2)).await.unwrap().is_none());
+    }
+
+    // ProgramTest doesn't deploy through the upgradeable loader, so the
+    // ProgramData account InitializeConfig checks is written by hand, naming
+    // the payer as upgrade authority
+    async fn start_with_upgrade_authority(
+        program_test: ProgramTest,
+        program_id: &Pubkey,
+    ) -> ProgramTestContext {
+        let mut context = program_test.start_with_context().await;
+
+        let mut program_data = vec![0; UpgradeableLoaderState::size_of_programdata_metadata()];
+        program_data[0] = 3;
+        program_data[12] = 1;
+        program_data[13..45].copy_from_slice(context.payer.pubkey().as_ref());
+
+        let program_data_account = Account {
+            lamports: sol_to_lamports(1.0),
+            data: program_data,
+            owner: bpf_loader_upgradeable::ID,
+            ..Account::default()
+        };
+        context.set_account(&get_program_data_address(program_id), &program_data_account.into());
+
+        context
+    }
+
+    #[tokio::test]
+    async fn test_same_title_from_two_reviewers() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let alice = add_funded_keypair(&mut program_test);
+        let bob = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[init_mint_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        for (reviewer, rating) in [(&alice, 5), (&bob, 2)] {
+            let user_ata = get_associated_token_address_with_program_id(
+                &reviewer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+            );
+
+            let mut transaction = Transaction::new_with_payer(
+                &[
+                    spl_associated_token_account::instruction::create_associated_token_account(
+                        &payer.pubkey(), 
+                        &reviewer.pubkey(), 
+                        &mint, 
+                        &TOKEN_PROGRAM_ID,
+                    ),
+                    create_add_movie_review_ix(
+                        reviewer.pubkey(), 
+                        program_id, 
+                        title.clone(), 
+                        rating, 
+                        format!("Rated {}", rating), 
+                        mint, 
+                        mint_auth, 
+                        user_ata, 
+                        system_program::ID, 
+                        TOKEN_PROGRAM_ID
+                    ),
+                ], 
+                Some(&payer.pubkey()),
+            );
+            transaction.sign(&[&payer, reviewer], recent_blockhash);
+
+            banks_client.process_transaction(transaction).await.unwrap();
+        }
+
+        let review_pda = |reviewer: &Keypair| Pubkey::find_program_address(
+            &[reviewer.pubkey().as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+            &program_id
+        ).0;
+
+        let alice_pda = review_pda(&alice);
+        let bob_pda = review_pda(&bob);
+        assert_ne!(alice_pda, bob_pda);
+
+        for (reviewer, pda, rating) in [(&alice, alice_pda, 5), (&bob, bob_pda, 2)] {
+            let account = banks_client.get_account(pda).await.unwrap().unwrap();
+            let review: MovieAccountState = try_from_slice_unchecked(&account.data).unwrap();
+            assert_eq!(review.reviewer, reviewer.pubkey());
+            assert_eq!(review.title, title);
+            assert_eq!(review.rating, rating);
+        }
+
+        // Bob's update with Alice's review swapped in is rejected
+        let mut update_ix = create_update_movie_instruction(
+            bob.pubkey(), 
+            program_id, 
+            title.clone(), 
+            1, 
+            String::from("Overwritten"),
+        );
+        update_ix.accounts[1].pubkey = alice_pda;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[update_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &bob], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+
+        // Bob's own update only touches his review
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_movie_instruction(
+                bob.pubkey(), 
+                program_id, 
+                title.clone(), 
+                3, 
+                String::from("Grew on me"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &bob], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let account = banks_client.get_account(alice_pda).await.unwrap().unwrap();
+        let review: MovieAccountState = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(review.rating, 5);
+        assert_eq!(review.description, "Rated 5");
+
+        let account = banks_client.get_account(bob_pda).await.unwrap().unwrap();
+        let review: MovieAccountState = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(review.rating, 3);
+        assert_eq!(review.description, "Grew on me");
+    }
+
+    #[tokio::test]
+    async fn test_direct_messages() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let recipient = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_send_direct_message_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    recipient.pubkey(), 
+                    String::from("Loved your Dune review"), 
+                    0,
+                ),
+                create_send_direct_message_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    recipient.pubkey(), 
+                    String::from("Seen Part Two?"), 
+                    1,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let counter_pda = find_direct_message_counter_pda(&payer.pubkey(), &recipient.pubkey(), &program_id);
+        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: DirectMessageCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.count, 2);
+
+        let message_pda = find_direct_message_pda(&payer.pubkey(), &recipient.pubkey(), &program_id, 1);
+        let message_account = banks_client.get_account(message_pda).await.unwrap().unwrap();
+        assert_eq!(message_account.owner, program_id);
+
+        let message_data: DirectMessage = try_from_slice_unchecked(&message_account.data).unwrap();
+        assert_eq!(message_data.sender, payer.pubkey());
+        assert_eq!(message_data.recipient, recipient.pubkey());
+        assert_eq!(message_data.message_index, 1);
+        assert_eq!(message_data.content, "Seen Part Two?");
+        assert!(!message_data.read);
+
+        // The sender cannot mark their own message read
+        let mut transaction = Transaction::new_with_payer(
+            &[create_mark_message_read_ix(payer.pubkey(), program_id, payer.pubkey(), 1)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountOwner)
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_mark_message_read_ix(recipient.pubkey(), program_id, payer.pubkey(), 1)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &recipient], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let message_account = banks_client.get_account(message_pda).await.unwrap().unwrap();
+        let message_data: DirectMessage = try_from_slice_unchecked(&message_account.data).unwrap();
+        assert!(message_data.read);
+
+        // The other message is untouched
+        let message_pda = find_direct_message_pda(&payer.pubkey(), &recipient.pubkey(), &program_id, 0);
+        let message_account = banks_client.get_account(message_pda).await.unwrap().unwrap();
+        let message_data: DirectMessage = try_from_slice_unchecked(&message_account.data).unwrap();
+        assert!(!message_data.read);
+    }
+
+    #[cfg(not(feature = "token-2022"))]
+    #[tokio::test]
+    async fn test_max_reward_amount() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let mint_auth = mint_auth_pda(&program_id).0;
+        let rent = banks_client.get_rent().await.unwrap();
+
+        // At 0 decimals the fixed 10_000_000_000 unit review reward would be
+        // ten billion whole tokens
+        let external_mint = Keypair::new();
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &external_mint.pubkey(), &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                solana_sdk::system_instruction::create_account(
+                    &payer.pubkey(), 
+                    &external_mint.pubkey(), 
+                    rent.minimum_balance(token::state::Mint::LEN), 
+                    token::state::Mint::LEN as u64, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                initialize_mint2(
+                    &TOKEN_PROGRAM_ID, 
+                    &external_mint.pubkey(), 
+                    &mint_auth, 
+                    None, 
+                    0,
+                ).unwrap(),
+                create_set_reward_mint_ix(payer.pubkey(), program_id, external_mint.pubkey()),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &external_mint.pubkey(), 
+                    &TOKEN_PROGRAM_ID,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &external_mint], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let config_account = banks_client.get_account(find_config_pda(&program_id)).await.unwrap().unwrap();
+        let config: ProgramConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(config.max_reward_amount, ProgramConfig::DEFAULT_MAX_REWARD_AMOUNT);
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            3, 
+            String::from("Liked the movie"), 
+            external_mint.pubkey(), 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_max_reward_amount_ix(payer.pubkey(), program_id, REVIEW_REWARD - 1),
+                add_review_ix("Captain America"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(1, InstructionError::Custom(ReviewError::RewardTooLarge as u32))
+        );
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), 0);
+
+        // A cap at the reward itself lets it through
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_max_reward_amount_ix(payer.pubkey(), program_id, REVIEW_REWARD),
+                add_review_ix("Iron Man"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+
+        // Only the admin can move the cap
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_max_reward_amount_ix(stranger.pubkey(), program_id, u64::MAX)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sequential_comments_from_two_wallets() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let commenter = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &commenter.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    get_associated_token_address_with_program_id(&payer.pubkey(), &mint, &TOKEN_PROGRAM_ID), 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+            &program_id
+        );
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+
+        for (expected_count, author) in [&payer, &commenter, &payer].into_iter().enumerate() {
+            let expected_count = expected_count as u64;
+
+            let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+            let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+            assert_eq!(counter_data.counter, expected_count);
+
+            let user_ata = get_associated_token_address_with_program_id(
+                &author.pubkey(), &mint, &TOKEN_PROGRAM_ID
+            );
+
+            let mut transaction = Transaction::new_with_payer(
+                &[create_add_comment_instruction(
+                    author.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    format!("Comment {}", expected_count), 
+                    counter_data.counter, 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID,
+                )], 
+                Some(&payer.pubkey()),
+            );
+            transaction.sign(&[&payer, author], recent_blockhash);
+
+            banks_client.process_transaction(transaction).await.unwrap();
+
+            let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+                &[review_pda.as_ref(), &expected_count.to_be_bytes()], 
+                &program_id
+            );
+
+            let comment_account = banks_client.get_account(comment_pda).await.unwrap().unwrap();
+            let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+            assert_eq!(comment_data.count, expected_count);
+            assert_eq!(comment_data.review, review_pda);
+            assert_eq!(comment_data.commenter, author.pubkey());
+            assert_eq!(comment_data.comment, format!("Comment {}", expected_count));
+        }
+
+        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 3);
+    }
+
+    #[tokio::test]
+    async fn test_export_all_reviews() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let other_reviewer = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let review_ix = |reviewer: &Keypair, title: &str, rating: u8, description: &str| create_add_movie_review_ix(
+            reviewer.pubkey(), 
+            program_id, 
+            String::from(title), 
+            rating, 
+            String::from(description), 
+            mint, 
+            mint_auth, 
+            get_associated_token_address_with_program_id(&reviewer.pubkey(), &mint, &TOKEN_PROGRAM_ID), 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let review_pda = |reviewer: &Keypair, title: &str| Pubkey::find_program_address(
+            &[reviewer.pubkey().as_ref(), MovieAccountState::title_seed(title).as_bytes()], 
+            &program_id
+        ).0;
+
+        let exported = [("Alien", 5, "Tense"), ("Brazil", 4, "Strange"), ("Casablanca", 3, "Classic")];
+
+        let mut instructions = vec![init_mint_ix];
+
+        for reviewer in [&payer, &other_reviewer] {
+            instructions.push(spl_associated_token_account::instruction::create_associated_token_account(
+                &payer.pubkey(), 
+                &reviewer.pubkey(), 
+                &mint, 
+                &TOKEN_PROGRAM_ID,
+            ));
+        }
+
+        for (title, rating, description) in exported {
+            instructions.push(review_ix(&payer, title, rating, description));
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[review_ix(&other_reviewer, "Alien", 1, "Not for me")], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &other_reviewer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let reviews: Vec<Pubkey> = exported.iter()
+            .map(|(title, ..)| review_pda(&payer, title))
+            .collect();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_export_all_reviews_ix(program_id, payer.pubkey(), &reviews)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let simulation = banks_client.simulate_transaction(transaction).await.unwrap();
+        let logs = simulation.simulation_details.unwrap().logs;
+
+        let lines: Vec<Vec<&str>> = logs.iter()
+            .filter_map(|log| log.strip_prefix("Program log: EXPORT:"))
+            .map(|line| line.split(':').collect())
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        for (fields, (title, rating, description)) in lines.iter().zip(exported) {
+            assert_eq!(fields.len(), 4);
+            assert_eq!(fields[0], title);
+            assert_eq!(fields[1].parse::<u8>().unwrap(), rating);
+            assert_eq!(fields[2], description);
+            assert!(fields[3].parse::<i64>().is_ok());
+        }
+
+        // Another reviewer's review in the list fails the whole export
+        let mut transaction = Transaction::new_with_payer(
+            &[create_export_all_reviews_ix(
+                program_id, 
+                payer.pubkey(), 
+                &[reviews[0], review_pda(&other_reviewer, "Alien")],
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::Unauthorized as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_preserves_reviewer_and_created_at() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let mut context = program_test.start_with_context().await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+            &program_id
+        );
+        let (audit_pda, _audit_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"audit"], 
+            &program_id
+        );
+
+        let review_account = context.banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let before: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+
+        // Update a day later so a rewritten `created_at` would show
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += 24 * 60 * 60;
+        context.set_sysvar(&clock);
+
+        let recent_blockhash = context.banks_client.get_new_latest_blockhash(&context.last_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_movie_instruction(
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                5, 
+                String::from("Loved it on a rewatch"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = context.banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let after: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+
+        assert_eq!(after.rating, 5);
+        assert_eq!(after.description, "Loved it on a rewatch");
+
+        assert_eq!(after.reviewer, payer.pubkey());
+        assert_eq!(after.reviewer, before.reviewer);
+        assert_eq!(after.created_at, before.created_at);
+        assert_eq!(after.title, before.title);
+        assert_eq!(after.watched_at, before.watched_at);
+        assert_eq!(after.language_code, before.language_code);
+        assert_eq!(after.reward_minted, before.reward_minted);
+
+        // The update time lives in the audit log, not on the review
+        let audit_account = context.banks_client.get_account(audit_pda).await.unwrap().unwrap();
+        let audit_log: AuditLog = try_from_slice_unchecked(&audit_account.data).unwrap();
+        let entry = audit_log.entries.last().unwrap();
+        assert_eq!(entry.changed_at, clock.unix_timestamp);
+        assert_eq!(entry.rating, 5);
+    }
+
+    #[tokio::test]
+    async fn test_recompute_program_stats() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let other_reviewer = add_funded_keypair(&mut program_test);
+
+        let mut context = start_with_upgrade_authority(program_test, &program_id).await;
+        let payer = context.payer.insecure_clone();
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = |reviewer: &Keypair| get_associated_token_address_with_program_id(
+            &reviewer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let review_ix = |reviewer: &Keypair, title: &str| create_add_movie_review_ix(
+            reviewer.pubkey(), 
+            program_id, 
+            String::from(title), 
+            4, 
+            String::from("Watched it"), 
+            mint, 
+            mint_auth, 
+            user_ata(reviewer), 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let comment_ix = |title: &str, count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            String::from(title), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata(&payer), 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut instructions = vec![init_mint_ix, create_initialize_config_ix(payer.pubkey(), program_id)];
+
+        for reviewer in [&payer, &other_reviewer] {
+            instructions.push(spl_associated_token_account::instruction::create_associated_token_account(
+                &payer.pubkey(), 
+                &reviewer.pubkey(), 
+                &mint, 
+                &TOKEN_PROGRAM_ID,
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                review_ix(&payer, "Alien"),
+                review_ix(&payer, "Brazil"),
+                review_ix(&other_reviewer, "Alien"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &other_reviewer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[comment_ix("Alien", 0), comment_ix("Alien", 1), comment_ix("Brazil", 0)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut accounts = Vec::new();
+        for (reviewer, title) in [(&payer, "Alien"), (&payer, "Brazil"), (&other_reviewer, "Alien")] {
+            let (review_pda, _review_bump) = Pubkey::find_program_address(
+                &[reviewer.pubkey().as_ref(), MovieAccountState::title_seed(title).as_bytes()], 
+                &program_id
+            );
+            let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+                &[review_pda.as_ref(), b"comment"], 
+                &program_id
+            );
+
+            accounts.push(review_pda);
+            accounts.push(counter_pda);
+        }
+
+        // Corrupt the stats so they no longer match the accounts
+        let stats_pda = find_stats_pda(&program_id);
+        let mut stats_account = context.banks_client.get_account(stats_pda).await.unwrap().unwrap();
+        let mut stats_data: GlobalStats = try_from_slice_unchecked(&stats_account.data).unwrap();
+        let total_tokens_minted = stats_data.total_tokens_minted;
+        stats_data.total_reviews = 1;
+        stats_data.total_comments = 42;
+        stats_data.total_reviewers = 7;
+        stats_data.serialize(&mut &mut stats_account.data[..]).unwrap();
+        context.set_account(&stats_pda, &stats_account.into());
+
+        // Only the admin can recompute
+        let mut transaction = Transaction::new_with_payer(
+            &[create_recompute_program_stats_ix(other_reviewer.pubkey(), program_id, &accounts)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &other_reviewer], context.last_blockhash);
+
+        let err = context.banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+
+        // Duplicates and foreign accounts are skipped
+        accounts.push(accounts[0]);
+        accounts.push(find_config_pda(&program_id));
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_recompute_program_stats_ix(payer.pubkey(), program_id, &accounts)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], context.last_blockhash);
+
+        context.banks_client.process_transaction(transaction).await.unwrap();
+
+        let stats_account = context.banks_client.get_account(stats_pda).await.unwrap().unwrap();
+        let stats_data: GlobalStats = try_from_slice_unchecked(&stats_account.data).unwrap();
+        assert_eq!(stats_data.total_reviews, 3);
+        assert_eq!(stats_data.total_comments, 3);
+        assert_eq!(stats_data.total_reviewers, 2);
+        assert_eq!(stats_data.total_tokens_minted, total_tokens_minted);
+    }
+
+    #[cfg(not(feature = "token-2022"))]
+    #[tokio::test]
+    async fn test_separate_comment_mint() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (review_mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let rent = banks_client.get_rent().await.unwrap();
+
+        // Comments pay out in their own token; the foreign mint keeps its
+        // original authority
+        let comment_mint = Keypair::new();
+        let foreign_mint = Keypair::new();
+
+        let mut instructions = vec![init_mint_ix, create_initialize_config_ix(payer.pubkey(), program_id)];
+
+        for (mint, authority) in [(&comment_mint, mint_auth), (&foreign_mint, payer.pubkey())] {
+            instructions.push(solana_sdk::system_instruction::create_account(
+                &payer.pubkey(), 
+                &mint.pubkey(), 
+                rent.minimum_balance(token::state::Mint::LEN), 
+                token::state::Mint::LEN as u64, 
+                &TOKEN_PROGRAM_ID,
+            ));
+            instructions.push(initialize_mint2(
+                &TOKEN_PROGRAM_ID, 
+                &mint.pubkey(), 
+                &authority, 
+                None, 
+                REWARD_MINT_DECIMALS,
+            ).unwrap());
+        }
+
+        let mut transaction = Transaction::new_with_payer(
+            &instructions, 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &comment_mint, &foreign_mint], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_comment_mint_ix(payer.pubkey(), program_id, Some(foreign_mint.pubkey()))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidMintAuthority as u32))
+        );
+
+        let review_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &review_mint, &TOKEN_PROGRAM_ID
+        );
+        let comment_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &comment_mint.pubkey(), &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let comment_ix = |mint: Pubkey, user_ata: Pubkey, count: u64| create_add_comment_instruction(
+            payer.pubkey(), 
+            program_id, 
+            title.clone(), 
+            format!("Comment {}", count), 
+            count, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_comment_mint_ix(payer.pubkey(), program_id, Some(comment_mint.pubkey())),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &review_mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &comment_mint.pubkey(), 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    review_mint, 
+                    mint_auth, 
+                    review_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                comment_ix(comment_mint.pubkey(), comment_ata, 0),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_ata_account = banks_client.get_account(review_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&review_ata_account.data).unwrap(), REVIEW_REWARD);
+
+        let comment_ata_account = banks_client.get_account(comment_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&comment_ata_account.data).unwrap(), comment_reward(0));
+
+        // The review mint no longer pays comments
+        let mut transaction = Transaction::new_with_payer(
+            &[comment_ix(review_mint, review_ata, 1)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+
+        // Claimed rewards are paid in the review mint, so claiming can't be
+        // enabled alongside a comment mint
+        let mut transaction = Transaction::new_with_payer(
+            &[create_set_claim_rewards_ix(payer.pubkey(), program_id, true)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::ConflictingRewardSettings as u32))
+        );
+
+        // Until the comment mint is cleared
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_comment_mint_ix(payer.pubkey(), program_id, None),
+                comment_ix(review_mint, review_ata, 1),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_ata_account = banks_client.get_account(review_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&review_ata_account.data).unwrap(), REVIEW_REWARD + comment_reward(1));
+
+        // Nor can a comment mint be set while claiming is enabled
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_set_claim_rewards_ix(payer.pubkey(), program_id, true),
+                create_set_comment_mint_ix(payer.pubkey(), program_id, Some(comment_mint.pubkey())),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(1, InstructionError::Custom(ReviewError::ConflictingRewardSettings as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_reviewer_cannot_update_review() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let intruder = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let title = String::from("Captain America");
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let (review_pda, _review_bump) = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), MovieAccountState::title_seed(&title).as_bytes()], 
+            &program_id
+        );
+
+        let update_ix = || create_update_movie_instruction(
+            intruder.pubkey(), 
+            program_id, 
+            title.clone(), 
+            1, 
+            String::from("Hijacked"),
+        );
+
+        // (a) The reviewer's PDA with the intruder signing does not re-derive
+        let mut hijack_ix = update_ix();
+        hijack_ix.accounts[1].pubkey = review_pda;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[hijack_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &intruder], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+
+        // (b) The intruder's own PDA for the title was never created
+        let mut transaction = Transaction::new_with_payer(
+            &[update_ix()], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &intruder], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::InvalidAccountOwner)
+        );
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review.reviewer, payer.pubkey());
+        assert_eq!(review.rating, 3);
+        assert_eq!(review.description, "Liked the movie");
+    }
+
+    #[tokio::test]
+    async fn test_freeze_token_account() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title.to_string(), 
+            3, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Captain America"),
+                create_freeze_token_mint_ix(payer.pubkey(), program_id, user_ata),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Rewards cannot be minted into a frozen account
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix("Iron Man")], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(
+                0, 
+                InstructionError::Custom(token::error::TokenError::AccountFrozen as u32)
+            )
+        );
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), REVIEW_REWARD);
+
+        // Only the admin can thaw it
+        let stranger = Keypair::new();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_thaw_token_mint_ix(stranger.pubkey(), program_id, user_ata)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &stranger], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::IncorrectAccount as u32))
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_thaw_token_mint_ix(payer.pubkey(), program_id, user_ata),
+                add_review_ix("Iron Man"),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), 2 * REVIEW_REWARD);
+    }
+
+    #[tokio::test]
+    async fn test_review_length_boundaries() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: String| create_add_movie_review_ix(
+            payer.pubkey(), 
+            program_id, 
+            title, 
+            4, 
+            String::from("Liked the movie"), 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID
+        );
+
+        let title = "a".repeat(MAX_SEED_LEN);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix(title.clone()),
+            ], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // Descriptions this long only fit in an update transaction
+        let room = MovieAccountState::LEN
+            - MovieAccountState::get_account_size(title.clone(), String::new(), None, Vec::new()).unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_movie_instruction(payer.pubkey(), program_id, title.clone(), 4, "a".repeat(room))], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_pda = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), title.as_bytes()], 
+            &program_id
+        ).0;
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review.description.len(), room);
+        assert_eq!(review.account_len().unwrap(), MovieAccountState::LEN);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_update_movie_instruction(payer.pubkey(), program_id, title, 4, "a".repeat(room + 1))], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidDataLength as u32))
+        );
+
+        // Titles are PDA seeds, so one byte over the seed limit is rejected
+        // as malformed before any size check
+        let mut transaction = Transaction::new_with_payer(
+            &[add_review_ix("a".repeat(MAX_SEED_LEN + 1))], 
+            Some(&payer.pubkey())
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(err, TransactionError::InstructionError(0, InstructionError::InvalidInstructionData));
+    }
+
+    #[tokio::test]
+    async fn test_review_tags() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let add_review_ix = |title: &str, tags: Vec<String>| create_add_movie_review_with_payload_ix(
+            payer.pubkey(), 
+            program_id, 
+            AddMovieReviewPayload {
+                tight_size: true,
+                tags,
+                ..AddMovieReviewPayload::new(title.to_string(), 3, String::from("Liked the movie"))
+            }, 
+            mint, 
+            mint_auth, 
+            user_ata, 
+            system_program::ID, 
+            TOKEN_PROGRAM_ID,
+        );
+
+        let update_review_ix = |tags: Option<Vec<String>>| create_update_movie_with_payload_ix(
+            payer.pubkey(), 
+            program_id, 
+            MovieReviewPayload {
+                tags,
+                ..MovieReviewPayload::new(String::from("Captain America"), 4, String::from("Liked the movie"))
+            },
+        );
+
+        let review_pda = Pubkey::find_program_address(
+            &[payer.pubkey().as_ref(), b"Captain America"], 
+            &program_id
+        ).0;
+
+        let tags = vec![String::from("classic"), String::from("rewatch"), String::from("marvel")];
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                add_review_ix("Captain America", tags.clone()),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.tags, tags);
+        assert_eq!(review_account.data.len(), review_data.account_len().unwrap());
+
+        // Too many tags, an oversized tag and an empty one are rejected
+        let max_len_tag = "a".repeat(MovieAccountState::MAX_TAG_LEN);
+
+        for tags in [
+            vec![String::from("classic"); MovieAccountState::MAX_TAGS + 1],
+            vec![format!("{}a", max_len_tag)],
+            vec![String::new()],
+        ] {
+            let mut transaction = Transaction::new_with_payer(
+                &[add_review_ix("Thor", tags)], 
+                Some(&payer.pubkey()),
+            );
+            transaction.sign(&[&payer], recent_blockhash);
+
+            let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+            assert_eq!(
+                err, 
+                TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidTags as u32))
+            );
+        }
+
+        // Updating without tags keeps the current ones
+        let mut transaction = Transaction::new_with_payer(&[update_review_ix(None)], Some(&payer.pubkey()));
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.tags, tags);
+        assert_eq!(review_data.rating, 4);
+
+        // A full set of tags at the length limit grows the tight account
+        let full_tags = vec![max_len_tag; MovieAccountState::MAX_TAGS];
+
+        let mut transaction = Transaction::new_with_payer(
+            &[update_review_ix(Some(full_tags.clone()))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let grown_len = MovieAccountState::get_account_size(
+            String::from("Captain America"), 
+            String::from("Liked the movie"),
+            None,
+            full_tags.clone(),
+        ).unwrap();
+
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        assert_eq!(review_account.data.len(), grown_len);
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.tags, full_tags);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[update_review_ix(Some(vec![String::from("classic"); MovieAccountState::MAX_TAGS + 1]))], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidTags as u32))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approve_pending_comment() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        let commenter = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let payer_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let commenter_ata = get_associated_token_address_with_program_id(
+            &commenter.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    payer_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                create_set_comment_moderation_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    MovieAccountState::COMMENTS_REQUIRE_APPROVAL,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_add_pending_comment_ix(
+                commenter.pubkey(), 
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                String::from("Totally agree!"), 
+                mint, 
+                mint_auth, 
+                commenter_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &commenter], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_pda = find_review_pda(&payer.pubkey(), &title, &program_id);
+        let pending_pda = find_pending_comment_pda(&review_pda, &commenter.pubkey(), &program_id);
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), &0u64.to_be_bytes()], 
+            &program_id
+        );
+
+        let pending_account = banks_client.get_account(pending_pda).await.unwrap().unwrap();
+        let pending_data: PendingComment = try_from_slice_unchecked(&pending_account.data).unwrap();
+        assert_eq!(pending_data.review, review_pda);
+        assert_eq!(pending_data.submitter, commenter.pubkey());
+        assert_eq!(pending_data.comment, "Totally agree!");
+
+        assert!(banks_client.get_account(comment_pda).await.unwrap().is_none());
+
+        // Only the reviewer can approve
+        let mut transaction = Transaction::new_with_payer(
+            &[create_approve_pending_comment_ix(commenter.pubkey(), program_id, commenter.pubkey(), title.clone(), 0)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &commenter], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
+
+        let commenter_lamports = banks_client.get_account(commenter.pubkey()).await.unwrap().unwrap().lamports;
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_approve_pending_comment_ix(payer.pubkey(), program_id, commenter.pubkey(), title.clone(), 0)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let comment_account = banks_client.get_account(comment_pda).await.unwrap().unwrap();
+        let comment_data: MovieComment = try_from_slice_unchecked(&comment_account.data).unwrap();
+        assert_eq!(comment_data.review, review_pda);
+        assert_eq!(comment_data.commenter, commenter.pubkey());
+        assert_eq!(comment_data.comment, "Totally agree!");
+        assert_eq!(comment_data.count, 0);
+
+        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 1);
+
+        // The pending account is closed and its rent goes back to the commenter
+        assert!(banks_client.get_account(pending_pda).await.unwrap().is_none());
+        assert_eq!(
+            banks_client.get_account(commenter.pubkey()).await.unwrap().unwrap().lamports,
+            commenter_lamports + pending_account.lamports
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reject_pending_comment() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
+        );
+        let commenter = add_funded_keypair(&mut program_test);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
+
+        let title = String::from("Captain America");
+
+        let payer_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let commenter_ata = get_associated_token_address_with_program_id(
+            &commenter.pubkey(), &mint, &TOKEN_PROGRAM_ID
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    payer_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+                create_set_comment_moderation_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    MovieAccountState::COMMENTS_REQUIRE_APPROVAL,
+                ),
+            ], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        // The commenter funds the pending account; the payer only pays fees
+        let mut transaction = Transaction::new_with_payer(
+            &[create_add_pending_comment_ix(
+                commenter.pubkey(), 
+                payer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                String::from("Spoiler: he lives"), 
+                mint, 
+                mint_auth, 
+                commenter_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID,
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &commenter], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let review_pda = find_review_pda(&payer.pubkey(), &title, &program_id);
+        let pending_pda = find_pending_comment_pda(&review_pda, &commenter.pubkey(), &program_id);
+
+        let commenter_account = banks_client.get_account(commenter.pubkey()).await.unwrap().unwrap();
+        assert!(commenter_account.lamports < sol_to_lamports(1.0));
+        assert!(banks_client.get_account(pending_pda).await.unwrap().is_some());
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_reject_pending_comment_ix(
+                payer.pubkey(), 
+                program_id, 
+                commenter.pubkey(), 
+                title.clone(), 
+                String::from("Spoilers"),
+            )], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
 
-    use borsh::BorshDeserialize;
+        assert!(banks_client.get_account(pending_pda).await.unwrap().is_none());
 
-    use solana_program_test::*;
+        let commenter_account = banks_client.get_account(commenter.pubkey()).await.unwrap().unwrap();
+        assert_eq!(commenter_account.lamports, sol_to_lamports(1.0));
 
-    use solana_sdk::{
-        signature::Signer,
-        instruction::{Instruction, AccountMeta},
-        system_program,
-        transaction::Transaction,
-    };
+        // Nothing was stored as a comment
+        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), b"comment"], 
+            &program_id
+        );
+        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
+        assert_eq!(counter_data.counter, 0);
+    }
+
+    // Every handler with a fixed account list rejects one account short of
+    // it up front, with nothing else set up. The builders pass at least the
+    // required count, so the counts stay in step with the real layouts.
+    #[test]
+    fn test_short_account_lists_fail_up_front() {
+        let program_id = Pubkey::new_unique();
+        let reviewer = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let title = || String::from("Captain America");
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(reviewer, &program_id);
+        let user_ata = get_associated_token_address_with_program_id(&reviewer, &mint, &TOKEN_PROGRAM_ID);
+        let review = find_review_pda(&reviewer, &title(), &program_id);
+
+        let cases = vec![
+            (create_add_movie_review_ix(
+                reviewer, program_id, title(), 5, String::from("Liked it"), mint, mint_auth, user_ata,
+                system_program::ID, TOKEN_PROGRAM_ID,
+            ), 11),
+            (create_update_movie_instruction(reviewer, program_id, title(), 3, String::from("Less so")), 4),
+            (create_add_comment_instruction(
+                reviewer, program_id, title(), String::from("Agreed"), 0, mint, mint_auth, user_ata,
+                system_program::ID, TOKEN_PROGRAM_ID,
+            ), 11),
+            (init_mint_ix, 5),
+            (create_migrate_legacy_review_ix(reviewer, program_id, title()), 3),
+            (create_initialize_config_ix(reviewer, program_id), 4),
+            (create_set_reward_mint_ix(reviewer, program_id, mint), 3),
+            (create_claim_rewards_ix(reviewer, program_id, mint, user_ata), 7),
+            (create_set_claim_rewards_ix(reviewer, program_id, true), 2),
+            (create_delete_movie_review_ix(reviewer, program_id, title(), mint, user_ata), 8),
+            (create_set_clawback_period_ix(reviewer, program_id, 86_400), 2),
+            (create_approve_comment_ix(reviewer, program_id, title(), 0), 5),
+            (create_backfill_reward_ix(reviewer, program_id, review, mint, user_ata), 7),
+            (create_set_skip_revoked_rewards_ix(reviewer, program_id, true), 2),
+            (create_sync_comment_counter_ix(program_id, reviewer, title(), &[]), 2),
+            (create_update_comment_text_ix(other, program_id, reviewer, title(), 0, String::from("Edited")), 4),
+            (create_rename_review_ix(reviewer, program_id, title(), String::from("Iron Man")), 6),
+            (create_set_min_description_len_ix(reviewer, program_id, 10), 2),
+            (create_change_review_language_ix(reviewer, program_id, title(), *b"fr"), 2),
+            (create_gift_tokens_ix(reviewer, program_id, other, 1, mint), 5),
+            (create_batch_create_atas_ix(reviewer, program_id, vec![other], mint), 7),
+            (create_view_audit_log_ix(reviewer, program_id, title()), 3),
+            (create_series_ix(reviewer, program_id, String::from("MCU")), 3),
+            (create_add_to_series_ix(reviewer, program_id, String::from("MCU"), title()), 3),
+            (create_finalize_series_ix(reviewer, program_id, String::from("MCU")), 2),
+            (create_react_to_review_ix(other, program_id, reviewer, title(), 0), 4),
+            (create_set_comment_moderation_ix(reviewer, program_id, title(), MovieAccountState::COMMENTS_OPEN), 2),
+            (create_nominate_review_ix(other, program_id, review, reviewer, title(), String::from("Best Picture"), 2024), 5),
+            (create_vote_for_nomination_ix(other, program_id, review, reviewer, title(), 2024), 5),
+            (create_declare_award_winner_ix(reviewer, program_id, String::from("Best Picture"), 2024, &[]), 4),
+            (create_sync_counter_ix(reviewer, program_id, reviewer, title(), &[]), 4),
+            (create_update_merkle_root_ix(reviewer, program_id, reviewer, title(), [1; 32], 1), 5),
+            (create_verify_comment_inclusion_ix(program_id, reviewer, title(), other, vec![[2; 32]]), 2),
+            (create_set_callback_program_ix(reviewer, program_id, Some(other)), 2),
+            (create_mark_sponsored_ix(reviewer, program_id, title()), 2),
+            (create_update_tag_cloud_ix(reviewer, program_id, title()), 5),
+            (create_mint_first_review_badge_ix(reviewer, program_id, reviewer), 9),
+            (create_fetch_comments_ix(reviewer, program_id, title(), 0, 1), 3),
+            (create_verify_program_version_ix(program_id, [0; 32]), 1),
+            (create_propose_authority_transfer_ix(reviewer, program_id, other), 5),
+            (create_accept_authority_transfer_ix(other, program_id), 4),
+            (create_set_auto_verify_threshold_ix(reviewer, program_id, 2), 2),
+            (create_collection_ix(reviewer, program_id, 0, String::from("MCU"), String::from("Every MCU film")), 4),
+            (create_add_review_to_collection_ix(reviewer, program_id, 0, title()), 3),
+            (create_remove_review_from_collection_ix(reviewer, program_id, 0, title()), 2),
+            (create_compact_comment_ix(reviewer, program_id, reviewer, title(), 1, 0), 7),
+            (create_send_direct_message_ix(reviewer, program_id, other, String::from("Great take"), 0), 4),
+            (create_mark_message_read_ix(other, program_id, reviewer, 0), 2),
+            (create_set_max_reward_amount_ix(reviewer, program_id, 1_000_000_000), 2),
+            (create_recompute_program_stats_ix(reviewer, program_id, &[]), 3),
+            (create_set_comment_mint_ix(reviewer, program_id, Some(mint)), 3),
+            (create_freeze_token_mint_ix(reviewer, program_id, user_ata), 6),
+            (create_thaw_token_mint_ix(reviewer, program_id, user_ata), 6),
+            (create_approve_pending_comment_ix(reviewer, program_id, other, title(), 0), 8),
+            (create_reject_pending_comment_ix(reviewer, program_id, other, title(), String::new()), 4),
+        ];
+
+        let owner = system_program::ID;
+
+        for (instruction, required) in cases {
+            let discriminator = instruction.data[0];
+            assert!(
+                instruction.accounts.len() >= required,
+                "instruction {} builder passes {} accounts, {} required",
+                discriminator,
+                instruction.accounts.len(),
+                required
+            );
+
+            let keys: Vec<Pubkey> = instruction.accounts[..required - 1].iter().map(|meta| meta.pubkey).collect();
+            let mut lamports = vec![0; keys.len()];
+            let mut data = vec![Vec::new(); keys.len()];
+
+            let accounts: Vec<AccountInfo> = keys.iter()
+                .zip(lamports.iter_mut())
+                .zip(data.iter_mut())
+                .map(|((key, lamports), data)| account_info(key, false, lamports, data, &owner))
+                .collect();
+
+            assert_eq!(
+                process_instruction(&program_id, &accounts, &instruction.data),
+                Err(ProgramError::NotEnoughAccountKeys),
+                "instruction {}",
+                discriminator
+            );
+        }
+    }
 
     #[tokio::test]
-    async fn test_initialize_mint_instruction() {
+    async fn test_initialize_config_requires_upgrade_authority() {
         let program_id = Pubkey::new_unique();
 
         let mut program_test = ProgramTest::default();
         program_test.add_program(
-            "movie_review_program",
-            program_id,
+            "movie_review_program", 
+            program_id, 
             processor!(process_instruction)
         );
 
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let impostor = add_funded_keypair(&mut program_test);
 
-        let (_mint, _mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
 
         let mut transaction = Transaction::new_with_payer(
-            &[init_mint_ix], 
-            Some(&payer.pubkey())
+            &[create_initialize_config_ix(impostor.pubkey(), program_id)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &impostor], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::Unauthorized as u32))
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[create_initialize_config_ix(payer.pubkey(), program_id)], 
+            Some(&payer.pubkey()),
         );
         transaction.sign(&[&payer], recent_blockhash);
 
-        let transaction_result = banks_client.process_transaction(transaction).await;
+        banks_client.process_transaction(transaction).await.unwrap();
 
-        assert!(transaction_result.is_ok());
+        let config_account = banks_client.get_account(find_config_pda(&program_id)).await.unwrap().unwrap();
+        let config: ProgramConfig = try_from_slice_unchecked(&config_account.data).unwrap();
+        assert_eq!(config.admin, payer.pubkey());
     }
 
     #[tokio::test]
-    async fn test_add_movie_review_instruction() {
+    async fn test_add_comment_rejects_foreign_counter() {
         let program_id = Pubkey::new_unique();
 
         let mut program_test = ProgramTest::default();
@@ -554,53 +14599,78 @@ mod tests {
             program_id, 
             processor!(process_instruction)
         );
-        
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        let title = String::from("Captain America");
-        let rating: u8 = 3;
-        let description =  String::from("Liked the movie");
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
+        );
 
         let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
             &payer.pubkey(), 
             &payer.pubkey(), 
             &mint, 
-            &spl_token::ID
+            &TOKEN_PROGRAM_ID,
         );
 
-        let user_ata = spl_associated_token_account::get_associated_token_address(
-            &payer.pubkey(), &mint
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
         );
 
-        let add_movie_review_ix = create_add_movie_review_ix(
+        let mut instructions = vec![init_mint_ix, create_ata_ix];
+        for title in ["Captain America", "Iron Man"] {
+            instructions.push(create_add_movie_review_ix(
+                payer.pubkey(), 
+                program_id, 
+                String::from(title), 
+                3, 
+                String::from("Liked the movie"), 
+                mint, 
+                mint_auth, 
+                user_ata, 
+                system_program::ID, 
+                TOKEN_PROGRAM_ID
+            ));
+        }
+
+        let mut transaction = Transaction::new_with_payer(&instructions, Some(&payer.pubkey()));
+        transaction.sign(&[&payer], recent_blockhash);
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let other_review = find_review_pda(&payer.pubkey(), "Iron Man", &program_id);
+        let (other_counter, _) = Pubkey::find_program_address(
+            &[other_review.as_ref(), b"comment"], 
+            &program_id
+        );
+
+        // A real counter, but one belonging to another review
+        let mut add_comment_ix = create_add_comment_instruction(
             payer.pubkey(), 
-            program_id, 
-            title, 
-            rating, 
-            description, 
+            program_id,
+            String::from("Captain America"),
+            String::from("Totally agree!"), 
+            0, 
             mint, 
             mint_auth, 
             user_ata, 
             system_program::ID, 
-            spl_token::ID
-        );
-
-        let mut transaction = Transaction::new_with_payer(
-            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
-            Some(&payer.pubkey())
+            TOKEN_PROGRAM_ID,
         );
+        add_comment_ix.accounts[2].pubkey = other_counter;
 
+        let mut transaction = Transaction::new_with_payer(&[add_comment_ix], Some(&payer.pubkey()));
         transaction.sign(&[&payer], recent_blockhash);
 
-        let transaction_result = banks_client.process_transaction(transaction).await;
-
-        assert!(transaction_result.is_ok());
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::InvalidPDA as u32))
+        );
     }
 
     #[tokio::test]
-    async fn test_update_movie_review_instruction() {
+    async fn test_migrate_baseline_review() {
         let program_id = Pubkey::new_unique();
 
         let mut program_test = ProgramTest::default();
@@ -610,75 +14680,92 @@ mod tests {
             processor!(process_instruction)
         );
 
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-
-        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
-            payer.pubkey(),
-            &program_id
-        );
+        let reviewer = add_funded_keypair(&mut program_test);
 
         let title = String::from("Captain America");
-        let rating: u8 = 3;
-        let description = String::from("Liked the movie");
-
-        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
-            &payer.pubkey(), 
-            &payer.pubkey(), 
-            &mint, 
-            &spl_token::ID
+        let review_pda = find_review_pda(&reviewer.pubkey(), &title, &program_id);
+
+        // Padded to `LEN` like the original handler allocated it
+        let mut baseline_data = borsh::to_vec(&BaselineMovieAccountState {
+            discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            reviewer: reviewer.pubkey(),
+            rating: 4,
+            title: title.clone(),
+            description: String::from("Liked the movie"),
+        }).unwrap();
+        baseline_data.resize(MovieAccountState::LEN, 0);
+
+        program_test.add_account(
+            review_pda, 
+            Account {
+                lamports: Rent::default().minimum_balance(baseline_data.len()),
+                data: baseline_data,
+                owner: program_id,
+                ..Account::default()
+            },
         );
 
-        let user_ata = spl_associated_token_account::get_associated_token_address(
-            &payer.pubkey(), &mint
-        );
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        let add_movie_review_ix = create_add_movie_review_ix(
-            payer.pubkey(), 
+        let migrate_ix = create_migrate_legacy_review_ix(
+            reviewer.pubkey(), 
             program_id, 
-            title.clone(), 
-            rating, 
-            description, 
-            mint, 
-            mint_auth, 
-            user_ata, 
-            system_program::ID, 
-            spl_token::ID
+            title.clone(),
         );
 
         let mut transaction = Transaction::new_with_payer(
-            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
-            Some(&payer.pubkey())
+            std::slice::from_ref(&migrate_ix), 
+            Some(&payer.pubkey()),
         );
-
-        transaction.sign(&[&payer], recent_blockhash);
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
 
         banks_client.process_transaction(transaction).await.unwrap();
 
-        let new_rating: u8 = 2;
-        let new_description =  String::from("Didn't like the movie");
-        
-        let update_movie_review_ix = create_update_movie_instruction(
-            payer.pubkey(), 
-            program_id, 
-            title.clone(), 
-            new_rating, 
-            new_description,
-        );
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.version, MovieAccountState::VERSION);
+        assert_eq!(review_data.reviewer, reviewer.pubkey());
+        assert_eq!(review_data.rating, 4);
+        assert_eq!(review_data.language_code, MovieAccountState::DEFAULT_LANGUAGE_CODE);
+        assert_eq!(review_data.title, title);
+        assert_eq!(review_data.description, "Liked the movie");
 
         let mut transaction = Transaction::new_with_payer(
-            &[update_movie_review_ix], 
-            Some(&payer.pubkey())
+            &[create_update_movie_instruction(
+                reviewer.pubkey(), 
+                program_id, 
+                title.clone(), 
+                2, 
+                String::from("Didn't like the movie"),
+            )], 
+            Some(&payer.pubkey()),
         );
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
 
-        transaction.sign(&[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
 
-        let transaction_result = banks_client.process_transaction(transaction).await;
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert_eq!(review_data.rating, 2);
 
-        assert!(transaction_result.is_ok());
+        let recent_blockhash = banks_client.get_new_latest_blockhash(&recent_blockhash).await.unwrap();
+
+        let mut transaction = Transaction::new_with_payer(
+            &[migrate_ix], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
+
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::AccountAlreadyInitialized)
+        );
     }
 
     #[tokio::test]
-    async fn test_add_comment_instruction() {
+    async fn test_delete_review_clawback_in_claim_mode() {
         let program_id = Pubkey::new_unique();
 
         let mut program_test = ProgramTest::default();
@@ -688,255 +14775,209 @@ mod tests {
             processor!(process_instruction)
         );
 
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
 
         let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
             payer.pubkey(), &program_id
         );
 
         let title = String::from("Captain America");
-        let rating: u8 = 3;
-        let description = String::from("Liked the movie");
-        
-        let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
-            &payer.pubkey(), 
-            &payer.pubkey(), 
-            &mint, 
-            &spl_token::ID,
-        );
+        let tracker = find_reward_tracker_pda(&payer.pubkey(), &program_id);
 
-        let user_ata = spl_associated_token_account::get_associated_token_address(
-            &payer.pubkey(), 
-            &mint
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                create_set_claim_rewards_ix(payer.pubkey(), program_id, true),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    3, 
+                    String::from("Liked the movie"), 
+                    mint, 
+                    mint_auth, 
+                    tracker, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
         );
+        transaction.sign(&[&payer], recent_blockhash);
 
-        let add_movie_review_ix = create_add_movie_review_ix(
-            payer.pubkey(), 
-            program_id, 
-            title.clone(), 
-            rating, 
-            description, 
-            mint, 
-            mint_auth, 
-            user_ata, 
-            system_program::ID, 
-            spl_token::ID
-        );
+        banks_client.process_transaction(transaction).await.unwrap();
 
+        let tracker_account = banks_client.get_account(tracker).await.unwrap().unwrap();
+        let tracker_data: RewardTracker = try_from_slice_unchecked(&tracker_account.data).unwrap();
+        assert_eq!(tracker_data.unclaimed_rewards, REVIEW_REWARD);
+
+        // Nothing was minted, so the unclaimed balance is what gets clawed back
         let mut transaction = Transaction::new_with_payer(
-            &[init_mint_ix, create_ata_ix, add_movie_review_ix], 
+            &[create_delete_movie_review_ix(payer.pubkey(), program_id, title, mint, tracker)], 
             Some(&payer.pubkey()),
         );
-
         transaction.sign(&[&payer], recent_blockhash);
 
         banks_client.process_transaction(transaction).await.unwrap();
 
-        let comment = String::from("Totally agree!");
+        let tracker_account = banks_client.get_account(tracker).await.unwrap().unwrap();
+        let tracker_data: RewardTracker = try_from_slice_unchecked(&tracker_account.data).unwrap();
+        assert_eq!(tracker_data.unclaimed_rewards, 0);
 
-        let (review_pda, _review_bump) = Pubkey::find_program_address(
-            &[payer.pubkey().as_ref(), title.as_bytes()], 
-            &program_id
+        let stats_account = banks_client.get_account(find_stats_pda(&program_id)).await.unwrap().unwrap();
+        let stats_data: GlobalStats = try_from_slice_unchecked(&stats_account.data).unwrap();
+        assert_eq!(stats_data.total_reviews, 0);
+        assert_eq!(stats_data.total_tokens_minted, 0);
+    }
+
+    #[tokio::test]
+    async fn test_delete_unrewarded_review_skips_clawback() {
+        let program_id = Pubkey::new_unique();
+
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
+            program_id, 
+            processor!(process_instruction)
         );
 
-        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
-            &[review_pda.as_ref(), b"comment"], 
-            &program_id
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
+
+        let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(
+            payer.pubkey(), &program_id
         );
 
-        let counter_account = banks_client.get_account(counter_pda).await.unwrap().unwrap();
+        let title = String::from("Captain America");
 
-        let counter_data: MovieCommentCounter = try_from_slice_unchecked(&counter_account.data).unwrap();
-        
-        let add_comment_ix = create_add_comment_instruction(
-            payer.pubkey(), 
-            program_id,
-            title.clone(),
-            comment, 
-            counter_data.counter, 
-            mint, 
-            mint_auth, 
-            user_ata, 
-            system_program::ID, 
-            spl_token::ID,
+        let user_ata = get_associated_token_address_with_program_id(
+            &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
         );
 
+        // Too short to earn the review reward
         let mut transaction = Transaction::new_with_payer(
-            &[add_comment_ix], 
-            Some(&payer.pubkey())
+            &[
+                init_mint_ix,
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(), 
+                    &payer.pubkey(), 
+                    &mint, 
+                    &TOKEN_PROGRAM_ID,
+                ),
+                create_set_min_description_len_ix(payer.pubkey(), program_id, 15),
+                create_add_movie_review_ix(
+                    payer.pubkey(), 
+                    program_id, 
+                    title.clone(), 
+                    4, 
+                    String::from("Great"), 
+                    mint, 
+                    mint_auth, 
+                    user_ata, 
+                    system_program::ID, 
+                    TOKEN_PROGRAM_ID
+                ),
+            ], 
+            Some(&payer.pubkey()),
         );
-
         transaction.sign(&[&payer], recent_blockhash);
 
-        let transaction_result = banks_client.process_transaction(transaction).await;
-
-        assert!(transaction_result.is_ok());
-    }
+        banks_client.process_transaction(transaction).await.unwrap();
 
-    fn create_init_mint_ix(payer: Pubkey, program_id: &Pubkey) -> (Pubkey, Pubkey, Instruction) {
-        let (mint, _mint_bump) = Pubkey::find_program_address(
-            &[b"token_mint"], program_id
-        );
-        let (mint_auth, _mint_auth_bump) = Pubkey::find_program_address(
-            &[b"token_auth"], 
-            program_id
-        );
+        let ata_account = banks_client.get_account(user_ata).await.unwrap().unwrap();
+        assert_eq!(token_account_balance(&ata_account.data).unwrap(), 0);
 
-        let init_mint_ix = Instruction::new_with_borsh(
-            *program_id, 
-            &3, 
-            vec![
-                AccountMeta::new_readonly(payer, true),
-                AccountMeta::new(mint, false),
-                AccountMeta::new_readonly(mint_auth, false),
-                AccountMeta::new_readonly(system_program::ID, false),
-                AccountMeta::new_readonly(spl_token::ID, false),
-            ],
+        // Within the grace period, but there is no reward to claw back
+        let mut transaction = Transaction::new_with_payer(
+            &[create_delete_movie_review_ix(payer.pubkey(), program_id, title.clone(), mint, user_ata)], 
+            Some(&payer.pubkey()),
         );
+        transaction.sign(&[&payer], recent_blockhash);
 
-        (mint, mint_auth, init_mint_ix)
-    }
+        banks_client.process_transaction(transaction).await.unwrap();
 
-    fn create_add_movie_review_ix(
-        payer: Pubkey,
-        program_id: Pubkey,
-        title: String,
-        rating: u8,
-        description: String,
-        mint: Pubkey,
-        mint_auth: Pubkey,
-        user_ata: Pubkey,
-        system_program: Pubkey,
-        token_program: Pubkey
-    ) -> Instruction {
-        let (review_pda, _review_bump) = Pubkey::find_program_address(
-            &[payer.as_ref(), title.as_bytes()], 
-            &program_id
-        );
+        let review_pda = find_review_pda(&payer.pubkey(), &title, &program_id);
+        assert!(banks_client.get_account(review_pda).await.unwrap().is_none());
 
-        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
-            &[review_pda.as_ref(), b"comment"], 
-            &program_id
-        );
+        let stats_account = banks_client.get_account(find_stats_pda(&program_id)).await.unwrap().unwrap();
+        let stats_data: GlobalStats = try_from_slice_unchecked(&stats_account.data).unwrap();
+        assert_eq!(stats_data.total_tokens_minted, 0);
+    }
 
-        let movie_review_payload = MovieReviewPayload {
-            discriminator: 0,
-            title,
-            rating,
-            description
-        };
+    #[tokio::test]
+    async fn test_backfill_rejects_migrated_baseline_review() {
+        let program_id = Pubkey::new_unique();
 
-        Instruction::new_with_borsh(
+        let mut program_test = ProgramTest::default();
+        program_test.add_program(
+            "movie_review_program", 
             program_id, 
-            &movie_review_payload, 
-            vec![
-                AccountMeta::new_readonly(payer, true),
-                AccountMeta::new(review_pda, false),
-                AccountMeta::new(counter_pda, false),
-                AccountMeta::new(mint, false),
-                AccountMeta::new_readonly(mint_auth, false),
-                AccountMeta::new(user_ata, false),
-                AccountMeta::new_readonly(system_program, false),
-                AccountMeta::new_readonly(token_program, false),
-            ]
-        )
-    }
-
-    fn create_update_movie_instruction(
-        payer: Pubkey,
-        program_id: Pubkey,
-        title: String,
-        rating: u8,
-        description: String,
-    ) -> Instruction {
-        let (review_pda, _review_bump) = Pubkey::find_program_address(
-            &[payer.as_ref(), title.as_bytes()], &program_id
+            processor!(process_instruction)
         );
 
-        let movie_review_payload = MovieReviewPayload {
-            discriminator: 1,
-            title,
-            rating,
-            description,
-        };
+        let reviewer = add_funded_keypair(&mut program_test);
 
-        Instruction::new_with_borsh(
-            program_id, 
-            &movie_review_payload, 
-            vec![
-                AccountMeta::new_readonly(payer, true),
-                AccountMeta::new(review_pda, false)
-            ]
-        )
-    }
-
-    fn create_add_comment_instruction(
-        payer: Pubkey,
-        program_id: Pubkey,
-        title: String,
-        comment: String,
-        comment_count: u64,
-        mint: Pubkey,
-        mint_auth: Pubkey,
-        user_ata: Pubkey,
-        system_program: Pubkey,
-        token_program: Pubkey
-    ) -> Instruction {
-        let (review_pda, _review_bump) = Pubkey::find_program_address(
-            &[payer.as_ref(), title.as_bytes()], 
-            &program_id
+        let title = String::from("Captain America");
+        let review_pda = find_review_pda(&reviewer.pubkey(), &title, &program_id);
+
+        let mut baseline_data = borsh::to_vec(&BaselineMovieAccountState {
+            discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            reviewer: reviewer.pubkey(),
+            rating: 4,
+            title: title.clone(),
+            description: String::from("Liked the movie"),
+        }).unwrap();
+        baseline_data.resize(MovieAccountState::LEN, 0);
+
+        program_test.add_account(
+            review_pda, 
+            Account {
+                lamports: Rent::default().minimum_balance(baseline_data.len()),
+                data: baseline_data,
+                owner: program_id,
+                ..Account::default()
+            },
         );
 
-        let (counter_pda, _counter_bump) = Pubkey::find_program_address(
-            &[review_pda.as_ref(), b"comment"], 
-            &program_id
-        );
+        let context = start_with_upgrade_authority(program_test, &program_id).await;
+        let (mut banks_client, payer, recent_blockhash) =
+            (context.banks_client.clone(), context.payer.insecure_clone(), context.last_blockhash);
 
-        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
-            &[review_pda.as_ref(), &comment_count.to_be_bytes()], 
-            &program_id
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                create_initialize_config_ix(payer.pubkey(), program_id),
+                create_migrate_legacy_review_ix(reviewer.pubkey(), program_id, title.clone()),
+            ], 
+            Some(&payer.pubkey()),
         );
+        transaction.sign(&[&payer, &reviewer], recent_blockhash);
 
-        let comment_payload = CommentPayload {
-            discriminator: 2,
-            comment,
-        };
+        banks_client.process_transaction(transaction).await.unwrap();
 
-        Instruction::new_with_borsh(
-            program_id, 
-            &comment_payload, 
-            vec![
-                AccountMeta::new_readonly(payer, true),
-                AccountMeta::new_readonly(review_pda, false),
-                AccountMeta::new(counter_pda, false),
-                AccountMeta::new(comment_pda, false),
-                AccountMeta::new(mint, false),
-                AccountMeta::new_readonly(mint_auth, false),
-                AccountMeta::new(user_ata, false),
-                AccountMeta::new_readonly(system_program, false),
-                AccountMeta::new_readonly(token_program, false),
-            ]
-        )
-    }
+        // The baseline handler already paid this review's reward
+        let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+        let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+        assert!(review_data.reward_minted);
 
-    #[derive(BorshSerialize)]
-    struct MovieReviewPayload {
-        discriminator: u8,
-        title: String,
-        rating: u8,
-        description: String,
-    }
+        let (mint, _mint_bump) = mint_pda(&program_id);
+        let reviewer_ata = get_associated_token_address_with_program_id(&reviewer.pubkey(), &mint, &TOKEN_PROGRAM_ID);
 
-    #[derive(BorshSerialize)]
-    struct CommentPayload {
-        discriminator: u8,
-        comment: String,
-    }
+        let mut transaction = Transaction::new_with_payer(
+            &[create_backfill_reward_ix(payer.pubkey(), program_id, review_pda, mint, reviewer_ata)], 
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
 
-    #[derive(BorshDeserialize, Debug)]
-    struct MovieCommentCounter {
-        discriminator: String,
-        is_initialized: bool,
-        counter: u64,
+        let err = banks_client.process_transaction(transaction).await.unwrap_err().unwrap();
+        assert_eq!(
+            err, 
+            TransactionError::InstructionError(0, InstructionError::Custom(ReviewError::RewardAlreadyMinted as u32))
+        );
     }
 }
\ No newline at end of file
@@ -1,18 +1,43 @@
+use std::borrow::Cow;
+
 use borsh::{BorshSerialize, BorshDeserialize};
 
 use solana_program::{
-    pubkey::Pubkey,
+    pubkey::{Pubkey, MAX_SEED_LEN},
     program_pack::{IsInitialized, Sealed},
+    hash::hashv,
 };
 
+use crate::error::ReviewError;
+use crate::math::{checked_add, checked_sum};
+
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct MovieAccountState {
     pub discriminator: String,
     pub is_initialized: bool,
+    pub version: u8,
     pub reviewer: Pubkey,
     pub rating: u8,
+    pub language_code: [u8; 2],
+    pub watched_at: i64,
+    pub created_at: i64,
+    // One counter per reaction kind, indexed by `ReactionRecord::reaction`
+    pub reactions: [u32; MovieAccountState::REACTION_KINDS],
+    pub comment_moderation: u8,
+    // Set once the review reward has been paid out, so `BackfillReward`
+    // cannot pay it twice
+    pub reward_minted: bool,
+    // Allocated at exactly `get_account_size` instead of `LEN`; such accounts
+    // are reallocated whenever an update makes them larger
+    pub tight_size: bool,
+    // Disclosure of paid content; once set it can never be cleared
+    pub is_sponsored: bool,
     pub title: String,
     pub description: String,
+    // Cover art; padded reviews written before it existed read as `None`
+    pub poster_uri: Option<String>,
+    // Kept last so padded reviews written before it existed read as untagged
+    pub tags: Vec<String>,
 }
 
 impl MovieAccountState {
@@ -20,16 +45,161 @@ impl MovieAccountState {
 
     pub const LEN: usize = 1000;
 
-    pub fn get_account_size(title: String, description: String) -> usize {
-        (4 + MovieAccountState::DISCRIMINATOR.len())
+    pub const MAX_POSTER_URI_LEN: usize = 128;
+
+    pub const POSTER_URI_SCHEMES: [&'static str; 3] = ["https://", "ar://", "ipfs://"];
+
+    pub const MAX_TAGS: usize = 5;
+    pub const MAX_TAG_LEN: usize = 20;
+
+    // Layout version. Accounts without it, whether written before the
+    // discriminator existed or in the discriminated `BaselineMovieAccountState`
+    // layout, must go through `MigrateLegacyReview`
+    pub const VERSION: u8 = 1;
+
+    pub const DEFAULT_LANGUAGE_CODE: [u8; 2] = *b"en";
+
+    // 👍 🔥 😂 ❤️ 😮 😢
+    pub const REACTION_KINDS: usize = 6;
+
+    // `comment_moderation` modes
+    pub const COMMENTS_OPEN: u8 = 0;
+    pub const COMMENTS_REQUIRE_APPROVAL: u8 = 1;
+    pub const COMMENTS_LOCKED: u8 = 2;
+
+    // Byte offset of `language_code` for `memcmp` filters in getProgramAccounts
+    pub const LANGUAGE_CODE_OFFSET: usize = (4 + MovieAccountState::DISCRIMINATOR.len())
         + 1
-        + 32
         + 1
-        + (4 + title.len())
-        + (4 + description.len())
+        + 32
+        + 1;
+
+    pub fn get_account_size(
+        title: String,
+        description: String,
+        poster_uri: Option<String>,
+        tags: Vec<String>,
+    ) -> Result<usize, ReviewError> {
+        let poster_uri_len = poster_uri.map_or(Ok(0), |uri| checked_add(4, uri.len()))?;
+        let tags_len = tags.iter().try_fold(0, |len, tag| checked_sum([len, 4, tag.len()]))?;
+
+        checked_sum([
+            4 + MovieAccountState::DISCRIMINATOR.len(),
+            1,
+            1,
+            32,
+            1,
+            2,
+            8,
+            8,
+            4 * MovieAccountState::REACTION_KINDS,
+            1,
+            1,
+            1,
+            1,
+            4,
+            title.len(),
+            4,
+            description.len(),
+            1,
+            poster_uri_len,
+            4,
+            tags_len,
+        ])
+    }
+
+    // Title as used in the `[reviewer, title]` review seed. With the
+    // `normalize-titles` feature it is trimmed and lowercased, so case
+    // variants share one review; `title` in the account keeps the original.
+    pub fn title_seed(title: &str) -> Cow<'_, str> {
+        #[cfg(feature = "normalize-titles")]
+        return Cow::Owned(title.trim().to_lowercase());
+
+        #[cfg(not(feature = "normalize-titles"))]
+        Cow::Borrowed(title)
+    }
+
+    // Bytes the account needs for its current contents
+    pub fn account_len(&self) -> Result<usize, ReviewError> {
+        if self.tight_size {
+            MovieAccountState::get_account_size(
+                self.title.clone(),
+                self.description.clone(),
+                self.poster_uri.clone(),
+                self.tags.clone(),
+            )
+        } else {
+            Ok(MovieAccountState::LEN)
+        }
+    }
+
+    // Hash of the title and description, lowercased with runs of whitespace
+    // collapsed, so front ends can spot the same text posted to different
+    // reviews. Nothing else in the account goes in, and the title's length
+    // is hashed first so text cannot move between the two fields.
+    pub fn content_fingerprint(&self) -> [u8; 32] {
+        let normalize = |text: &str| text.split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let title = normalize(&self.title);
+        let description = normalize(&self.description);
+
+        hashv(&[
+            &(title.len() as u32).to_le_bytes(),
+            title.as_bytes(),
+            description.as_bytes(),
+        ]).to_bytes()
+    }
+
+    // Accounts written before ratings were validated may hold 0 (the Borsh
+    // default) or another out-of-range value; those read back as `None`
+    pub fn normalized_rating(&self) -> Option<u8> {
+        (1..=5).contains(&self.rating).then_some(self.rating)
     }
 }
 
+// Layout written by the original handler, before reviews carried a
+// discriminator or the reviewer key
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct LegacyMovieAccountState {
+    pub rating: u8,
+    pub title: String,
+    pub description: String,
+    pub is_initialized: bool,
+}
+
+// Layout of discriminated reviews written before `version`, `language_code`
+// and the other fixed-size fields were inserted ahead of `title`. The
+// language code has to sit at a fixed offset for `memcmp` filters, so these
+// accounts are upgraded by `MigrateLegacyReview` rather than read in place.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct BaselineMovieAccountState {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub reviewer: Pubkey,
+    pub rating: u8,
+    pub title: String,
+    pub description: String,
+}
+
+// Left at the old address by `RenameReview` when comments still point at it
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReviewTombstone {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub renamed_to: Pubkey,
+}
+
+impl ReviewTombstone {
+    pub const DISCRIMINATOR: &'static str = "renamed";
+
+    pub const LEN: usize = (4 + ReviewTombstone::DISCRIMINATOR.len())
+        + 1
+        + 32;
+}
+
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct MovieCommentCounter {
     pub discriminator: String,
@@ -53,21 +223,511 @@ pub struct MovieComment {
     pub commenter: Pubkey,
     pub comment: String,
     pub count: u64,
+    pub created_at: i64,
 }
 
 impl MovieComment {
     pub const DISCRIMINATOR: &'static str = "comment";
 
-    pub fn get_account_size(comment: String) -> usize {
-        (4 + MovieComment::DISCRIMINATOR.len())
+    pub fn get_account_size(comment: String) -> Result<usize, ReviewError> {
+        checked_sum([
+            4 + MovieComment::DISCRIMINATOR.len(),
+            1,
+            32,
+            32,
+            4,
+            comment.len(),
+            8,
+            8,
+        ])
+    }
+}
+
+// A comment submitted to a `COMMENTS_REQUIRE_APPROVAL` review, held at
+// `[review, submitter, "pending"]` until the reviewer approves it into a
+// `MovieComment` or rejects it
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct PendingComment {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub review: Pubkey,
+    pub submitter: Pubkey,
+    pub comment: String,
+    pub created_at: i64,
+}
+
+impl PendingComment {
+    pub const DISCRIMINATOR: &'static str = "pending";
+
+    pub fn get_account_size(comment: String) -> Result<usize, ReviewError> {
+        checked_sum([
+            4 + PendingComment::DISCRIMINATOR.len(),
+            1,
+            32,
+            32,
+            4,
+            comment.len(),
+            8,
+        ])
+    }
+}
+
+// Marks a comment on a `COMMENTS_REQUIRE_APPROVAL` review as visible. Only
+// comments stored before `PendingComment` existed need one; newer ones are
+// held back until `ApprovePendingComment` instead.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CommentApproval {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub comment: Pubkey,
+    pub is_approved: bool,
+}
+
+impl CommentApproval {
+    pub const DISCRIMINATOR: &'static str = "approval";
+
+    pub const LEN: usize = (4 + CommentApproval::DISCRIMINATOR.len())
         + 1
         + 32
+        + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReactionRecord {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub review: Pubkey,
+    pub reactor: Pubkey,
+    pub reaction: u8,
+}
+
+impl ReactionRecord {
+    pub const DISCRIMINATOR: &'static str = "reaction";
+
+    pub const LEN: usize = (4 + ReactionRecord::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 32
+        + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProgramConfig {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub admin: Pubkey,
+    // `Pubkey::default()` means the derived `[b"token_mint"]` PDA mint is used
+    pub reward_mint: Pubkey,
+    // When set, rewards accrue on a `RewardTracker` and are paid out by
+    // `ClaimRewards` instead of being minted on every write
+    pub claim_rewards: bool,
+    // Seconds after `created_at` during which deleting a review burns its reward
+    pub clawback_period: i64,
+    // When set, reviews and comments skip their reward instead of failing
+    // once the `token_auth` PDA no longer holds the mint authority
+    pub skip_revoked_rewards: bool,
+    // Program notified through CPI after every new review
+    pub callback_program_id: Option<Pubkey>,
+    // Reviews with a shorter description (in bytes) are stored without a reward
+    pub min_description_len_for_reward: u32,
+    // Reviews a reviewer must post before their profile is marked verified
+    pub min_reviews_for_auto_verify: u8,
+    // Largest single review or comment reward, in base units of the reward
+    // mint. Guards against a mint whose decimals make the fixed amounts huge.
+    pub max_reward_amount: u64,
+    // Mint for pushed comment rewards; `None` pays them in `reward_mint`.
+    // Claimed rewards are always paid in `reward_mint`.
+    pub comment_mint: Option<Pubkey>,
+}
+
+impl ProgramConfig {
+    pub const DISCRIMINATOR: &'static str = "config";
+
+    pub const LEN: usize = (4 + ProgramConfig::DISCRIMINATOR.len())
+        + 1
         + 32
-        + (4 + comment.len())
+        + 32
+        + 1
+        + 8
+        + 1
+        + (1 + 32)
+        + 4
+        + 1
         + 8
+        + (1 + 32);
+
+    pub const DEFAULT_CLAWBACK_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+    pub const DEFAULT_MIN_REVIEWS_FOR_AUTO_VERIFY: u8 = 10;
+
+    // No cap
+    pub const DEFAULT_MAX_REWARD_AMOUNT: u64 = u64::MAX;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct RewardTracker {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub user: Pubkey,
+    pub unclaimed_rewards: u64,
+}
+
+impl RewardTracker {
+    pub const DISCRIMINATOR: &'static str = "rewards";
+
+    pub const LEN: usize = (4 + RewardTracker::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 8;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct AuditEntry {
+    pub changed_at: i64,
+    pub rating: u8,
+    pub description_hash: [u8; 32],
+}
+
+impl AuditEntry {
+    pub const LEN: usize = 8 + 1 + 32;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AuditLog {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    pub const DISCRIMINATOR: &'static str = "audit";
+
+    pub const MAX_ENTRIES: usize = 10;
+
+    pub const LEN: usize = (4 + AuditLog::DISCRIMINATOR.len())
+        + 1
+        + (4 + AuditLog::MAX_ENTRIES * AuditEntry::LEN);
+
+    // Appends an entry, dropping the oldest ones once the log is full
+    pub fn push(&mut self, entry: AuditEntry) {
+        if self.entries.len() >= AuditLog::MAX_ENTRIES {
+            let overflow = self.entries.len() + 1 - AuditLog::MAX_ENTRIES;
+            self.entries.drain(..overflow);
+        }
+
+        self.entries.push(entry);
     }
 }
 
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReviewSeries {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub name: String,
+    pub parts: Vec<Pubkey>,
+    pub total_parts: u8,
+    pub is_finalized: bool,
+}
+
+impl ReviewSeries {
+    pub const DISCRIMINATOR: &'static str = "series";
+
+    pub const MAX_NAME_LEN: usize = 64;
+
+    pub const MAX_PARTS: usize = 10;
+
+    pub const LEN: usize = (4 + ReviewSeries::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + (4 + ReviewSeries::MAX_NAME_LEN)
+        + (4 + ReviewSeries::MAX_PARTS * 32)
+        + 1
+        + 1;
+}
+
+// Named group of an owner's reviews at `[owner, b"collection", collection_id]`.
+// Ids count up from the owner's `ReviewerProfile::collection_count`, so one
+// name can be used for several collections.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReviewCollection {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub owner: Pubkey,
+    pub collection_id: u64,
+    pub name: String,
+    pub description: String,
+    // Always `review_pdas.len()`
+    pub review_count: u32,
+    pub review_pdas: Vec<Pubkey>,
+}
+
+impl ReviewCollection {
+    pub const DISCRIMINATOR: &'static str = "collection";
+
+    pub const MAX_NAME_LEN: usize = 64;
+
+    pub const MAX_DESCRIPTION_LEN: usize = 256;
+
+    pub const MAX_REVIEWS: usize = 50;
+
+    pub const LEN: usize = (4 + ReviewCollection::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 8
+        + (4 + ReviewCollection::MAX_NAME_LEN)
+        + (4 + ReviewCollection::MAX_DESCRIPTION_LEN)
+        + 4
+        + (4 + ReviewCollection::MAX_REVIEWS * 32);
+}
+
+// Message from `sender` to `recipient` at
+// `[sender, recipient, message_index, b"dm"]`, with the index little endian
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct DirectMessage {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub message_index: u64,
+    pub content: String,
+    pub read: bool,
+    pub created_at: i64,
+}
+
+impl DirectMessage {
+    pub const DISCRIMINATOR: &'static str = "dm";
+
+    pub const MAX_CONTENT_LEN: usize = 256;
+
+    pub const LEN: usize = (4 + DirectMessage::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 32
+        + 8
+        + (4 + DirectMessage::MAX_CONTENT_LEN)
+        + 1
+        + 8;
+}
+
+// Messages sent so far from one sender to one recipient, at
+// `[sender, recipient, b"dm_count"]`; the next message takes index `count`
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct DirectMessageCounter {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub count: u64,
+}
+
+impl DirectMessageCounter {
+    pub const DISCRIMINATOR: &'static str = "dm_count";
+
+    pub const LEN: usize = (4 + DirectMessageCounter::DISCRIMINATOR.len())
+        + 1
+        + 8;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AwardNomination {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub review: Pubkey,
+    pub year: u16,
+    pub nominated_by: Pubkey,
+    pub category: String,
+    pub votes: u32,
+    pub is_winner: bool,
+}
+
+impl AwardNomination {
+    pub const DISCRIMINATOR: &'static str = "nomination";
+
+    pub const MAX_CATEGORY_LEN: usize = 32;
+
+    pub const LEN: usize = (4 + AwardNomination::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 2
+        + 32
+        + (4 + AwardNomination::MAX_CATEGORY_LEN)
+        + 4
+        + 1;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct NominationVote {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub nomination: Pubkey,
+    pub voter: Pubkey,
+}
+
+impl NominationVote {
+    pub const DISCRIMINATOR: &'static str = "vote";
+
+    pub const LEN: usize = (4 + NominationVote::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 32;
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Award {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub category: String,
+    pub year: u16,
+    pub nomination: Pubkey,
+    pub votes: u32,
+}
+
+impl Award {
+    pub const DISCRIMINATOR: &'static str = "award";
+
+    pub const LEN: usize = (4 + Award::DISCRIMINATOR.len())
+        + 1
+        + (4 + AwardNomination::MAX_CATEGORY_LEN)
+        + 2
+        + 32
+        + 4;
+}
+
+// Root of an off-chain Merkle tree over a review's comment PDAs. Leaves are
+// `hash(comment_pda)` and each parent hashes its two children in sorted order.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CommentMerkleRoot {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub review: Pubkey,
+    pub root: [u8; 32],
+    pub num_comments: u64,
+}
+
+impl CommentMerkleRoot {
+    pub const DISCRIMINATOR: &'static str = "merkle";
+
+    pub const LEN: usize = (4 + CommentMerkleRoot::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 32
+        + 8;
+}
+
+// Program-wide totals at `[b"stats"]`, created by the first review
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct GlobalStats {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub total_reviews: u64,
+    pub total_comments: u64,
+    // Reward units granted for reviews and comments, whether minted
+    // directly or accrued for a later claim
+    pub total_tokens_minted: u64,
+    // Counted on each reviewer's first review, when their `ReviewerProfile`
+    // goes from 0 to 1 reviews
+    pub total_reviewers: u64,
+}
+
+impl GlobalStats {
+    pub const DISCRIMINATOR: &'static str = "stats";
+
+    pub const LEN: usize = (4 + GlobalStats::DISCRIMINATOR.len())
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8;
+}
+
+// Per-reviewer totals at `[b"profile", reviewer]`, created by the reviewer's
+// first review
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReviewerProfile {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub reviewer: Pubkey,
+    // Reviews posted, including any later deleted or renamed
+    pub review_count: u64,
+    pub has_first_badge: bool,
+    // Set once `review_count` reaches the config's auto-verify threshold
+    pub is_verified: bool,
+    // Collections created, and so the id of the next `ReviewCollection`
+    pub collection_count: u64,
+}
+
+impl ReviewerProfile {
+    pub const DISCRIMINATOR: &'static str = "profile";
+
+    pub const FIRST_REVIEW_BADGE_NAME: &'static str = "First Review Badge";
+
+    pub const LEN: usize = (4 + ReviewerProfile::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + 8
+        + 1
+        + 1
+        + 8;
+}
+
+// Word frequencies over review titles at `[b"tagcloud"]`, created by the
+// first `UpdateTagCloud`
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct TagCloud {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub entries: Vec<(String, u32)>,
+}
+
+impl TagCloud {
+    pub const DISCRIMINATOR: &'static str = "tagcloud";
+
+    pub const MAX_ENTRIES: usize = 50;
+
+    // Tags come from titles, which are capped by the PDA seed length
+    pub const MAX_TAG_LEN: usize = MAX_SEED_LEN;
+
+    pub const LEN: usize = (4 + TagCloud::DISCRIMINATOR.len())
+        + 1
+        + 4 + TagCloud::MAX_ENTRIES * ((4 + TagCloud::MAX_TAG_LEN) + 4);
+}
+
+// Marks a review as counted into the tag cloud, at `[review, b"tagcloud"]`
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct TagCloudRecord {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub review: Pubkey,
+}
+
+impl TagCloudRecord {
+    pub const DISCRIMINATOR: &'static str = "tagged";
+
+    pub const LEN: usize = (4 + TagCloudRecord::DISCRIMINATOR.len())
+        + 1
+        + 32;
+}
+
+// Two-step handover of the program's upgrade authority at `[b"authority"]`,
+// created by the first `ProposeAuthorityTransfer`. From the proposal on the
+// PDA holds the upgrade authority itself, so it can sign the handover when
+// `pending_authority` accepts.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProgramAuthorityRecord {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub upgrade_authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+}
+
+impl ProgramAuthorityRecord {
+    pub const DISCRIMINATOR: &'static str = "authority";
+
+    pub const LEN: usize = (4 + ProgramAuthorityRecord::DISCRIMINATOR.len())
+        + 1
+        + 32
+        + (1 + 32);
+}
+
 impl Sealed for MovieAccountState {}
 
 impl IsInitialized for MovieAccountState {
@@ -76,6 +736,12 @@ impl IsInitialized for MovieAccountState {
     }
 }
 
+impl IsInitialized for ReviewTombstone {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
 impl IsInitialized for MovieCommentCounter {
     fn is_initialized(&self) -> bool {
         self.is_initialized
@@ -87,3 +753,291 @@ impl IsInitialized for MovieComment {
         self.is_initialized
     }
 }
+
+impl IsInitialized for PendingComment {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for CommentApproval {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for ReactionRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for ProgramConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for RewardTracker {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for AuditLog {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for ReviewSeries {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for ReviewCollection {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for DirectMessage {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for DirectMessageCounter {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for CommentMerkleRoot {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for GlobalStats {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for ReviewerProfile {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for TagCloud {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for ProgramAuthorityRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for AwardNomination {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for NominationVote {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl IsInitialized for Award {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_rating() {
+        let review = |rating: u8| MovieAccountState {
+            discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            version: MovieAccountState::VERSION,
+            reviewer: Pubkey::new_unique(),
+            rating,
+            language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+            watched_at: 0,
+            created_at: 0,
+            reactions: [0; MovieAccountState::REACTION_KINDS],
+            comment_moderation: MovieAccountState::COMMENTS_OPEN,
+            reward_minted: true,
+            tight_size: false,
+            is_sponsored: false,
+            title: String::from("Captain America"),
+            description: String::from("Liked the movie"),
+            poster_uri: None,
+            tags: Vec::new(),
+        };
+
+        assert_eq!(review(0).normalized_rating(), None);
+        assert_eq!(review(3).normalized_rating(), Some(3));
+        assert_eq!(review(6).normalized_rating(), None);
+    }
+
+    #[test]
+    fn test_review_round_trip() {
+        let review = MovieAccountState {
+            discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            version: MovieAccountState::VERSION,
+            reviewer: Pubkey::new_from_array([7; 32]),
+            rating: 4,
+            language_code: *b"fr",
+            watched_at: 1_700_000_000,
+            created_at: 1_700_000_600,
+            reactions: [1, 2, 3, 4, 5, 6],
+            comment_moderation: MovieAccountState::COMMENTS_REQUIRE_APPROVAL,
+            reward_minted: true,
+            tight_size: true,
+            is_sponsored: true,
+            title: "a".repeat(MAX_SEED_LEN),
+            description: String::from("A slow burn that pays off in the last act"),
+            poster_uri: Some(String::from("ipfs://poster")),
+            tags: vec![String::from("classic"), String::from("rewatch")],
+        };
+
+        let serialized = borsh::to_vec(&review).unwrap();
+        assert_eq!(
+            serialized.len(),
+            MovieAccountState::get_account_size(
+                review.title.clone(),
+                review.description.clone(),
+                review.poster_uri.clone(),
+                review.tags.clone(),
+            ).unwrap()
+        );
+
+        let decoded = MovieAccountState::try_from_slice(&serialized).unwrap();
+        assert_eq!(decoded.discriminator, review.discriminator);
+        assert_eq!(decoded.is_initialized, review.is_initialized);
+        assert_eq!(decoded.version, review.version);
+        assert_eq!(decoded.reviewer, review.reviewer);
+        assert_eq!(decoded.rating, review.rating);
+        assert_eq!(decoded.language_code, review.language_code);
+        assert_eq!(decoded.watched_at, review.watched_at);
+        assert_eq!(decoded.created_at, review.created_at);
+        assert_eq!(decoded.reactions, review.reactions);
+        assert_eq!(decoded.comment_moderation, review.comment_moderation);
+        assert_eq!(decoded.reward_minted, review.reward_minted);
+        assert_eq!(decoded.tight_size, review.tight_size);
+        assert_eq!(decoded.is_sponsored, review.is_sponsored);
+        assert_eq!(decoded.title, review.title);
+        assert_eq!(decoded.description, review.description);
+        assert_eq!(decoded.poster_uri, review.poster_uri);
+        assert_eq!(decoded.tags, review.tags);
+    }
+
+    #[test]
+    fn test_comment_counter_round_trip() {
+        let counter = MovieCommentCounter {
+            discriminator: MovieCommentCounter::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            counter: 42,
+        };
+
+        let serialized = borsh::to_vec(&counter).unwrap();
+        assert_eq!(serialized.len(), MovieCommentCounter::LEN);
+
+        let decoded = MovieCommentCounter::try_from_slice(&serialized).unwrap();
+        assert_eq!(decoded.discriminator, counter.discriminator);
+        assert_eq!(decoded.is_initialized, counter.is_initialized);
+        assert_eq!(decoded.counter, counter.counter);
+    }
+
+    #[test]
+    fn test_comment_round_trip() {
+        let comment = MovieComment {
+            discriminator: MovieComment::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            review: Pubkey::new_from_array([7; 32]),
+            commenter: Pubkey::new_from_array([9; 32]),
+            comment: String::from("Agreed, the score carries it"),
+            count: 3,
+            created_at: 1_700_000_600,
+        };
+
+        let serialized = borsh::to_vec(&comment).unwrap();
+        assert_eq!(serialized.len(), MovieComment::get_account_size(comment.comment.clone()).unwrap());
+
+        let decoded = MovieComment::try_from_slice(&serialized).unwrap();
+        assert_eq!(decoded.discriminator, comment.discriminator);
+        assert_eq!(decoded.is_initialized, comment.is_initialized);
+        assert_eq!(decoded.review, comment.review);
+        assert_eq!(decoded.commenter, comment.commenter);
+        assert_eq!(decoded.comment, comment.comment);
+        assert_eq!(decoded.count, comment.count);
+        assert_eq!(decoded.created_at, comment.created_at);
+    }
+
+    #[test]
+    fn test_title_seed() {
+        let program_id = Pubkey::new_unique();
+        let reviewer = Pubkey::new_unique();
+
+        let review_pda = |title: &str| Pubkey::find_program_address(
+            &[reviewer.as_ref(), MovieAccountState::title_seed(title).as_bytes()], 
+            &program_id
+        ).0;
+
+        #[cfg(feature = "normalize-titles")]
+        assert_eq!(review_pda("Inception"), review_pda(" inception "));
+
+        #[cfg(not(feature = "normalize-titles"))]
+        assert_ne!(review_pda("Inception"), review_pda("inception"));
+    }
+
+    #[test]
+    fn test_content_fingerprint() {
+        let review = |title: &str, description: &str| MovieAccountState {
+            discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+            is_initialized: true,
+            version: MovieAccountState::VERSION,
+            reviewer: Pubkey::new_unique(),
+            rating: 4,
+            language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+            watched_at: 0,
+            created_at: 0,
+            reactions: [0; MovieAccountState::REACTION_KINDS],
+            comment_moderation: MovieAccountState::COMMENTS_OPEN,
+            reward_minted: true,
+            tight_size: false,
+            is_sponsored: false,
+            title: title.to_string(),
+            description: description.to_string(),
+            poster_uri: None,
+            tags: Vec::new(),
+        };
+
+        let fingerprint = review("Captain America", "Liked the movie").content_fingerprint();
+
+        // Different reviewers, same text
+        assert_eq!(review("Captain America", "Liked the movie").content_fingerprint(), fingerprint);
+        assert_eq!(review(" captain  AMERICA", "Liked\tthe movie ").content_fingerprint(), fingerprint);
+
+        assert_ne!(review("Captain America", "Loved the movie").content_fingerprint(), fingerprint);
+        assert_ne!(review("Iron Man", "Liked the movie").content_fingerprint(), fingerprint);
+        assert_ne!(
+            review("Captain", "America Liked the movie").content_fingerprint(),
+            review("Captain America", "Liked the movie").content_fingerprint()
+        );
+    }
+}
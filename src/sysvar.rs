@@ -0,0 +1,46 @@
+use solana_program::{
+    clock::Clock,
+    msg,
+    program_error::ProgramError,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+pub fn get_rent() -> Result<Rent, ProgramError> {
+    fetch_sysvar(Rent::get(), "Rent")
+}
+
+pub fn get_clock() -> Result<Clock, ProgramError> {
+    fetch_sysvar(Clock::get(), "Clock")
+}
+
+// A bare `SysvarNotPresent` doesn't say which sysvar was missing, so log it
+// and surface a plain `InvalidArgument` instead.
+fn fetch_sysvar<T>(sysvar: Result<T, ProgramError>, name: &str) -> Result<T, ProgramError> {
+    sysvar.map_err(|err| {
+        msg!("Failed to fetch {} sysvar: {}", name, err);
+        ProgramError::InvalidArgument
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The sysvar syscalls are stubbed process-wide by `ProgramTest`, so a
+    // missing sysvar is simulated at the result level.
+    #[test]
+    fn test_missing_sysvar_is_reported() {
+        assert_eq!(
+            fetch_sysvar::<Rent>(Err(ProgramError::UnsupportedSysvar), "Rent").unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+        assert_eq!(
+            fetch_sysvar::<Clock>(Err(ProgramError::UnsupportedSysvar), "Clock").unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+
+        let rent = fetch_sysvar(Ok(Rent::default()), "Rent").unwrap();
+        assert_eq!(rent.minimum_balance(0), Rent::default().minimum_balance(0));
+    }
+}
@@ -0,0 +1,1790 @@
+use borsh::BorshSerialize;
+
+use solana_program::{
+    pubkey::Pubkey,
+    instruction::{Instruction, AccountMeta},
+    hash::hash,
+};
+
+use solana_loader_v3_interface::get_program_data_address;
+use solana_sdk_ids::{bpf_loader_upgradeable, system_program};
+
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+#[cfg(any(test, feature = "program-test"))]
+use solana_program_test::ProgramTest;
+#[cfg(any(test, feature = "program-test"))]
+use solana_sdk::{
+    account::Account,
+    native_token::sol_to_lamports,
+    signature::{Keypair, Signer},
+};
+
+use crate::instruction::*;
+use crate::processor::{TOKEN_PROGRAM_ID, freeze_auth_pda, mint_pda, mint_auth_pda};
+use crate::state::MovieAccountState;
+
+pub fn create_init_mint_ix(payer: Pubkey, program_id: &Pubkey) -> (Pubkey, Pubkey, Instruction) {
+    let (mint, _mint_bump) = mint_pda(program_id);
+    let (mint_auth, _mint_auth_bump) = mint_auth_pda(program_id);
+
+    let init_mint_ix = Instruction::new_with_borsh(
+        *program_id, 
+        &3, 
+        vec![
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(mint_auth, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    );
+
+    (mint, mint_auth, init_mint_ix)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_add_movie_review_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    rating: u8,
+    description: String,
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    create_add_movie_review_with_payload_ix(
+        payer,
+        program_id,
+        AddMovieReviewPayload::new(title, rating, description),
+        mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_add_movie_review_with_language_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    rating: u8,
+    description: String,
+    language_code: [u8; 2],
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    create_add_movie_review_with_payload_ix(
+        payer,
+        program_id,
+        AddMovieReviewPayload {
+            language_code,
+            ..AddMovieReviewPayload::new(title, rating, description)
+        },
+        mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_add_movie_review_with_payload_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    movie_review_payload: AddMovieReviewPayload,
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    let review_pda = find_review_pda(&payer, &movie_review_payload.title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], 
+        &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(0u8, movie_review_payload), 
+        vec![
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(review_pda, false),
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(mint_auth, false),
+            AccountMeta::new(user_ata, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new(find_stats_pda(&program_id), false),
+            AccountMeta::new(find_profile_pda(&payer, &program_id), false),
+        ]
+    )
+}
+
+// Same as `create_add_movie_review_ix`, but `fee_payer` funds the review and
+// counter accounts while `reviewer` only signs as the author
+#[allow(clippy::too_many_arguments)]
+pub fn create_sponsored_add_movie_review_ix(
+    reviewer: Pubkey,
+    fee_payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    rating: u8,
+    description: String,
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    let mut add_movie_review_ix = create_add_movie_review_ix(
+        reviewer,
+        program_id,
+        title,
+        rating,
+        description,
+        mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+    );
+    add_movie_review_ix.accounts.push(AccountMeta::new(fee_payer, true));
+
+    add_movie_review_ix
+}
+
+pub fn create_update_movie_instruction(
+    payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    rating: u8,
+    description: String,
+) -> Instruction {
+    create_update_movie_with_poster_ix(payer, program_id, title, rating, description, None)
+}
+
+pub fn create_update_movie_with_poster_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    rating: u8,
+    description: String,
+    poster_uri: Option<String>,
+) -> Instruction {
+    create_update_movie_with_payload_ix(
+        payer,
+        program_id,
+        MovieReviewPayload {
+            poster_uri,
+            ..MovieReviewPayload::new(title, rating, description)
+        },
+    )
+}
+
+pub fn create_update_movie_with_payload_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    movie_review_payload: MovieReviewPayload,
+) -> Instruction {
+    let review_pda = find_review_pda(&payer, &movie_review_payload.title, &program_id);
+
+    let (audit_pda, _audit_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"audit"], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(1u8, movie_review_payload), 
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(review_pda, false),
+            AccountMeta::new(audit_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_view_audit_log_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (audit_pda, _audit_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"audit"], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(100u8, ViewAuditLogPayload {
+            title,
+        }), 
+        vec![
+            AccountMeta::new_readonly(reviewer, false),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new_readonly(audit_pda, false),
+        ]
+    )
+}
+
+pub fn create_change_review_language_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    language_code: [u8; 2],
+) -> Instruction {
+    let review_pda = find_review_pda(&payer, &title, &program_id);
+
+    let review_language_payload = (95u8, ReviewLanguagePayload {
+        title,
+        language_code,
+    });
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &review_language_payload, 
+        vec![
+            AccountMeta::new_readonly(payer, true),
+            AccountMeta::new(review_pda, false)
+        ]
+    )
+}
+
+pub fn create_gift_tokens_ix(
+    gifter: Pubkey,
+    program_id: Pubkey,
+    recipient: Pubkey,
+    amount: u64,
+    mint: Pubkey,
+) -> Instruction {
+    let gifter_ata = get_associated_token_address_with_program_id(&gifter, &mint, &TOKEN_PROGRAM_ID);
+    let recipient_ata = get_associated_token_address_with_program_id(&recipient, &mint, &TOKEN_PROGRAM_ID);
+
+    let gift_tokens_payload = (96u8, GiftTokensPayload {
+        recipient,
+        amount,
+    });
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &gift_tokens_payload, 
+        vec![
+            AccountMeta::new_readonly(gifter, true),
+            AccountMeta::new(gifter_ata, false),
+            AccountMeta::new(recipient_ata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ]
+    )
+}
+
+pub fn create_query_reviews_by_range_ix(
+    program_id: Pubkey,
+    min_rating: u8,
+    max_rating: u8,
+    max_results: u8,
+    reviews: &[Pubkey],
+) -> Instruction {
+    let query_payload = (97u8, QueryReviewsPayload {
+        min_rating,
+        max_rating,
+        max_results,
+    });
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &query_payload, 
+        reviews.iter()
+            .map(|review| AccountMeta::new_readonly(*review, false))
+            .collect()
+    )
+}
+
+pub fn create_export_all_reviews_ix(
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    reviews: &[Pubkey],
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(127u8, ExportAllReviewsPayload {
+            reviewer,
+        }), 
+        reviews.iter()
+            .map(|review| AccountMeta::new_readonly(*review, false))
+            .collect()
+    )
+}
+
+// `accounts` are the reviews and comment counters to count
+pub fn create_recompute_program_stats_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    accounts: &[Pubkey],
+) -> Instruction {
+    let mut metas = vec![
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new_readonly(find_config_pda(&program_id), false),
+        AccountMeta::new(find_stats_pda(&program_id), false),
+    ];
+
+    metas.extend(accounts.iter().map(|account| AccountMeta::new_readonly(*account, false)));
+
+    Instruction::new_with_borsh(program_id, &128u8, metas)
+}
+
+pub fn create_batch_create_atas_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    users: Vec<Pubkey>,
+    mint: Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+    ];
+
+    for user in &users {
+        accounts.push(AccountMeta::new_readonly(*user, false));
+        accounts.push(AccountMeta::new(
+            get_associated_token_address_with_program_id(user, &mint, &TOKEN_PROGRAM_ID),
+            false
+        ));
+    }
+
+    let batch_payload = (98u8, BatchCreateATAsPayload {
+        users,
+    });
+
+    Instruction::new_with_borsh(program_id, &batch_payload, accounts)
+}
+
+pub fn find_review_pda(reviewer: &Pubkey, title: &str, program_id: &Pubkey) -> Pubkey {
+    let (review_pda, _review_bump) = Pubkey::find_program_address(
+        &[reviewer.as_ref(), MovieAccountState::title_seed(title).as_bytes()], program_id
+    );
+
+    review_pda
+}
+
+// A review as `AddMovieReview` stores it, for seeding accounts directly
+// with `ProgramTest::add_account`; override fields with struct update syntax
+pub fn review_fixture(reviewer: Pubkey, title: &str, rating: u8, description: &str) -> MovieAccountState {
+    MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        version: MovieAccountState::VERSION,
+        reviewer,
+        rating,
+        language_code: MovieAccountState::DEFAULT_LANGUAGE_CODE,
+        watched_at: 0,
+        created_at: 0,
+        reactions: [0; MovieAccountState::REACTION_KINDS],
+        comment_moderation: MovieAccountState::COMMENTS_OPEN,
+        reward_minted: true,
+        tight_size: false,
+        is_sponsored: false,
+        title: title.to_string(),
+        description: description.to_string(),
+        poster_uri: None,
+        tags: Vec::new(),
+    }
+}
+
+// `review` serialized into a zero-padded account of `len` bytes
+pub fn review_account_data(review: &MovieAccountState, len: usize) -> Vec<u8> {
+    let mut data = vec![0; len];
+    review.serialize(&mut &mut data[..]).unwrap();
+
+    data
+}
+
+// Adds a system account holding 1 SOL, enough to sign, pay fees and fund
+// new accounts without the payer
+#[cfg(any(test, feature = "program-test"))]
+pub fn add_funded_keypair(program_test: &mut ProgramTest) -> Keypair {
+    let keypair = Keypair::new();
+
+    program_test.add_account(
+        keypair.pubkey(),
+        Account {
+            lamports: sol_to_lamports(1.0),
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+
+    keypair
+}
+
+pub fn find_config_pda(program_id: &Pubkey) -> Pubkey {
+    let (config_pda, _config_bump) = Pubkey::find_program_address(&[b"config"], program_id);
+
+    config_pda
+}
+
+pub fn find_stats_pda(program_id: &Pubkey) -> Pubkey {
+    let (stats_pda, _stats_bump) = Pubkey::find_program_address(&[b"stats"], program_id);
+
+    stats_pda
+}
+
+pub fn find_profile_pda(reviewer: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (profile_pda, _profile_bump) = Pubkey::find_program_address(
+        &[b"profile", reviewer.as_ref()], program_id
+    );
+
+    profile_pda
+}
+
+pub fn find_badge_mint_pda(reviewer: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (badge_mint, _badge_mint_bump) = Pubkey::find_program_address(
+        &[b"badge", reviewer.as_ref()], program_id
+    );
+
+    badge_mint
+}
+
+pub fn create_mint_first_review_badge_ix(
+    payer: Pubkey,
+    program_id: Pubkey,
+    reviewer: Pubkey,
+) -> Instruction {
+    let (mint_auth, _mint_auth_bump) = mint_auth_pda(&program_id);
+    let badge_mint = find_badge_mint_pda(&reviewer, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(116u8, MintFirstReviewBadgePayload {
+            reviewer,
+        }), 
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(reviewer, false),
+            AccountMeta::new(find_profile_pda(&reviewer, &program_id), false),
+            AccountMeta::new(badge_mint, false),
+            AccountMeta::new_readonly(mint_auth, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(&reviewer, &badge_mint, &TOKEN_PROGRAM_ID), 
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        ],
+    )
+}
+
+pub fn create_fetch_comments_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    review_title: String,
+    start_index: u64,
+    count: u8,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &review_title, &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(reviewer, false),
+        AccountMeta::new_readonly(review_pda, false),
+    ];
+    accounts.extend((start_index..start_index + u64::from(count)).map(|index| {
+        let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+            &[review_pda.as_ref(), index.to_be_bytes().as_ref()], &program_id
+        );
+
+        AccountMeta::new_readonly(comment_pda, false)
+    }));
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(117u8, FetchCommentsPayload {
+            review_title,
+            start_index,
+            count,
+        }), 
+        accounts,
+    )
+}
+
+pub fn create_verify_program_version_ix(
+    program_id: Pubkey,
+    expected_hash: [u8; 32],
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(118u8, VerifyProgramVersionPayload {
+            expected_hash,
+        }), 
+        vec![
+            AccountMeta::new_readonly(
+                get_program_data_address(&program_id), 
+                false,
+            ),
+        ],
+    )
+}
+
+pub fn find_authority_record_pda(program_id: &Pubkey) -> Pubkey {
+    let (record_pda, _record_bump) = Pubkey::find_program_address(&[b"authority"], program_id);
+
+    record_pda
+}
+
+pub fn create_propose_authority_transfer_ix(
+    authority: Pubkey,
+    program_id: Pubkey,
+    new_authority: Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(119u8, ProposeAuthorityTransferPayload {
+            new_authority,
+        }), 
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(find_authority_record_pda(&program_id), false),
+            AccountMeta::new(get_program_data_address(&program_id), false),
+            AccountMeta::new_readonly(bpf_loader_upgradeable::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn create_accept_authority_transfer_ix(
+    new_authority: Pubkey,
+    program_id: Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &120u8, 
+        vec![
+            AccountMeta::new_readonly(new_authority, true),
+            AccountMeta::new(find_authority_record_pda(&program_id), false),
+            AccountMeta::new(get_program_data_address(&program_id), false),
+            AccountMeta::new_readonly(bpf_loader_upgradeable::ID, false),
+        ],
+    )
+}
+
+pub fn create_set_auto_verify_threshold_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    threshold: u8,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(121u8, AutoVerifyThresholdPayload {
+            threshold,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_set_max_reward_amount_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    max_amount: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(19u8, MaxRewardAmountPayload {
+            max_amount,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn find_collection_pda(owner: &Pubkey, program_id: &Pubkey, collection_id: u64) -> Pubkey {
+    let (collection_pda, _collection_bump) = Pubkey::find_program_address(
+        &[owner.as_ref(), b"collection", &collection_id.to_le_bytes()], 
+        program_id
+    );
+
+    collection_pda
+}
+
+// `collection_id` must be the owner's current `ReviewerProfile::collection_count`
+pub fn create_collection_ix(
+    owner: Pubkey,
+    program_id: Pubkey,
+    collection_id: u64,
+    name: String,
+    description: String,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(122u8, CreateCollectionPayload {
+            name,
+            description,
+        }), 
+        vec![
+            AccountMeta::new(owner, true),
+            AccountMeta::new(find_profile_pda(&owner, &program_id), false),
+            AccountMeta::new(find_collection_pda(&owner, &program_id, collection_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_add_review_to_collection_ix(
+    owner: Pubkey,
+    program_id: Pubkey,
+    collection_id: u64,
+    review_title: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&owner, &review_title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(123u8, CollectionReviewPayload {
+            collection_id,
+            review_title,
+        }), 
+        vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(find_collection_pda(&owner, &program_id, collection_id), false),
+            AccountMeta::new_readonly(review_pda, false),
+        ]
+    )
+}
+
+pub fn create_remove_review_from_collection_ix(
+    owner: Pubkey,
+    program_id: Pubkey,
+    collection_id: u64,
+    review_title: String,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(124u8, CollectionReviewPayload {
+            collection_id,
+            review_title,
+        }), 
+        vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(find_collection_pda(&owner, &program_id, collection_id), false),
+        ]
+    )
+}
+
+pub fn create_compact_comment_ix(
+    authority: Pubkey,
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    old_index: u64,
+    new_index: u64,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], &program_id
+    );
+
+    let (old_comment_pda, _old_comment_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), old_index.to_be_bytes().as_ref()], &program_id
+    );
+
+    let (new_comment_pda, _new_comment_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), new_index.to_be_bytes().as_ref()], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(18u8, CompactCommentPayload {
+            old_index,
+            new_index,
+        }), 
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new_readonly(counter_pda, false),
+            AccountMeta::new(old_comment_pda, false),
+            AccountMeta::new(new_comment_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn find_direct_message_counter_pda(sender: &Pubkey, recipient: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[sender.as_ref(), recipient.as_ref(), b"dm_count"], program_id
+    );
+
+    counter_pda
+}
+
+pub fn find_direct_message_pda(
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    program_id: &Pubkey,
+    message_index: u64,
+) -> Pubkey {
+    let (message_pda, _message_bump) = Pubkey::find_program_address(
+        &[sender.as_ref(), recipient.as_ref(), &message_index.to_le_bytes(), b"dm"], program_id
+    );
+
+    message_pda
+}
+
+// `message_index` is the sender's current count of messages to `recipient`
+pub fn create_send_direct_message_ix(
+    sender: Pubkey,
+    program_id: Pubkey,
+    recipient: Pubkey,
+    message: String,
+    message_index: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(125u8, SendDirectMessagePayload {
+            recipient,
+            message,
+        }), 
+        vec![
+            AccountMeta::new(sender, true),
+            AccountMeta::new(find_direct_message_counter_pda(&sender, &recipient, &program_id), false),
+            AccountMeta::new(find_direct_message_pda(&sender, &recipient, &program_id, message_index), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_mark_message_read_ix(
+    recipient: Pubkey,
+    program_id: Pubkey,
+    sender: Pubkey,
+    message_index: u64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(126u8, MarkMessageReadPayload {
+            sender,
+            message_index,
+        }), 
+        vec![
+            AccountMeta::new_readonly(recipient, true),
+            AccountMeta::new(find_direct_message_pda(&sender, &recipient, &program_id, message_index), false),
+        ]
+    )
+}
+
+pub fn create_ping_ix(program_id: Pubkey) -> Instruction {
+    Instruction::new_with_borsh(program_id, &63u8, vec![])
+}
+
+pub fn find_tag_cloud_pda(program_id: &Pubkey) -> Pubkey {
+    let (tag_cloud_pda, _tag_cloud_bump) = Pubkey::find_program_address(&[b"tagcloud"], program_id);
+
+    tag_cloud_pda
+}
+
+pub fn find_tag_cloud_record_pda(review_pda: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (record_pda, _record_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"tagcloud"], program_id
+    );
+
+    record_pda
+}
+
+pub fn create_update_tag_cloud_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(115u8, UpdateTagCloudPayload {
+            title,
+        }), 
+        vec![
+            AccountMeta::new(reviewer, true),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new(find_tag_cloud_pda(&program_id), false),
+            AccountMeta::new(find_tag_cloud_record_pda(&review_pda, &program_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn create_initialize_config_ix(admin: Pubkey, program_id: Pubkey) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &5u8, 
+        vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+            AccountMeta::new_readonly(get_program_data_address(&program_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn create_set_reward_mint_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    reward_mint: Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(6u8, RewardMintPayload {
+            reward_mint,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+            AccountMeta::new_readonly(reward_mint, false),
+        ],
+    )
+}
+
+pub fn create_set_comment_mint_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    comment_mint: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new(find_config_pda(&program_id), false),
+    ];
+
+    if let Some(comment_mint) = comment_mint {
+        accounts.push(AccountMeta::new_readonly(comment_mint, false));
+    }
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(20u8, CommentMintPayload {
+            comment_mint,
+        }), 
+        accounts,
+    )
+}
+
+// Freezes `token_account`, a holder's account of the PDA mint
+pub fn create_freeze_token_mint_ix(admin: Pubkey, program_id: Pubkey, token_account: Pubkey) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &129u8, 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new_readonly(mint_pda(&program_id).0, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(freeze_auth_pda(&program_id).0, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+pub fn create_thaw_token_mint_ix(admin: Pubkey, program_id: Pubkey, token_account: Pubkey) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &130u8, 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new_readonly(mint_pda(&program_id).0, false),
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(freeze_auth_pda(&program_id).0, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+pub fn find_reward_tracker_pda(user: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (tracker_pda, _tracker_bump) = Pubkey::find_program_address(
+        &[b"rewards", user.as_ref()], program_id
+    );
+
+    tracker_pda
+}
+
+pub fn create_set_claim_rewards_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    enabled: bool,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(8u8, ClaimRewardsPayload {
+            enabled,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_set_skip_revoked_rewards_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    enabled: bool,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(13u8, SkipRevokedRewardsPayload {
+            enabled,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_mark_sponsored_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(114u8, MarkSponsoredPayload {
+            title,
+        }), 
+        vec![
+            AccountMeta::new_readonly(reviewer, true),
+            AccountMeta::new(review_pda, false),
+        ],
+    )
+}
+
+pub fn create_set_callback_program_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    callback_program_id: Option<Pubkey>,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(113u8, CallbackProgramPayload {
+            program_id: callback_program_id,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_set_min_description_len_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    min_len: u32,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(17u8, MinDescriptionLenPayload {
+            min_len,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_claim_rewards_ix(
+    user: Pubkey,
+    program_id: Pubkey,
+    mint: Pubkey,
+    user_ata: Pubkey,
+) -> Instruction {
+    let (mint_auth, _mint_auth_bump) = mint_auth_pda(&program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &7u8, 
+        vec![
+            AccountMeta::new_readonly(user, true),
+            AccountMeta::new(find_reward_tracker_pda(&user, &program_id), false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(mint_auth, false),
+            AccountMeta::new(user_ata, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_delete_movie_review_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    mint: Pubkey,
+    user_ata: Pubkey,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(9u8, DeleteReviewPayload {
+            title,
+        }), 
+        vec![
+            AccountMeta::new(reviewer, true),
+            AccountMeta::new(review_pda, false),
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(user_ata, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new(find_stats_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_rename_review_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    new_title: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], &program_id
+    );
+
+    let new_review_pda = find_review_pda(&reviewer, &new_title, &program_id);
+
+    let (new_counter_pda, _new_counter_bump) = Pubkey::find_program_address(
+        &[new_review_pda.as_ref(), b"comment"], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(16u8, RenameReviewPayload {
+            title,
+            new_title,
+        }), 
+        vec![
+            AccountMeta::new(reviewer, true),
+            AccountMeta::new(review_pda, false),
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(new_review_pda, false),
+            AccountMeta::new(new_counter_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn create_set_clawback_period_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    seconds: i64,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        program_id, 
+        &(10u8, ClawbackPeriodPayload {
+            seconds,
+        }), 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new(find_config_pda(&program_id), false),
+        ],
+    )
+}
+
+pub fn create_backfill_reward_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    review_pda: Pubkey,
+    mint: Pubkey,
+    reviewer_ata: Pubkey,
+) -> Instruction {
+    let (mint_auth, _mint_auth_bump) = mint_auth_pda(&program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &12u8, 
+        vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new(review_pda, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(mint_auth, false),
+            AccountMeta::new(reviewer_ata, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+    )
+}
+
+pub fn create_migrate_legacy_review_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &4u8, 
+        vec![
+            AccountMeta::new(reviewer, true),
+            AccountMeta::new(review_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_add_comment_instruction(
+    payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    comment: String,
+    comment_count: u64,
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    create_add_comment_on_review_ix(
+        payer,
+        payer,
+        program_id,
+        title,
+        comment,
+        comment_count,
+        mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+    )
+}
+
+// Same as `create_add_comment_instruction`, but comments on `reviewer`'s review
+// instead of the commenter's own
+#[allow(clippy::too_many_arguments)]
+pub fn create_add_comment_on_review_ix(
+    commenter: Pubkey,
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    comment: String,
+    comment_count: u64,
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], 
+        &program_id
+    );
+
+    let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), &comment_count.to_be_bytes()], 
+        &program_id
+    );
+
+    let comment_payload = (2u8, CommentPayload {
+        comment,
+    });
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &comment_payload, 
+        vec![
+            AccountMeta::new_readonly(commenter, true),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(comment_pda, false),
+            AccountMeta::new(mint, false),
+            AccountMeta::new_readonly(mint_auth, false),
+            AccountMeta::new(user_ata, false),
+            AccountMeta::new_readonly(system_program, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new(find_stats_pda(&program_id), false),
+        ]
+    )
+}
+
+// Same as `create_add_comment_instruction`, but `fee_payer` funds the comment
+// account while `commenter` only signs as the author
+#[allow(clippy::too_many_arguments)]
+pub fn create_sponsored_add_comment_ix(
+    commenter: Pubkey,
+    fee_payer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    comment: String,
+    comment_count: u64,
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    let mut add_comment_ix = create_add_comment_instruction(
+        commenter,
+        program_id,
+        title,
+        comment,
+        comment_count,
+        mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+    );
+    add_comment_ix.accounts.push(AccountMeta::new(fee_payer, true));
+
+    add_comment_ix
+}
+
+pub fn find_series_pda(owner: &Pubkey, program_id: &Pubkey, series_name: &str) -> Pubkey {
+    let (series_pda, _series_bump) = Pubkey::find_program_address(
+        &[owner.as_ref(), b"series", hash(series_name.as_bytes()).as_ref()], 
+        program_id
+    );
+
+    series_pda
+}
+
+pub fn create_series_ix(
+    owner: Pubkey,
+    program_id: Pubkey,
+    series_name: String,
+) -> Instruction {
+    let series_pda = find_series_pda(&owner, &program_id, &series_name);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(101u8, SeriesPayload {
+            series_name,
+        }), 
+        vec![
+            AccountMeta::new(owner, true),
+            AccountMeta::new(series_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_add_to_series_ix(
+    owner: Pubkey,
+    program_id: Pubkey,
+    series_name: String,
+    review_title: String,
+) -> Instruction {
+    let series_pda = find_series_pda(&owner, &program_id, &series_name);
+
+    let review_pda = find_review_pda(&owner, &review_title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(102u8, AddToSeriesPayload {
+            series_name,
+            review_title,
+        }), 
+        vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(series_pda, false),
+            AccountMeta::new_readonly(review_pda, false),
+        ]
+    )
+}
+
+pub fn create_finalize_series_ix(
+    owner: Pubkey,
+    program_id: Pubkey,
+    series_name: String,
+) -> Instruction {
+    let series_pda = find_series_pda(&owner, &program_id, &series_name);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(103u8, SeriesPayload {
+            series_name,
+        }), 
+        vec![
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new(series_pda, false),
+        ]
+    )
+}
+
+pub fn create_react_to_review_ix(
+    reactor: Pubkey,
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    reaction: u8,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (reaction_pda, _reaction_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), reactor.as_ref()], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(104u8, ReactionPayload {
+            title,
+            reviewer,
+            reaction,
+        }), 
+        vec![
+            AccountMeta::new(reactor, true),
+            AccountMeta::new(review_pda, false),
+            AccountMeta::new(reaction_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_set_comment_moderation_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    mode: u8,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(105u8, CommentModerationPayload {
+            title,
+            mode,
+        }), 
+        vec![
+            AccountMeta::new_readonly(reviewer, true),
+            AccountMeta::new(review_pda, false),
+        ]
+    )
+}
+
+pub fn create_approve_comment_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    comment_index: u64,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), &comment_index.to_be_bytes()], &program_id
+    );
+
+    let (approval_pda, _approval_bump) = Pubkey::find_program_address(
+        &[comment_pda.as_ref(), b"approval"], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(11u8, ApproveCommentPayload {
+            title,
+            comment_index,
+        }), 
+        vec![
+            AccountMeta::new(reviewer, true),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new_readonly(comment_pda, false),
+            AccountMeta::new(approval_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn find_pending_comment_pda(review_pda: &Pubkey, submitter: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (pending_pda, _pending_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), submitter.as_ref(), b"pending"], program_id
+    );
+
+    pending_pda
+}
+
+// `AddComment` on a `COMMENTS_REQUIRE_APPROVAL` review, which takes the
+// commenter's `PendingComment` in place of the next comment account
+#[allow(clippy::too_many_arguments)]
+pub fn create_add_pending_comment_ix(
+    commenter: Pubkey,
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    title: String,
+    comment: String,
+    mint: Pubkey,
+    mint_auth: Pubkey,
+    user_ata: Pubkey,
+    system_program: Pubkey,
+    token_program: Pubkey
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let mut instruction = create_add_comment_on_review_ix(
+        commenter,
+        reviewer,
+        program_id,
+        title,
+        comment,
+        0,
+        mint,
+        mint_auth,
+        user_ata,
+        system_program,
+        token_program,
+    );
+    instruction.accounts[3] = AccountMeta::new(
+        find_pending_comment_pda(&review_pda, &commenter, &program_id), false
+    );
+
+    instruction
+}
+
+pub fn create_approve_pending_comment_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    submitter: Pubkey,
+    title: String,
+    comment_index: u64,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], &program_id
+    );
+
+    let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), &comment_index.to_be_bytes()], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(131u8, ApprovePendingCommentPayload {
+            submitter,
+            title,
+        }), 
+        vec![
+            AccountMeta::new(reviewer, true),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new(counter_pda, false),
+            AccountMeta::new(find_pending_comment_pda(&review_pda, &submitter, &program_id), false),
+            AccountMeta::new(comment_pda, false),
+            AccountMeta::new(submitter, false),
+            AccountMeta::new(find_stats_pda(&program_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_reject_pending_comment_ix(
+    reviewer: Pubkey,
+    program_id: Pubkey,
+    submitter: Pubkey,
+    title: String,
+    reason: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(132u8, RejectPendingCommentPayload {
+            submitter,
+            title,
+            reason,
+        }), 
+        vec![
+            AccountMeta::new_readonly(reviewer, true),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new(find_pending_comment_pda(&review_pda, &submitter, &program_id), false),
+            AccountMeta::new(submitter, false),
+        ]
+    )
+}
+
+pub fn create_update_comment_text_ix(
+    commenter: Pubkey,
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    comment_index: u64,
+    comment: String,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (comment_pda, _comment_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), &comment_index.to_be_bytes()], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(15u8, UpdateCommentTextPayload {
+            title,
+            comment_index,
+            comment,
+        }), 
+        vec![
+            AccountMeta::new(commenter, true),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new(comment_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn find_nomination_pda(review_pda: &Pubkey, year: u16, program_id: &Pubkey) -> Pubkey {
+    let (nomination_pda, _nomination_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"nominate", &year.to_le_bytes()], program_id
+    );
+
+    nomination_pda
+}
+
+pub fn find_award_pda(category: &str, year: u16, program_id: &Pubkey) -> Pubkey {
+    let (award_pda, _award_bump) = Pubkey::find_program_address(
+        &[b"award", &year.to_le_bytes(), category.as_bytes()], program_id
+    );
+
+    award_pda
+}
+
+// `year` must match the cluster clock; it is only used to derive the
+// nomination address
+pub fn create_nominate_review_ix(
+    nominator: Pubkey,
+    program_id: Pubkey,
+    nominator_review: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    category: String,
+    year: u16,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(106u8, NominateReviewPayload {
+            reviewer,
+            title,
+            category,
+        }), 
+        vec![
+            AccountMeta::new(nominator, true),
+            AccountMeta::new_readonly(nominator_review, false),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new(find_nomination_pda(&review_pda, year, &program_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_vote_for_nomination_ix(
+    voter: Pubkey,
+    program_id: Pubkey,
+    voter_review: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    year: u16,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let nomination_pda = find_nomination_pda(&review_pda, year, &program_id);
+
+    let (vote_pda, _vote_bump) = Pubkey::find_program_address(
+        &[nomination_pda.as_ref(), voter.as_ref()], &program_id
+    );
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(107u8, VoteForNominationPayload {
+            reviewer,
+            title,
+            year,
+        }), 
+        vec![
+            AccountMeta::new(voter, true),
+            AccountMeta::new_readonly(voter_review, false),
+            AccountMeta::new(nomination_pda, false),
+            AccountMeta::new(vote_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_declare_award_winner_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    category: String,
+    year: u16,
+    nominations: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(admin, true),
+        AccountMeta::new_readonly(find_config_pda(&program_id), false),
+        AccountMeta::new(find_award_pda(&category, year, &program_id), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    accounts.extend(nominations.iter().map(|nomination| AccountMeta::new(*nomination, false)));
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(108u8, DeclareAwardWinnerPayload {
+            category,
+            year,
+        }), 
+        accounts,
+    )
+}
+
+pub fn find_merkle_root_pda(review_pda: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (merkle_pda, _merkle_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"merkle"], program_id
+    );
+
+    merkle_pda
+}
+
+// `authority` is the reviewer or the config admin
+pub fn create_update_merkle_root_ix(
+    authority: Pubkey,
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    root: [u8; 32],
+    num_comments: u64,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(111u8, UpdateMerkleRootPayload {
+            title,
+            root,
+            num_comments,
+        }), 
+        vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new(find_merkle_root_pda(&review_pda, &program_id), false),
+            AccountMeta::new_readonly(find_config_pda(&program_id), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ]
+    )
+}
+
+pub fn create_verify_comment_inclusion_ix(
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    comment_pda: Pubkey,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(112u8, VerifyCommentInclusionPayload {
+            title,
+            comment_pda,
+            proof,
+        }), 
+        vec![
+            AccountMeta::new_readonly(review_pda, false),
+            AccountMeta::new_readonly(find_merkle_root_pda(&review_pda, &program_id), false),
+        ]
+    )
+}
+
+// `candidates` are the comment PDAs from the stored counter onwards, in order
+pub fn create_sync_comment_counter_ix(
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    candidates: &[Pubkey],
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], &program_id
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(review_pda, false),
+        AccountMeta::new(counter_pda, false),
+    ];
+
+    accounts.extend(candidates.iter().map(|comment| AccountMeta::new_readonly(*comment, false)));
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(14u8, SyncCounterPayload {
+            title,
+        }), 
+        accounts,
+    )
+}
+
+pub fn create_sync_counter_ix(
+    admin: Pubkey,
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    title: String,
+    comments: &[Pubkey],
+) -> Instruction {
+    let review_pda = find_review_pda(&reviewer, &title, &program_id);
+
+    let (counter_pda, _counter_bump) = Pubkey::find_program_address(
+        &[review_pda.as_ref(), b"comment"], &program_id
+    );
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new_readonly(find_config_pda(&program_id), false),
+        AccountMeta::new_readonly(review_pda, false),
+        AccountMeta::new(counter_pda, false),
+    ];
+
+    accounts.extend(comments.iter().map(|comment| AccountMeta::new_readonly(*comment, false)));
+
+    Instruction::new_with_borsh(
+        program_id, 
+        &(109u8, SyncCounterPayload {
+            title,
+        }), 
+        accounts,
+    )
+}
@@ -0,0 +1,151 @@
+// Compute-unit regression guard.
+//
+// Each instruction is simulated before it is processed and the consumed
+// units are checked against a budget. Numbers are only meaningful under
+// `cargo test-sbf`, where the compiled .so is metered; the native fallback
+// used by plain `cargo test` reports far lower figures.
+
+use movie_review_program::{
+    processor::{process_instruction, TOKEN_PROGRAM_ID},
+    test_utils::*,
+};
+
+use solana_program_test::*;
+
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+};
+
+// Budgets leave roughly 2x headroom over the measured usage so that only
+// real regressions trip them. The runtime default is 200_000 per instruction.
+const INITIALIZE_MINT_BUDGET: u64 = 40_000;
+const ADD_MOVIE_REVIEW_BUDGET: u64 = 100_000;
+const UPDATE_MOVIE_REVIEW_BUDGET: u64 = 30_000;
+const ADD_COMMENT_BUDGET: u64 = 80_000;
+const CHANGE_REVIEW_LANGUAGE_BUDGET: u64 = 30_000;
+
+async fn measure(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    recent_blockhash: Hash,
+    instructions: &[Instruction],
+) -> u64 {
+    let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+    transaction.sign(&[payer], recent_blockhash);
+
+    let simulation = banks_client
+        .simulate_transaction(transaction.clone())
+        .await
+        .unwrap();
+
+    simulation.result.unwrap().unwrap();
+    let units_consumed = simulation.simulation_details.unwrap().units_consumed;
+
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    units_consumed
+}
+
+#[tokio::test]
+async fn test_compute_unit_budgets() {
+    let program_id = Pubkey::new_unique();
+
+    let program_test = ProgramTest::new(
+        "movie_review_program",
+        program_id,
+        processor!(process_instruction)
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let title = String::from("Captain America");
+
+    let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+    let user_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+    );
+
+    let mut results: Vec<(&str, u64, u64)> = Vec::new();
+
+    let units = measure(&mut banks_client, &payer, recent_blockhash, &[init_mint_ix]).await;
+    results.push(("InitializeMint", units, INITIALIZE_MINT_BUDGET));
+
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &mint,
+        &TOKEN_PROGRAM_ID
+    );
+    measure(&mut banks_client, &payer, recent_blockhash, &[create_ata_ix]).await;
+
+    let add_movie_review_ix = create_add_movie_review_ix(
+        payer.pubkey(),
+        program_id,
+        title.clone(),
+        3,
+        String::from("Liked the movie"),
+        mint,
+        mint_auth,
+        user_ata,
+        system_program::ID,
+        TOKEN_PROGRAM_ID
+    );
+    let units = measure(&mut banks_client, &payer, recent_blockhash, &[add_movie_review_ix]).await;
+    results.push(("AddMovieReview", units, ADD_MOVIE_REVIEW_BUDGET));
+
+    let update_movie_review_ix = create_update_movie_instruction(
+        payer.pubkey(),
+        program_id,
+        title.clone(),
+        2,
+        String::from("Didn't like the movie"),
+    );
+    let units = measure(&mut banks_client, &payer, recent_blockhash, &[update_movie_review_ix]).await;
+    results.push(("UpdateMovieReview", units, UPDATE_MOVIE_REVIEW_BUDGET));
+
+    let add_comment_ix = create_add_comment_instruction(
+        payer.pubkey(),
+        program_id,
+        title.clone(),
+        String::from("Totally agree!"),
+        0,
+        mint,
+        mint_auth,
+        user_ata,
+        system_program::ID,
+        TOKEN_PROGRAM_ID,
+    );
+    let units = measure(&mut banks_client, &payer, recent_blockhash, &[add_comment_ix]).await;
+    results.push(("AddComment", units, ADD_COMMENT_BUDGET));
+
+    let change_language_ix = create_change_review_language_ix(
+        payer.pubkey(),
+        program_id,
+        title,
+        *b"fr",
+    );
+    let units = measure(&mut banks_client, &payer, recent_blockhash, &[change_language_ix]).await;
+    results.push(("ChangeReviewLanguage", units, CHANGE_REVIEW_LANGUAGE_BUDGET));
+
+    println!("{:<24} {:>10} {:>10}", "instruction", "units", "budget");
+    for (name, units, budget) in &results {
+        println!("{:<24} {:>10} {:>10}", name, units, budget);
+    }
+
+    for (name, units, budget) in results {
+        assert!(
+            units <= budget,
+            "{} used {} compute units, {} over its budget of {}",
+            name,
+            units,
+            units - budget,
+            budget
+        );
+    }
+}
@@ -0,0 +1,239 @@
+// CPI from another program through the `cpi` helpers.
+//
+// The proxy below stands in for a program that reviews on behalf of its own
+// PDA. It signs for the PDA with `invoke_signed`, so the review program sees
+// a signer it never had a keypair for.
+
+use borsh::BorshDeserialize;
+
+use movie_review_program::{
+    cpi::{self, AddMovieReview, CpiContext, UpdateMovieReview},
+    processor::{process_instruction, TOKEN_PROGRAM_ID},
+    state::MovieAccountState,
+    test_utils::*,
+};
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh1::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    native_token::sol_to_lamports,
+    program_error::ProgramError,
+};
+
+use solana_program_test::*;
+
+use solana_sdk::{
+    account::Account,
+    pubkey::Pubkey,
+    signature::Signer,
+    system_program,
+    transaction::Transaction,
+};
+
+#[derive(BorshDeserialize)]
+enum ProxyInstruction {
+    AddReview { title: String, rating: u8, description: String },
+    UpdateReview { title: String, rating: u8, description: String },
+}
+
+fn proxy_processor(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = ProxyInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let (_reviewer, reviewer_bump) = Pubkey::find_program_address(&[b"reviewer"], program_id);
+    let signer_seeds: &[&[u8]] = &[b"reviewer", &[reviewer_bump]];
+
+    let account_info_iter = &mut accounts.iter();
+
+    let movie_program = next_account_info(account_info_iter)?;
+    let reviewer = next_account_info(account_info_iter)?;
+
+    match instruction {
+        ProxyInstruction::AddReview { title, rating, description } => {
+            let accounts = AddMovieReview {
+                reviewer: reviewer.clone(),
+                review: next_account_info(account_info_iter)?.clone(),
+                comment_counter: next_account_info(account_info_iter)?.clone(),
+                token_mint: next_account_info(account_info_iter)?.clone(),
+                mint_auth: next_account_info(account_info_iter)?.clone(),
+                user_ata: next_account_info(account_info_iter)?.clone(),
+                system_program: next_account_info(account_info_iter)?.clone(),
+                token_program: next_account_info(account_info_iter)?.clone(),
+                config: next_account_info(account_info_iter)?.clone(),
+                stats: next_account_info(account_info_iter)?.clone(),
+                profile: next_account_info(account_info_iter)?.clone(),
+                payer: Some(next_account_info(account_info_iter)?.clone()),
+                callback_program: None,
+            };
+
+            cpi::add_movie_review(
+                CpiContext::new_with_signer(movie_program.clone(), accounts, &[signer_seeds]),
+                title,
+                rating,
+                description,
+            )
+        },
+        ProxyInstruction::UpdateReview { title, rating, description } => {
+            let accounts = UpdateMovieReview {
+                reviewer: reviewer.clone(),
+                review: next_account_info(account_info_iter)?.clone(),
+                audit_log: next_account_info(account_info_iter)?.clone(),
+                system_program: next_account_info(account_info_iter)?.clone(),
+            };
+
+            cpi::update_movie_review(
+                CpiContext::new_with_signer(movie_program.clone(), accounts, &[signer_seeds]),
+                title,
+                rating,
+                description,
+            )
+        },
+    }
+}
+
+// Routes an instruction built for a wallet reviewer through the proxy. The
+// PDA cannot sign the outer transaction, so its signer flag is dropped.
+fn proxy_ix(
+    proxy_program_id: Pubkey,
+    program_id: Pubkey,
+    reviewer: Pubkey,
+    inner: Instruction,
+    data: Vec<u8>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new(reviewer, false),
+    ];
+    accounts.extend(inner.accounts.into_iter().skip(1).map(|mut meta| {
+        if meta.pubkey == reviewer {
+            meta.is_signer = false;
+        }
+
+        meta
+    }));
+
+    Instruction {
+        program_id: proxy_program_id,
+        accounts,
+        data,
+    }
+}
+
+#[tokio::test]
+async fn test_add_and_update_review_through_proxy() {
+    let program_id = Pubkey::new_unique();
+    let proxy_program_id = Pubkey::new_unique();
+
+    let (reviewer_pda, _reviewer_bump) = Pubkey::find_program_address(
+        &[b"reviewer"],
+        &proxy_program_id
+    );
+
+    let mut program_test = ProgramTest::new(
+        "movie_review_program",
+        program_id,
+        processor!(process_instruction)
+    );
+    program_test.add_program(
+        "cinema_dao_proxy",
+        proxy_program_id,
+        processor!(proxy_processor)
+    );
+    // Funded so the PDA can pay for the audit log on update
+    program_test.add_account(
+        reviewer_pda,
+        Account {
+            lamports: sol_to_lamports(1.0),
+            owner: system_program::ID,
+            ..Account::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+    let reviewer_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &reviewer_pda, &mint, &TOKEN_PROGRAM_ID
+    );
+
+    let title = String::from("Captain America");
+
+    let add_review_ix = proxy_ix(
+        proxy_program_id,
+        program_id,
+        reviewer_pda,
+        create_sponsored_add_movie_review_ix(
+            reviewer_pda,
+            payer.pubkey(),
+            program_id,
+            title.clone(),
+            4,
+            String::from("Liked the movie"),
+            mint,
+            mint_auth,
+            reviewer_ata,
+            system_program::ID,
+            TOKEN_PROGRAM_ID
+        ),
+        borsh::to_vec(&(0u8, title.clone(), 4u8, String::from("Liked the movie"))).unwrap(),
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[
+            init_mint_ix,
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer.pubkey(),
+                &reviewer_pda,
+                &mint,
+                &TOKEN_PROGRAM_ID
+            ),
+            add_review_ix,
+        ],
+        Some(&payer.pubkey()),
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let (review_pda, _review_bump) = Pubkey::find_program_address(
+        &[reviewer_pda.as_ref(), MovieAccountState::title_seed(&title).as_bytes()],
+        &program_id
+    );
+
+    let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+    let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+    assert_eq!(review_data.reviewer, reviewer_pda);
+    assert_eq!(review_data.rating, 4);
+
+    let update_review_ix = proxy_ix(
+        proxy_program_id,
+        program_id,
+        reviewer_pda,
+        create_update_movie_instruction(
+            reviewer_pda,
+            program_id,
+            title.clone(),
+            2,
+            String::from("Didn't hold up"),
+        ),
+        borsh::to_vec(&(1u8, title, 2u8, String::from("Didn't hold up"))).unwrap(),
+    );
+
+    let mut transaction = Transaction::new_with_payer(&[update_review_ix], Some(&payer.pubkey()));
+    transaction.sign(&[&payer], recent_blockhash);
+
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+    let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+    assert_eq!(review_data.reviewer, reviewer_pda);
+    assert_eq!(review_data.rating, 2);
+    assert_eq!(review_data.description, "Didn't hold up");
+}
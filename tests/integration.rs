@@ -0,0 +1,129 @@
+// End-to-end flows against the compiled program.
+//
+// Under `cargo test-sbf` the `SBF_OUT_DIR` environment variable is set and
+// `ProgramTest` loads `movie_review_program.so` instead of the native
+// processor, so these tests exercise the real SBF artifact (stack size,
+// syscalls, compute limits). Under plain `cargo test` they fall back to the
+// native processor.
+
+use movie_review_program::{
+    processor::{process_instruction, TOKEN_PROGRAM_ID},
+    test_utils::*,
+};
+
+use solana_program_test::*;
+
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_program,
+    transaction::Transaction,
+    hash::Hash,
+};
+
+async fn setup() -> (BanksClient, Keypair, Hash, Pubkey) {
+    let program_id = Pubkey::new_unique();
+
+    let program_test = ProgramTest::new(
+        "movie_review_program",
+        program_id,
+        processor!(process_instruction)
+    );
+
+    let (banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    (banks_client, payer, recent_blockhash, program_id)
+}
+
+#[tokio::test]
+async fn test_sbf_initialize_mint() {
+    let (mut banks_client, payer, recent_blockhash, program_id) = setup().await;
+
+    let (_mint, _mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+    let mut transaction = Transaction::new_with_payer(
+        &[init_mint_ix],
+        Some(&payer.pubkey())
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_sbf_review_update_and_comment() {
+    let (mut banks_client, payer, recent_blockhash, program_id) = setup().await;
+
+    let (mint, mint_auth, init_mint_ix) = create_init_mint_ix(payer.pubkey(), &program_id);
+
+    let title = String::from("Captain America");
+
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &mint,
+        &TOKEN_PROGRAM_ID
+    );
+
+    let user_ata = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &payer.pubkey(), &mint, &TOKEN_PROGRAM_ID
+    );
+
+    let add_movie_review_ix = create_add_movie_review_ix(
+        payer.pubkey(),
+        program_id,
+        title.clone(),
+        3,
+        String::from("Liked the movie"),
+        mint,
+        mint_auth,
+        user_ata,
+        system_program::ID,
+        TOKEN_PROGRAM_ID
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[init_mint_ix, create_ata_ix, add_movie_review_ix],
+        Some(&payer.pubkey())
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let update_movie_review_ix = create_update_movie_instruction(
+        payer.pubkey(),
+        program_id,
+        title.clone(),
+        2,
+        String::from("Didn't like the movie"),
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[update_movie_review_ix],
+        Some(&payer.pubkey())
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let add_comment_ix = create_add_comment_instruction(
+        payer.pubkey(),
+        program_id,
+        title,
+        String::from("Totally agree!"),
+        0,
+        mint,
+        mint_auth,
+        user_ata,
+        system_program::ID,
+        TOKEN_PROGRAM_ID,
+    );
+
+    let mut transaction = Transaction::new_with_payer(
+        &[add_comment_ix],
+        Some(&payer.pubkey())
+    );
+    transaction.sign(&[&payer], recent_blockhash);
+
+    assert!(banks_client.process_transaction(transaction).await.is_ok());
+}
@@ -0,0 +1,73 @@
+// Flows that start from accounts written straight into the bank with the
+// `test_utils` fixtures, rather than built up through earlier instructions.
+
+use movie_review_program::{
+    processor::process_instruction,
+    state::MovieAccountState,
+    test_utils::*,
+};
+
+use solana_program::borsh1::try_from_slice_unchecked;
+
+use solana_program_test::*;
+
+use solana_sdk::{
+    account::Account,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Signer,
+    transaction::Transaction,
+};
+
+#[tokio::test]
+async fn test_update_seeded_review() {
+    let program_id = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::new(
+        "movie_review_program",
+        program_id,
+        processor!(process_instruction)
+    );
+
+    let reviewer = add_funded_keypair(&mut program_test);
+    let title = "Captain America";
+
+    let review = MovieAccountState {
+        created_at: 1_700_000_000,
+        ..review_fixture(reviewer.pubkey(), title, 3, "Liked the movie")
+    };
+    let review_pda = find_review_pda(&reviewer.pubkey(), title, &program_id);
+
+    program_test.add_account(
+        review_pda,
+        Account {
+            lamports: Rent::default().minimum_balance(MovieAccountState::LEN),
+            data: review_account_data(&review, MovieAccountState::LEN),
+            owner: program_id,
+            ..Account::default()
+        },
+    );
+
+    let (mut banks_client, _payer, recent_blockhash) = program_test.start().await;
+
+    let mut transaction = Transaction::new_with_payer(
+        &[create_update_movie_instruction(
+            reviewer.pubkey(),
+            program_id,
+            title.to_string(),
+            5,
+            String::from("Better on a second watch"),
+        )],
+        Some(&reviewer.pubkey())
+    );
+    transaction.sign(&[&reviewer], recent_blockhash);
+
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let review_account = banks_client.get_account(review_pda).await.unwrap().unwrap();
+    let review_data: MovieAccountState = try_from_slice_unchecked(&review_account.data).unwrap();
+    assert_eq!(review_data.rating, 5);
+    assert_eq!(review_data.description, "Better on a second watch");
+    assert_eq!(review_data.reviewer, reviewer.pubkey());
+    assert_eq!(review_data.created_at, 1_700_000_000);
+}